@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        background::{BackgroundCtx, BackgroundModel}, roi::RoiCtx, speed::SpeedCtx,
+        ContextRead, ContextWrite, DetectingContoursCvCtx, EvalResult,
+    },
+    domain::{Error, Eval, Image, RwLock},
+};
+///
+/// Suppresses the static background in the contours image
+/// - Each frame masks out the gradients matching the static structure of the [BackgroundModel],
+///   so `EdgeDetection` doesn't take the cable trays or the sheave for the rope edges
+/// - The model learned only on the frames of the rope measured moving by `Speed`,
+///   outside of the rope mask of `DetectingContoursCv`
+/// - Must be inserted after `DetectingContoursCv`, replaces it's result
+pub struct Background {
+    dbg: Dbg,
+    model: Arc<RwLock<BackgroundModel>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Background {
+    ///
+    /// New instance [Background]
+    /// - `model` - background model, shared between the frames of the same camera
+    pub fn new(model: Arc<RwLock<BackgroundModel>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Background"),
+            model,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Background {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let contours = ContextRead::<DetectingContoursCvCtx>::read(&ctx).clone();
+                let src = contours.result;
+                let y = ContextRead::<RoiCtx>::read(&ctx).y;
+                let speed = ContextRead::<SpeedCtx>::read(&ctx);
+                let learned = speed.overlap.is_some() && !speed.stationary;
+                let (mat, suppressed) = match self.model.write().apply(&src.mat, &contours.mask.mat, y, learned) {
+                    Ok(result) => result,
+                    Err(err) => return Err(error.pass_with("Contours image format error", err.to_string())),
+                };
+                log::debug!("{}.eval | Suppressed: {} px, learned: {}", self.dbg, suppressed, learned);
+                let result = DetectingContoursCvCtx {
+                    result: Image {
+                        width: src.width,
+                        height: src.height,
+                        timestamp: src.timestamp,
+                        mat,
+                        bytes: src.bytes,
+                    },
+                    mask: contours.mask,
+                };
+                match ctx.write(result) {
+                    Ok(ctx) => ctx.write(BackgroundCtx { suppressed, learned }),
+                    Err(err) => Err(error.pass(err)),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
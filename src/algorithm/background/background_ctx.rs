@@ -0,0 +1,9 @@
+///
+/// Store result of `Background`
+/// - `suppressed` - number of the contour pixels masked out as the static background
+/// - `learned` - true if the model learned on the frame, the rope moving
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BackgroundCtx {
+    pub suppressed: usize,
+    pub learned: bool,
+}
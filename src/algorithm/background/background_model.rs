@@ -0,0 +1,144 @@
+use std::{fs, path::{Path, PathBuf}};
+use opencv::core::{Mat, MatTrait, MatTraitConst};
+use crate::domain::Error;
+///
+/// Running model of the static background of the contours image
+/// - `median` - approximate running median of the gradient of each pixel,
+///   moved by one level towards the current gradient on every frame
+/// - `deviation` - approximate running median of the gradient deviation from the `median`
+/// - `width`, `height` - size of the modeled image, px
+/// - `y` - position of the modeled image on the full frame, the model is shifted following the cropped strip
+///
+/// Strands of the running rope keep it's gradients changing, so only the static structure
+/// gets the small deviation, the stopped rope would look static, so the model isn't learned on it
+///
+/// Stored in the binary file: `width`, `height`, `y` as little endian u32, followed by the `median` and the `deviation`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BackgroundModel {
+    pub width: usize,
+    pub height: usize,
+    pub y: usize,
+    pub median: Vec<u8>,
+    pub deviation: Vec<u8>,
+}
+//
+//
+impl BackgroundModel {
+    /// Deviation of the pixel not learned yet
+    const UNLEARNED: u8 = 32;
+    /// Pixel is static if it's deviation doesn't exceed this
+    const STABLE: u8 = 4;
+    /// Only the strong gradients of the static structure suppressed
+    const STRONG: u8 = 32;
+    /// Allowed difference of the current gradient from the static one
+    const TOLERANCE: u8 = 8;
+    /// Size of the header of the binary file, bytes
+    const HEADER: usize = 12;
+    ///
+    /// Returns path of the background model file of the `camera` in the `dir`
+    pub fn path(dir: impl AsRef<Path>, camera: &str) -> PathBuf {
+        dir.as_ref().join(format!("{}.background.bin", camera.trim_start_matches('/').replace('/', "-")))
+    }
+    ///
+    /// Returns true if the pixel `i` holds the static background matching the `gradient`
+    fn is_static(&self, i: usize, gradient: u8) -> bool {
+        self.median[i] >= Self::STRONG
+            && self.deviation[i] <= Self::STABLE
+            && gradient.abs_diff(self.median[i]) <= self.deviation[i].saturating_add(Self::TOLERANCE)
+    }
+    ///
+    /// Shifts the model to the image of the `width` x `height` placed at the row `y` of the full frame,
+    /// pixels not covered by the previous model are initialised by the `grad` and marked as not learned
+    fn align(&mut self, grad: &Mat, width: usize, height: usize, y: usize) -> Result<(), opencv::Error> {
+        let mut median = Vec::with_capacity(width * height);
+        let mut deviation = Vec::with_capacity(width * height);
+        for row in 0..height {
+            let previous = (row + y).checked_sub(self.y).filter(|row| *row < self.height && width == self.width);
+            for col in 0..width {
+                match previous {
+                    Some(previous) => {
+                        median.push(self.median[previous * width + col]);
+                        deviation.push(self.deviation[previous * width + col]);
+                    }
+                    None => {
+                        median.push(*grad.at_2d::<u8>(row as i32, col as i32)?);
+                        deviation.push(Self::UNLEARNED);
+                    }
+                }
+            }
+        }
+        *self = Self { width, height, y, median, deviation };
+        Ok(())
+    }
+    ///
+    /// Masks out the static background of the contours image `grad` placed at the row `y` of the full frame,
+    /// returns it together with the number of pixels masked
+    /// - `learn` - the model updated by the image, false if the rope isn't measured moving
+    /// - `mask` - binary mask of the rope, the pixels of the rope never learned, empty if unknown
+    pub fn apply(&mut self, grad: &Mat, mask: &Mat, y: usize, learn: bool) -> Result<(Mat, usize), opencv::Error> {
+        let (width, height) = (grad.cols() as usize, grad.rows() as usize);
+        if width != self.width || height != self.height || y != self.y {
+            self.align(grad, width, height, y)?;
+        }
+        let masked = mask.cols() == grad.cols() && mask.rows() == grad.rows();
+        let mut dst = grad.try_clone()?;
+        let mut suppressed = 0;
+        for row in 0..height {
+            for col in 0..width {
+                let i = row * width + col;
+                let gradient = *grad.at_2d::<u8>(row as i32, col as i32)?;
+                if self.is_static(i, gradient) && gradient > 0 {
+                    *dst.at_2d_mut::<u8>(row as i32, col as i32)? = 0;
+                    suppressed += 1;
+                }
+                if !learn || (masked && *mask.at_2d::<u8>(row as i32, col as i32)? > 0) {
+                    continue;
+                }
+                let median = self.median[i];
+                self.median[i] = match gradient.cmp(&median) {
+                    std::cmp::Ordering::Greater => median + 1,
+                    std::cmp::Ordering::Less => median - 1,
+                    std::cmp::Ordering::Equal => median,
+                };
+                let spread = gradient.abs_diff(self.median[i]);
+                let deviation = self.deviation[i];
+                self.deviation[i] = match spread.cmp(&deviation) {
+                    std::cmp::Ordering::Greater => deviation + 1,
+                    std::cmp::Ordering::Less => deviation - 1,
+                    std::cmp::Ordering::Equal => deviation,
+                };
+            }
+        }
+        Ok((dst, suppressed))
+    }
+    ///
+    /// Reads [BackgroundModel] from the binary file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let error = Error::new("BackgroundModel", "read");
+        let bytes = fs::read(path.as_ref())
+            .map_err(|err| error.pass_with(format!("Read {:?} error", path.as_ref()), err.to_string()))?;
+        let field = |i: usize| bytes.get(4 * i..4 * i + 4)
+            .and_then(|field| field.try_into().ok())
+            .map(|field| u32::from_le_bytes(field) as usize);
+        match (field(0), field(1), field(2)) {
+            (Some(width), Some(height), Some(y)) if bytes.len() == Self::HEADER + 2 * width * height => {
+                let (median, deviation) = bytes[Self::HEADER..].split_at(width * height);
+                Ok(Self { width, height, y, median: median.to_vec(), deviation: deviation.to_vec() })
+            }
+            _ => Err(error.err(format!("Parse {:?} error: unexpected size {} bytes", path.as_ref(), bytes.len()))),
+        }
+    }
+    ///
+    /// Writes [BackgroundModel] into the binary file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let error = Error::new("BackgroundModel", "write");
+        let mut bytes = Vec::with_capacity(Self::HEADER + self.median.len() + self.deviation.len());
+        for field in [self.width, self.height, self.y] {
+            bytes.extend_from_slice(&(field as u32).to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.median);
+        bytes.extend_from_slice(&self.deviation);
+        fs::write(path.as_ref(), bytes)
+            .map_err(|err| error.pass_with(format!("Write {:?} error", path.as_ref()), err.to_string()))
+    }
+}
@@ -0,0 +1,12 @@
+//!
+//! Static background suppression
+//! - Gradients of the static structure behind the rope (cable trays, housing, sheave)
+//!   learned by the running model and masked out of the contours
+//! - The model learned on the running rope only and persisted between the restarts
+mod background_model;
+mod background_ctx;
+mod background;
+
+pub use background_model::*;
+pub use background_ctx::*;
+pub use background::*;
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        baseline::{BaselineCtx, BaselineModel},
+        geometry_defect::{Defect, DefectSide, GeometryDefectType},
+        position::PositionCtx,
+        vibration::VibrationCtx,
+        ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Mad, MadCtx, Side,
+    },
+    domain::{Error, Eval, RwLock},
+};
+///
+/// Compares the rope diameter of the frame with the long-term [BaselineModel]
+/// - Median statistics of the single frame don't see the whole frame of necking,
+///   the baseline learned over many frames does
+/// - Frame compared with the baseline of the section of the rope at the position of the frame,
+///   with the baseline of the whole rope until the section is learned or if the position unknown
+/// - Frames within the `limit` update the baseline, the exceeding ones are rejected and reported
+///   as [GeometryDefectType::Compressing] or [GeometryDefectType::Expansion] along the frame
+/// - While warming up the frames beyond the `limit` of the median of the warm-up are rejected
+/// - Baseline of the whole rope learned again, if the frames are rejected [BaselineModel::RELEARN] times in a row
+///   with the position unknown or along the several sections, the frames of the stopped or long defect never learned
+/// - Frames unreliable by the `Vibration` compared, but never learned
+pub struct Baseline {
+    dbg: Dbg,
+    limit: f64,
+    model: Arc<RwLock<BaselineModel>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Baseline {
+    ///
+    /// New instance [Baseline]
+    /// - `limit` - allowed deviation of the frame diameter, part of the baseline diameter
+    /// - `model` - baseline of the rope, shared between the frames of the same camera
+    pub fn new(limit: f64, model: Arc<RwLock<BaselineModel>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Baseline"),
+            limit,
+            model,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Baseline {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let (upper, lower) = (edges.get(Side::Upper), edges.get(Side::Lower));
+                let widths: Vec<f64> = upper.iter()
+                    .zip(lower.iter())
+                    .map(|(upper, lower)| (upper.y - lower.y).abs())
+                    .collect();
+                if widths.is_empty() {
+                    let position = ContextRead::<PositionCtx>::read(&ctx).position;
+                    let baseline = self.model.read().reference(position).0;
+                    return ctx.write(BaselineCtx { baseline, ..Default::default() });
+                }
+                let mad = Mad::new();
+                let diameter = Eval::<Vec<f64>, MadCtx>::eval(&mad, widths).median;
+                let roughness = (
+                    Eval::<Vec<f64>, MadCtx>::eval(&mad, upper.iter().map(|dot| dot.y).collect()).mad
+                    + Eval::<Vec<f64>, MadCtx>::eval(&mad, lower.iter().map(|dot| dot.y).collect()).mad
+                ) / 2.0;
+                let position = ContextRead::<PositionCtx>::read(&ctx).position;
+                let reliable = ContextRead::<VibrationCtx>::read(&ctx).reliable;
+                let mut model = self.model.write();
+                let (baseline, nominal_roughness) = model.reference(position);
+                let (deviation, roughness_deviation) = match model.ready() && baseline > 0.0 {
+                    true => (
+                        (diameter - baseline) / baseline,
+                        if nominal_roughness > 0.0 { (roughness - nominal_roughness) / nominal_roughness } else { 0.0 },
+                    ),
+                    false => (0.0, 0.0),
+                };
+                let exceeds = deviation.abs() > self.limit;
+                let learned = reliable && !exceeds && model.admits(diameter, self.limit);
+                if learned {
+                    model.learn(diameter, roughness, position);
+                } else if reliable && model.reject(position) {
+                    log::warn!("{}.eval | Frames rejected {} times in a row, baseline of the whole rope learned again", self.dbg, BaselineModel::RELEARN);
+                }
+                let result = match exceeds {
+                    true => {
+                        let kind = if deviation < 0.0 { GeometryDefectType::Compressing } else { GeometryDefectType::Expansion };
+                        log::debug!("{}.eval | {:?} of the frame: {:.1} px vs baseline {:.1} px", self.dbg, kind, diameter, baseline);
+                        Some(Defect::new(
+                            kind,
+                            DefectSide::Both,
+                            upper.first().map_or(0, |dot| dot.x as usize),
+                            upper.last().map_or(0, |dot| dot.x as usize),
+                            (diameter - baseline).abs(),
+                            deviation.abs(),
+                            Defect::confidence(deviation, self.limit),
+                        ))
+                    }
+                    false => None,
+                };
+                ctx.write(BaselineCtx {
+                    diameter,
+                    roughness,
+                    baseline: model.reference(position).0,
+                    deviation,
+                    roughness_deviation,
+                    learned,
+                    result,
+                })
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
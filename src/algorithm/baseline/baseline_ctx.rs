@@ -0,0 +1,20 @@
+use crate::algorithm::geometry_defect::Defect;
+///
+/// Store result of `Baseline`
+/// - `diameter` - median diameter of the rope on the frame, px
+/// - `roughness` - spread (MAD) of the rope edges on the frame, px
+/// - `baseline` - nominal diameter of the rope at the position of the frame learned over the frames, px
+/// - `deviation` - `diameter` deviation from the `baseline`, part of the `baseline`, 0.0 until the baseline is established
+/// - `roughness_deviation` - `roughness` deviation from the nominal one, part of the nominal, 0.0 until the baseline is established
+/// - `learned` - true if the frame updated the baseline, the frames exceeding the limit and the unreliable ones are rejected
+/// - `result` - [Defect] along the frame, if the frame diameter exceeds the limit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BaselineCtx {
+    pub diameter: f64,
+    pub roughness: f64,
+    pub baseline: f64,
+    pub deviation: f64,
+    pub roughness_deviation: f64,
+    pub learned: bool,
+    pub result: Option<Defect>,
+}
@@ -0,0 +1,165 @@
+use std::{collections::{BTreeMap, BTreeSet}, fs, path::{Path, PathBuf}};
+use serde::{Deserialize, Serialize};
+use crate::domain::Error;
+///
+/// Nominal state of the section of the rope learned over the frames passed it
+/// - `diameter` - nominal diameter of the section, px
+/// - `roughness` - nominal spread (MAD) of the rope edges of the section, px
+/// - `frames` - number of the frames learned
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BaselineSection {
+    pub diameter: f64,
+    pub roughness: f64,
+    pub frames: usize,
+}
+///
+/// Nominal state of the rope learned over the frames
+/// - `diameter` - nominal diameter of the rope, px
+/// - `roughness` - nominal spread (MAD) of the rope edges, px
+/// - `frames` - number of the frames learned
+/// - `warmup` - diameters of the frames learned while warming up, px, the outliers rejected against their median
+/// - `rejected` - number of the last frames rejected in a row
+/// - `rejected_sections` - indexes of the sections the last frames rejected in a row at, if the position known
+/// - `sections` - baselines of the [BaselineModel::SECTION] long sections of the rope, by the index of the section
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BaselineModel {
+    pub diameter: f64,
+    pub roughness: f64,
+    pub frames: usize,
+    #[serde(default)]
+    pub warmup: Vec<f64>,
+    #[serde(default)]
+    pub rejected: usize,
+    #[serde(default)]
+    pub rejected_sections: BTreeSet<i64>,
+    #[serde(default)]
+    pub sections: BTreeMap<i64, BaselineSection>,
+}
+//
+//
+impl BaselineModel {
+    /// Number of the frames to establish the baseline, averaged equally
+    pub const WARMUP: usize = 50;
+    /// Number of the frames to establish the baseline of the section
+    pub const SECTION_WARMUP: usize = 10;
+    /// Length of the section of the rope, m
+    pub const SECTION: f64 = 1.0;
+    /// Number of the warm-up frames, from which the outliers are rejected
+    pub const SEED: usize = 5;
+    /// Number of the frames rejected in a row, after which the baseline is considered wrong and learned again
+    pub const RELEARN: usize = 250;
+    /// Number of the sections the frames rejected in a row at, after which the baseline is considered wrong,
+    /// the frames rejected at fewer sections are the defect of the rope, stopped or long
+    pub const RELEARN_SECTIONS: usize = 3;
+    /// Weight of the new frame, once the baseline is established
+    const RATE: f64 = 0.01;
+    ///
+    /// Returns true if the baseline is established and the frames can be compared with it
+    pub fn ready(&self) -> bool {
+        self.frames >= Self::WARMUP
+    }
+    ///
+    /// Returns index of the section at the `position` along the rope, m
+    fn section(position: f64) -> i64 {
+        (position / Self::SECTION).floor() as i64
+    }
+    ///
+    /// Returns weight of the new frame, equal weights while warming up, then slow exponential adaptation
+    fn rate(frames: usize, warmup: usize) -> f64 {
+        match frames >= warmup {
+            true => Self::RATE,
+            false => 1.0 / (frames + 1) as f64,
+        }
+    }
+    ///
+    /// Returns nominal diameter and roughness at the `position` along the rope, px
+    /// - Baseline of the section, if it's established, of the whole rope otherwise
+    pub fn reference(&self, position: Option<f64>) -> (f64, f64) {
+        match position.and_then(|position| self.sections.get(&Self::section(position))) {
+            Some(section) if section.frames >= Self::SECTION_WARMUP => (section.diameter, section.roughness),
+            _ => (self.diameter, self.roughness),
+        }
+    }
+    ///
+    /// Returns true if the `diameter` of the warm-up frame is within the `limit` of the median of the warm-up,
+    /// always true until the [BaselineModel::SEED] frames learned and once the baseline is established
+    pub fn admits(&self, diameter: f64, limit: f64) -> bool {
+        if self.ready() || self.warmup.len() < Self::SEED {
+            return true;
+        }
+        let mut warmup = self.warmup.clone();
+        warmup.sort_by(f64::total_cmp);
+        let len = warmup.len();
+        let median = match len % 2 {
+            1 => warmup[len / 2],
+            _ => (warmup[len / 2 - 1] + warmup[len / 2]) / 2.0,
+        };
+        median > 0.0 && ((diameter - median) / median).abs() <= limit
+    }
+    ///
+    /// Updates the baseline by the frame `diameter` and edge `roughness`, px, at the `position` along the rope, m
+    pub fn learn(&mut self, diameter: f64, roughness: f64, position: Option<f64>) {
+        let rate = Self::rate(self.frames, Self::WARMUP);
+        self.diameter += rate * (diameter - self.diameter);
+        self.roughness += rate * (roughness - self.roughness);
+        self.frames += 1;
+        self.rejected = 0;
+        self.rejected_sections.clear();
+        match self.ready() {
+            true => self.warmup.clear(),
+            false => self.warmup.push(diameter),
+        }
+        if let Some(position) = position {
+            let section = self.sections.entry(Self::section(position)).or_default();
+            let rate = Self::rate(section.frames, Self::SECTION_WARMUP);
+            section.diameter += rate * (diameter - section.diameter);
+            section.roughness += rate * (roughness - section.roughness);
+            section.frames += 1;
+        }
+    }
+    ///
+    /// Counts the frame rejected at the `position` along the rope, m,
+    /// returns true if the baseline of the whole rope is dropped to be learned again,
+    /// the frames rejected [BaselineModel::RELEARN] times in a row with the position unknown
+    /// or at [BaselineModel::RELEARN_SECTIONS] sections at least
+    /// - Learned sections are never dropped, the replaced rope needs the baseline file removed
+    pub fn reject(&mut self, position: Option<f64>) -> bool {
+        self.rejected += 1;
+        if let Some(position) = position {
+            self.rejected_sections.insert(Self::section(position));
+        }
+        let spread = self.rejected_sections.is_empty() || self.rejected_sections.len() >= Self::RELEARN_SECTIONS;
+        match self.rejected >= Self::RELEARN && spread {
+            true => {
+                *self = Self { sections: std::mem::take(&mut self.sections), ..Self::default() };
+                true
+            }
+            false => false,
+        }
+    }
+    ///
+    /// Returns path of the baseline file of the `camera` in the `dir`
+    pub fn path(dir: impl AsRef<Path>, camera: &str) -> PathBuf {
+        dir.as_ref().join(format!("{}.baseline.json", camera.trim_start_matches('/').replace('/', "-")))
+    }
+    ///
+    /// Reads [BaselineModel] from the json file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let error = Error::new("BaselineModel", "read");
+        match fs::read_to_string(path.as_ref()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|err| error.pass_with(format!("Parse {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with(format!("Read {:?} error", path.as_ref()), err.to_string())),
+        }
+    }
+    ///
+    /// Writes [BaselineModel] into the json file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let error = Error::new("BaselineModel", "write");
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => fs::write(path.as_ref(), json)
+                .map_err(|err| error.pass_with(format!("Write {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with("Serialize error", err.to_string())),
+        }
+    }
+}
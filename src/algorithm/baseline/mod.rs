@@ -0,0 +1,13 @@
+//!
+//! Long-term baseline of the rope
+//! - Nominal diameter and edge roughness of the rope learned over many frames as the rope runs,
+//!   for the whole rope and for each section along it,
+//!   so the uniformly thinner or thicker frame is distinguished from the normal one
+//! - The baseline persisted between the restarts
+mod baseline_model;
+mod baseline_ctx;
+mod baseline;
+
+pub use baseline_model::*;
+pub use baseline_ctx::*;
+pub use baseline::*;
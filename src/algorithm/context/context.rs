@@ -1,5 +1,10 @@
 use crate::algorithm::{
-    geometry_defect::GeometryDefectCtx, width_emissions::WidthEmissionsCtx, DetectingContoursCvCtx, EdgeDetectionCtx, InitialCtx
+    geometry_defect::GeometryDefectCtx, surface_defect::SurfaceDefectCtx, width_emissions::WidthEmissionsCtx,
+    DetectingContoursCvCtx, EdgeDetectionCtx, InitialCtx, lay_length::LayLengthCtx, diameter::DiameterCtx,
+    rope_axis::RopeAxisCtx, profile_defect::ProfileDefectCtx, orientation::OrientationCtx, roi::RoiCtx,
+    illumination::IlluminationCtx, background::BackgroundCtx, baseline::BaselineCtx, discard::DiscardCtx,
+    position::PositionCtx, speed::SpeedCtx, panorama::PanoramaCtx, defect_tracker::DefectTrackerCtx,
+    vibration::VibrationCtx,
 };
 use super::testing_ctx::TestingCtx;
 ///
@@ -18,6 +23,38 @@ pub struct Context {
     pub(super) width_emissions: WidthEmissionsCtx,
     /// result of detecting [GeometryDefect's](design/theory/geometry_rope_defects.md)
     pub(super) geometry_defect: GeometryDefectCtx,
+    /// result of detecting corrosion and surface wear
+    pub(super) surface_defect: SurfaceDefectCtx,
+    /// lay length, strand count and lay direction
+    pub(super) lay_length: LayLengthCtx,
+    /// absolute diameter of the rope, mm
+    pub(super) diameter: DiameterCtx,
+    /// dynamic and static axes of the rope
+    pub(super) rope_axis: RopeAxisCtx,
+    /// result of detecting groove and mound defect's
+    pub(super) profile_defect: ProfileDefectCtx,
+    /// angle of the rope axis and the frame normalisation
+    pub(super) orientation: OrientationCtx,
+    /// rope working area the frame cropped to
+    pub(super) roi: RoiCtx,
+    /// brightness of the frame and the correction applied
+    pub(super) illumination: IlluminationCtx,
+    /// static background masked out of the contours
+    pub(super) background: BackgroundCtx,
+    /// frame diameter compared with the long-term baseline
+    pub(super) baseline: BaselineCtx,
+    /// verdict on the rope condition by the discard criteria
+    pub(super) discard: DiscardCtx,
+    /// position of the frame along the rope and the direction of travel
+    pub(super) position: PositionCtx,
+    /// speed, direction and coverage of the rope
+    pub(super) speed: SpeedCtx,
+    /// place of the frame on the panorama of the rope
+    pub(super) panorama: PanoramaCtx,
+    /// defect events tracked over the frames
+    pub(super) defect_tracker: DefectTrackerCtx,
+    /// lateral vibration and swing of the rope
+    pub(super) vibration: VibrationCtx,
     ///
     /// Uset for testing only
     #[allow(dead_code)]
@@ -36,6 +73,22 @@ impl Context {
             edge_detection: EdgeDetectionCtx::default(),
             width_emissions: WidthEmissionsCtx::default(),
             geometry_defect: GeometryDefectCtx::default(),
+            surface_defect: SurfaceDefectCtx::default(),
+            lay_length: LayLengthCtx::default(),
+            diameter: DiameterCtx::default(),
+            rope_axis: RopeAxisCtx::default(),
+            profile_defect: ProfileDefectCtx::default(),
+            orientation: OrientationCtx::default(),
+            roi: RoiCtx::default(),
+            illumination: IlluminationCtx::default(),
+            background: BackgroundCtx::default(),
+            baseline: BaselineCtx::default(),
+            discard: DiscardCtx::default(),
+            position: PositionCtx::default(),
+            speed: SpeedCtx::default(),
+            panorama: PanoramaCtx::default(),
+            defect_tracker: DefectTrackerCtx::default(),
+            vibration: VibrationCtx::default(),
             testing: None,
         }
     }
@@ -1,7 +1,13 @@
 use super::{context::Context};
 use crate::{
     algorithm::{
-        geometry_defect::GeometryDefectCtx, width_emissions::WidthEmissionsCtx, DetectingContoursCvCtx, EdgeDetectionCtx, InitialCtx,
+        geometry_defect::GeometryDefectCtx, surface_defect::SurfaceDefectCtx,
+        width_emissions::WidthEmissionsCtx, DetectingContoursCvCtx, EdgeDetectionCtx, InitialCtx,
+        lay_length::LayLengthCtx, diameter::DiameterCtx, rope_axis::RopeAxisCtx,
+        profile_defect::ProfileDefectCtx, orientation::OrientationCtx, roi::RoiCtx,
+        illumination::IlluminationCtx, background::BackgroundCtx, baseline::BaselineCtx, discard::DiscardCtx,
+        position::PositionCtx, speed::SpeedCtx, panorama::PanoramaCtx, defect_tracker::DefectTrackerCtx,
+        vibration::VibrationCtx,
     }, 
     domain::Error 
 };
@@ -80,3 +86,211 @@ impl ContextRead<GeometryDefectCtx> for Context {
         &self.geometry_defect
     }
 }
+//
+//
+impl ContextWrite<SurfaceDefectCtx> for Context {
+    fn write(mut self, value: SurfaceDefectCtx) -> Result<Self, Error> {
+        self.surface_defect = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<SurfaceDefectCtx> for Context {
+    fn read(&self) -> &SurfaceDefectCtx {
+        &self.surface_defect
+    }
+}
+//
+//
+impl ContextWrite<LayLengthCtx> for Context {
+    fn write(mut self, value: LayLengthCtx) -> Result<Self, Error> {
+        self.lay_length = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<LayLengthCtx> for Context {
+    fn read(&self) -> &LayLengthCtx {
+        &self.lay_length
+    }
+}
+//
+//
+impl ContextWrite<DiameterCtx> for Context {
+    fn write(mut self, value: DiameterCtx) -> Result<Self, Error> {
+        self.diameter = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<DiameterCtx> for Context {
+    fn read(&self) -> &DiameterCtx {
+        &self.diameter
+    }
+}
+//
+//
+impl ContextWrite<RopeAxisCtx> for Context {
+    fn write(mut self, value: RopeAxisCtx) -> Result<Self, Error> {
+        self.rope_axis = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<RopeAxisCtx> for Context {
+    fn read(&self) -> &RopeAxisCtx {
+        &self.rope_axis
+    }
+}
+//
+//
+impl ContextWrite<ProfileDefectCtx> for Context {
+    fn write(mut self, value: ProfileDefectCtx) -> Result<Self, Error> {
+        self.profile_defect = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<ProfileDefectCtx> for Context {
+    fn read(&self) -> &ProfileDefectCtx {
+        &self.profile_defect
+    }
+}
+//
+//
+impl ContextWrite<OrientationCtx> for Context {
+    fn write(mut self, value: OrientationCtx) -> Result<Self, Error> {
+        self.orientation = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<OrientationCtx> for Context {
+    fn read(&self) -> &OrientationCtx {
+        &self.orientation
+    }
+}
+//
+//
+impl ContextWrite<RoiCtx> for Context {
+    fn write(mut self, value: RoiCtx) -> Result<Self, Error> {
+        self.roi = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<RoiCtx> for Context {
+    fn read(&self) -> &RoiCtx {
+        &self.roi
+    }
+}
+//
+//
+impl ContextWrite<IlluminationCtx> for Context {
+    fn write(mut self, value: IlluminationCtx) -> Result<Self, Error> {
+        self.illumination = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<IlluminationCtx> for Context {
+    fn read(&self) -> &IlluminationCtx {
+        &self.illumination
+    }
+}
+//
+//
+impl ContextWrite<BackgroundCtx> for Context {
+    fn write(mut self, value: BackgroundCtx) -> Result<Self, Error> {
+        self.background = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<BackgroundCtx> for Context {
+    fn read(&self) -> &BackgroundCtx {
+        &self.background
+    }
+}
+//
+//
+impl ContextWrite<BaselineCtx> for Context {
+    fn write(mut self, value: BaselineCtx) -> Result<Self, Error> {
+        self.baseline = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<BaselineCtx> for Context {
+    fn read(&self) -> &BaselineCtx {
+        &self.baseline
+    }
+}
+//
+//
+impl ContextWrite<DiscardCtx> for Context {
+    fn write(mut self, value: DiscardCtx) -> Result<Self, Error> {
+        self.discard = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<DiscardCtx> for Context {
+    fn read(&self) -> &DiscardCtx {
+        &self.discard
+    }
+}
+//
+//
+impl ContextWrite<PositionCtx> for Context {
+    fn write(mut self, value: PositionCtx) -> Result<Self, Error> {
+        self.position = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<PositionCtx> for Context {
+    fn read(&self) -> &PositionCtx {
+        &self.position
+    }
+}
+//
+//
+impl ContextWrite<SpeedCtx> for Context {
+    fn write(mut self, value: SpeedCtx) -> Result<Self, Error> {
+        self.speed = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<SpeedCtx> for Context {
+    fn read(&self) -> &SpeedCtx {
+        &self.speed
+    }
+}
+//
+//
+impl ContextWrite<PanoramaCtx> for Context {
+    fn write(mut self, value: PanoramaCtx) -> Result<Self, Error> {
+        self.panorama = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<PanoramaCtx> for Context {
+    fn read(&self) -> &PanoramaCtx {
+        &self.panorama
+    }
+}
+//
+//
+impl ContextWrite<DefectTrackerCtx> for Context {
+    fn write(mut self, value: DefectTrackerCtx) -> Result<Self, Error> {
+        self.defect_tracker = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<DefectTrackerCtx> for Context {
+    fn read(&self) -> &DefectTrackerCtx {
+        &self.defect_tracker
+    }
+}
+//
+//
+impl ContextWrite<VibrationCtx> for Context {
+    fn write(mut self, value: VibrationCtx) -> Result<Self, Error> {
+        self.vibration = value;
+        Result::Ok(self)
+    }
+}
+impl ContextRead<VibrationCtx> for Context {
+    fn read(&self) -> &VibrationCtx {
+        &self.vibration
+    }
+}
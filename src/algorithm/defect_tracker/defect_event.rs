@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use crate::algorithm::geometry_defect::{Defect, DefectSide, GeometryDefectType};
+///
+/// Status of the [DefectEvent]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventStatus {
+    /// Observed on less frames than required, may be a false positive
+    Tentative,
+    /// Observed on enough frames
+    Confirmed,
+}
+///
+/// The same defect observed on the consecutive frames
+/// - `id` - number of the event, unique for the camera
+/// - `kind`, `side` - type and affected side of the defect, `Both` if the frames disagree on the side
+/// - `first`, `last` - extent of the defect along the track of the rope, px, see `EventTrack`
+/// - `start`, `end` - extent of the defect along the rope, m, if the position of the frames known
+/// - `peak`, `relative`, `percent`, `confidence` - largest of the observed ones, `percent` if the camera scale known
+/// - `observations` - number of the frames the defect observed on
+/// - `missed` - number of the last frames the defect expected but not observed on
+/// - `best` - record of the frame observed the largest `relative` deviation
+/// - `status` - tentative or confirmed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DefectEvent {
+    pub id: usize,
+    pub kind: GeometryDefectType,
+    pub side: DefectSide,
+    pub first: f64,
+    pub last: f64,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub peak: f64,
+    pub relative: f64,
+    #[serde(default)]
+    pub percent: Option<f64>,
+    pub confidence: f64,
+    pub observations: usize,
+    pub missed: usize,
+    pub best: Defect,
+    pub status: EventStatus,
+}
+//
+//
+impl DefectEvent {
+    ///
+    /// New tentative [DefectEvent] of the single record
+    /// - `first`, `last` - extent of the record along the track, px
+    /// - `start`, `end` - extent of the record along the rope, m
+    pub fn new(id: usize, defect: Defect, first: f64, last: f64, start: Option<f64>, end: Option<f64>) -> Self {
+        Self {
+            id,
+            kind: defect.kind.clone(),
+            side: defect.side,
+            first,
+            last,
+            start,
+            end,
+            peak: defect.peak,
+            relative: defect.relative,
+            percent: defect.percent,
+            confidence: defect.confidence,
+            observations: 1,
+            missed: 0,
+            best: defect,
+            status: EventStatus::Tentative,
+        }
+    }
+    ///
+    /// Returns true if the record of the `kind` and the `side` at `first..=last` of the track, px,
+    /// belongs to the event, allowing the `gap` between them
+    pub fn matches(&self, kind: &GeometryDefectType, side: DefectSide, first: f64, last: f64, gap: f64) -> bool {
+        let side = self.side == side || self.side == DefectSide::Both || side == DefectSide::Both;
+        self.kind == *kind && side && first <= self.last + gap && last >= self.first - gap
+    }
+    ///
+    /// Extends the event by the record, `observed` - false if the frame of the record already counted
+    pub fn merge(&mut self, defect: Defect, first: f64, last: f64, start: Option<f64>, end: Option<f64>, observed: bool) {
+        if self.side != defect.side {
+            self.side = DefectSide::Both;
+        }
+        self.first = self.first.min(first);
+        self.last = self.last.max(last);
+        self.start = Self::extend(self.start, start, f64::min);
+        self.end = Self::extend(self.end, end, f64::max);
+        self.peak = self.peak.max(defect.peak);
+        self.percent = Self::extend(self.percent, defect.percent, f64::max);
+        self.confidence = self.confidence.max(defect.confidence);
+        if observed {
+            self.observations += 1;
+        }
+        self.missed = 0;
+        if defect.relative > self.relative {
+            self.relative = defect.relative;
+            self.best = defect;
+        }
+    }
+    ///
+    /// Returns the bound extended by the `value` with the `op`
+    fn extend(bound: Option<f64>, value: Option<f64>, op: fn(f64, f64) -> f64) -> Option<f64> {
+        match (bound, value) {
+            (Some(bound), Some(value)) => Some(op(bound, value)),
+            (bound, value) => bound.or(value),
+        }
+    }
+}
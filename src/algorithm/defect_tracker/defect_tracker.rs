@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        defect_tracker::{DefectEvent, DefectTrackerCtx, EventStatus, EventTrack, TrackerConf},
+        diameter::Scale,
+        geometry_defect::{Defect, GeometryDefectCtx},
+        position::PositionCtx,
+        vibration::VibrationCtx,
+        ContextRead, ContextWrite, EvalResult, InitialCtx,
+    },
+    domain::{Error, Eval, RwLock},
+};
+///
+/// Associates the [Defect] records of the consecutive frames into the [DefectEvent]s
+/// - Columns of the record moved onto the track of the rope by the travel of the frame, [PositionCtx::travel_px],
+///   the rope considered stationary if the travel unknown
+/// - Record overlapping the event of the same kind on the track, within the `gap`, extends it, new event started otherwise
+/// - Event observed on the `confirm` frames is confirmed and alarmed once
+/// - Event left the view of the camera, or missed on more than `patience` frames, is closed,
+///   the tentative one dropped as the false positive
+/// - Records of the frame unreliable by the `Vibration` neither associated nor count the events missed,
+///   the events only moved along the track
+/// - Must be inserted after `GeometryDefect`
+pub struct DefectTracker {
+    dbg: Dbg,
+    conf: TrackerConf,
+    scale: Option<Scale>,
+    track: Arc<RwLock<EventTrack>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl DefectTracker {
+    ///
+    /// New instance [DefectTracker]
+    /// - `conf` - association and confirmation parameters
+    /// - `scale` - pixel to millimetre calibration of the camera, used for the feed travel and the extent of the events along the rope
+    /// - `track` - events tracked, shared between the frames of the same camera
+    pub fn new(
+        conf: TrackerConf,
+        scale: Option<Scale>,
+        track: Arc<RwLock<EventTrack>>,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("DefectTracker"),
+            conf,
+            scale,
+            track,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns extent of the `defect` along the rope, m, the frame `center` at it's position
+    /// - Without the [Scale] the whole record at the position of the frame
+    fn extent(&self, defect: &Defect, center: f64) -> (Option<f64>, Option<f64>) {
+        let at = |column: usize| {
+            defect.position.map(|position| position + self.scale.map_or(0.0, |scale| scale.mm(center - column as f64) / 1000.0))
+        };
+        match (at(defect.end), at(defect.start)) {
+            (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
+            _ => (None, None),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for DefectTracker {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let cols = ContextRead::<InitialCtx>::read(&ctx).src_frame.mat.cols().max(0) as f64;
+                let travel = ContextRead::<PositionCtx>::read(&ctx).travel_px(self.scale);
+                let reliable = ContextRead::<VibrationCtx>::read(&ctx).reliable;
+                let records: &[Defect] = match reliable {
+                    true => &ContextRead::<GeometryDefectCtx>::read(&ctx).result,
+                    false => {
+                        log::debug!("{}.eval | Frame unreliable, records skipped", self.dbg);
+                        &[]
+                    }
+                };
+                let gap = self.conf.gap as f64;
+                let mut track = self.track.write();
+                if track.frames > 0 {
+                    track.offset -= travel.unwrap_or(0.0);
+                }
+                let offset = track.offset;
+                let mut seen = vec![false; track.events.len()];
+                let mut result = DefectTrackerCtx::default();
+                for defect in records {
+                    let (first, last) = (defect.start as f64 + offset, defect.end as f64 + offset);
+                    let (start, end) = self.extent(defect, (cols / 2.0).floor());
+                    let matched = track.events.iter().position(|event| event.matches(&defect.kind, defect.side, first, last, gap));
+                    match matched {
+                        Some(index) => {
+                            let observed = seen.get(index).is_some_and(|seen| !seen);
+                            track.events[index].merge(defect.clone(), first, last, start, end, observed);
+                            if let Some(seen) = seen.get_mut(index) {
+                                *seen = true;
+                            }
+                        }
+                        None => {
+                            let id = track.next;
+                            track.next += 1;
+                            track.events.push(DefectEvent::new(id, defect.clone(), first, last, start, end));
+                        }
+                    }
+                }
+                let view = (offset - gap, offset + cols + gap);
+                for (index, event) in track.events.iter_mut().enumerate() {
+                    if reliable && seen.get(index).is_some_and(|seen| !seen) {
+                        event.missed += 1;
+                    }
+                }
+                let (open, closed): (Vec<_>, Vec<_>) = track.events.drain(..).partition(|event| {
+                    let visible = event.last >= view.0 && event.first <= view.1;
+                    visible && event.missed <= self.conf.patience
+                });
+                track.events = open;
+                for event in track.events.iter_mut() {
+                    if event.status == EventStatus::Tentative && event.observations >= self.conf.confirm {
+                        event.status = EventStatus::Confirmed;
+                        log::warn!(
+                            "{}.eval | Defect {} confirmed: {:?} {:?}, {:?}...{:?} m, relative {:.3}",
+                            self.dbg, event.id, event.kind, event.side, event.start, event.end, event.relative,
+                        );
+                        result.confirmed.push(event.clone());
+                    }
+                }
+                for event in closed {
+                    match event.status {
+                        EventStatus::Confirmed => result.closed.push(event),
+                        EventStatus::Tentative => result.dropped.push(event),
+                    }
+                }
+                track.frames += 1;
+                result.events = track.events.clone();
+                log::debug!(
+                    "{}.eval | Events: {}, confirmed: {}, closed: {}, dropped: {}",
+                    self.dbg, result.events.len(), result.confirmed.len(), result.closed.len(), result.dropped.len(),
+                );
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
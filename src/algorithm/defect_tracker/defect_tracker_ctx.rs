@@ -0,0 +1,14 @@
+use crate::algorithm::defect_tracker::DefectEvent;
+///
+/// Store result of `DefectTracker`
+/// - `events` - events in the view of the camera, tentative and confirmed
+/// - `confirmed` - events confirmed on this frame, each event alarmed here once
+/// - `closed` - confirmed events left the view or not observed anymore on this frame, final records of the defects
+/// - `dropped` - tentative events closed on this frame, the false positives
+#[derive(Debug, Clone, Default)]
+pub struct DefectTrackerCtx {
+    pub events: Vec<DefectEvent>,
+    pub confirmed: Vec<DefectEvent>,
+    pub closed: Vec<DefectEvent>,
+    pub dropped: Vec<DefectEvent>,
+}
@@ -0,0 +1,16 @@
+use crate::algorithm::defect_tracker::DefectEvent;
+///
+/// State of the defect tracking, shared between the frames of the same camera
+/// - Track of the rope - column `u` of the frame shifted by the travel of the rope since the first frame,
+///   so the same point of the rope keeps it's track column on all the frames
+/// - `offset` - track column of the column 0 of the last frame, px
+/// - `frames` - number of the frames tracked
+/// - `next` - id of the next event
+/// - `events` - events still in the view of the camera
+#[derive(Debug, Clone, Default)]
+pub struct EventTrack {
+    pub offset: f64,
+    pub frames: usize,
+    pub next: usize,
+    pub events: Vec<DefectEvent>,
+}
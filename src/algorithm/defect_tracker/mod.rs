@@ -0,0 +1,17 @@
+//!
+//! Tracking of the defects over the consecutive frames
+//! - [DefectEvent] - the same defect seen on many frames while the rope passes the camera,
+//!   associated by the overlap of it's columns moved by the travel of the rope
+//! - [DefectTracker] - turns the records of the frames into the events,
+//!   the event alarmed once, when confirmed, the single frame false positives dropped
+mod tracker_conf;
+mod defect_event;
+mod event_track;
+mod defect_tracker_ctx;
+mod defect_tracker;
+
+pub use tracker_conf::*;
+pub use defect_event::*;
+pub use event_track::*;
+pub use defect_tracker_ctx::*;
+pub use defect_tracker::*;
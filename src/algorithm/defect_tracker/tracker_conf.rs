@@ -0,0 +1,22 @@
+///
+/// Configuration parameters of the `DefectTracker`
+/// - `gap` - records closer than this are associated into the same event, px
+/// - `confirm` - frames the event must be observed on to be confirmed
+/// - `patience` - frames the event may be missed in the view before it's closed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackerConf {
+    pub gap: usize,
+    pub confirm: usize,
+    pub patience: usize,
+}
+//
+//
+impl Default for TrackerConf {
+    fn default() -> Self {
+        Self {
+            gap: 5,
+            confirm: 3,
+            patience: 2,
+        }
+    }
+}
@@ -1,16 +1,19 @@
 use opencv::imgproc;
-use opencv::core;
+use opencv::core::{self, MatTrait, MatTraitConst};
 use sal_core::error::Error;
 use crate::algorithm::{
     Context, ContextRead, ContextWrite,
-    DetectingContoursCvCtx,
-    EvalResult, InitialCtx,
+    Binarisation, DetectingContoursCvCtx, Segmentation,
+    EvalResult, IlluminationCtx, InitialCtx,
 };
 use crate::{Eval, domain::Image};
 ///
 /// Takes source [Image]
 /// Return filtered and binarised [Image] with contours detected
+/// - Rope separated from the background by the [Segmentation] into the binary mask
+/// - Works on the normalised frame of the [IlluminationCtx], if given, the `src_frame` otherwise
 pub struct DetectingContoursCv {
+    segmentation: Segmentation,
     ctx: Box<dyn Eval<(), Result<Context, Error>>>,
 }
 //
@@ -18,11 +21,82 @@ pub struct DetectingContoursCv {
 impl DetectingContoursCv{
     ///
     /// Returns [DetectingContoursCv] new instance
-    pub fn new(ctx: impl Eval<(), Result<Context, Error>> + 'static) -> Self {
+    pub fn new(segmentation: Segmentation, ctx: impl Eval<(), Result<Context, Error>> + 'static) -> Self {
         Self { 
+            segmentation,
             ctx: Box::new(ctx),
         }
     }
+    ///
+    /// Returns the mask filled between the first and the last edge pixel of each column
+    fn fill(edges: &core::Mat) -> Result<core::Mat, opencv::Error> {
+        let mut mask = core::Mat::new_rows_cols_with_default(edges.rows(), edges.cols(), core::CV_8UC1, core::Scalar::all(0.0))?;
+        for col in 0..edges.cols() {
+            let mut rows = (0..edges.rows()).filter(|&row| edges.at_2d::<u8>(row, col).is_ok_and(|value| *value > 0));
+            if let Some(first) = rows.next() {
+                let last = rows.last().unwrap_or(first);
+                for row in first..=last {
+                    *mask.at_2d_mut::<u8>(row, col)? = 255;
+                }
+            }
+        }
+        Ok(mask)
+    }
+    ///
+    /// Returns the binary mask of the rope on the `gray` frame
+    /// - the rope doesn't touch the top and the bottom of the frame,
+    ///   so the mask inverted if the most of the border rows are foreground
+    fn segment(&self, gray: &core::Mat) -> Result<core::Mat, opencv::Error> {
+        let mut mask = core::Mat::default();
+        match self.segmentation.binarisation {
+            Binarisation::Otsu => {
+                imgproc::threshold(gray, &mut mask, 0.0, 255.0, imgproc::THRESH_BINARY | imgproc::THRESH_OTSU)?;
+            }
+            Binarisation::Adaptive { block, offset } => {
+                let block = (block.max(3) | 1) as i32;
+                imgproc::adaptive_threshold(gray, &mut mask, 255.0, imgproc::ADAPTIVE_THRESH_GAUSSIAN_C, imgproc::THRESH_BINARY, block, offset)?;
+            }
+            Binarisation::Hysteresis { low, high } => {
+                let mut edges = core::Mat::default();
+                imgproc::canny(gray, &mut edges, low, high, 3, false)?;
+                mask = Self::fill(&edges)?;
+            }
+        }
+        if !matches!(self.segmentation.binarisation, Binarisation::Hysteresis { .. }) && mask.rows() > 1 {
+            let mut border = 0;
+            for row in [0, mask.rows() - 1] {
+                for col in 0..mask.cols() {
+                    if *mask.at_2d::<u8>(row, col)? > 0 {
+                        border += 1;
+                    }
+                }
+            }
+            if border > mask.cols() {
+                let mut inverted = core::Mat::default();
+                core::bitwise_not(&mask, &mut inverted, &core::no_array())?;
+                mask = inverted;
+            }
+        }
+        if self.segmentation.cleanup > 1 {
+            let size = self.segmentation.cleanup as i32;
+            let kernel = imgproc::get_structuring_element(imgproc::MORPH_ELLIPSE, core::Size::new(size, size), core::Point::new(-1, -1))?;
+            for op in [imgproc::MORPH_OPEN, imgproc::MORPH_CLOSE] {
+                let mut cleaned = core::Mat::default();
+                imgproc::morphology_ex(
+                    &mask,
+                    &mut cleaned,
+                    op,
+                    &kernel,
+                    core::Point::new(-1, -1),
+                    1,
+                    core::BORDER_CONSTANT,
+                    imgproc::morphology_default_border_value()?,
+                )?;
+                mask = cleaned;
+            }
+        }
+        Ok(mask)
+    }
 }
 //
 //
@@ -32,8 +106,9 @@ impl Eval<(), Result<Context, Error>> for DetectingContoursCv {
         match self.ctx.eval(()) {
             Ok(ctx) => {
                 let initial_ctx = ContextRead::<InitialCtx>::read(&ctx);
+                let frame = ContextRead::<IlluminationCtx>::read(&ctx).frame_or(&initial_ctx.src_frame);
                 let mut gray = core::Mat::default();
-                match imgproc::cvt_color(&initial_ctx.src_frame.mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0) {
+                match imgproc::cvt_color(&frame.mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0) {
                     Ok(_) => {
                         let mut blurred = core::Mat::default();
                         match imgproc::gaussian_blur(&gray, &mut blurred, core::Size::new(3, 3), 0.0, 0.0, core::BORDER_DEFAULT) {
@@ -53,6 +128,10 @@ impl Eval<(), Result<Context, Error>> for DetectingContoursCv {
                                                                 let mut grad = core::Mat::default();
                                                                 match core::add_weighted(&absx, 0.5, &absy, 0.5, 0.0, &mut grad, -1) {
                                                                     Ok(_) => {
+                                                                        let mask = match self.segment(&blurred) {
+                                                                            Ok(mask) => mask,
+                                                                            Err(err) => return Err(error.pass(err.to_string())),
+                                                                        };
                                                                         let result = DetectingContoursCvCtx {
                                                                             result: Image {
                                                                                 width: initial_ctx.src_frame.width,
@@ -60,7 +139,14 @@ impl Eval<(), Result<Context, Error>> for DetectingContoursCv {
                                                                                 timestamp: initial_ctx.src_frame.timestamp,
                                                                                 mat: grad,
                                                                                 bytes: initial_ctx.src_frame.bytes,
-                                                                            }
+                                                                            },
+                                                                            mask: Image {
+                                                                                width: initial_ctx.src_frame.width,
+                                                                                height: initial_ctx.src_frame.height,
+                                                                                timestamp: initial_ctx.src_frame.timestamp,
+                                                                                mat: mask,
+                                                                                bytes: 1,
+                                                                            },
                                                                         };
                                                                         ctx.write(result)
                                                                     }
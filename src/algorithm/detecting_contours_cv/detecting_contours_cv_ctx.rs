@@ -1,16 +1,20 @@
 use crate::domain::Image;
 ///
 /// Filtered and binarised image
+/// - `result` - gradient magnitude of the frame
+/// - `mask` - binary mask of the rope, 255 - rope, 0 - background, empty if the frame not segmented
 #[derive(Debug, Clone)]
 pub struct DetectingContoursCvCtx {
     pub result: Image,
+    pub mask: Image,
 }
 //
 //
 impl Default for DetectingContoursCvCtx {
     fn default() -> Self {
         Self { 
-            result: Image::default()
+            result: Image::default(),
+            mask: Image::default(),
          }
     }
 }
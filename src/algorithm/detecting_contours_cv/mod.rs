@@ -1,5 +1,7 @@
 mod detecting_contours_cv_ctx;
 mod detecting_contours_cv;
+mod segmentation;
 
 pub use detecting_contours_cv_ctx::*;
-pub use detecting_contours_cv::*;
\ No newline at end of file
+pub use detecting_contours_cv::*;
+pub use segmentation::*;
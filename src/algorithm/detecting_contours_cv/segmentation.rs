@@ -0,0 +1,31 @@
+///
+/// Method of the rope segmentation from the background
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binarisation {
+    /// Global threshold by Otsu's method, suits the evenly lit frames
+    Otsu,
+    /// Threshold by the Gaussian weighted mean of the `block` x `block` neighbourhood minus the `offset`,
+    /// the `block` is expected to exceed the rope diameter
+    Adaptive { block: usize, offset: f64 },
+    /// Canny edges with the hysteresis thresholds, the rope filled between it's outer edges
+    Hysteresis { low: f64, high: f64 },
+}
+///
+/// Configuration parameters of the rope segmentation
+/// - `binarisation` - method of the segmentation
+/// - `cleanup` - size of the morphological opening and closing of the mask, px, 0 - disabled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segmentation {
+    pub binarisation: Binarisation,
+    pub cleanup: usize,
+}
+//
+//
+impl Default for Segmentation {
+    fn default() -> Self {
+        Self {
+            binarisation: Binarisation::Otsu,
+            cleanup: 5,
+        }
+    }
+}
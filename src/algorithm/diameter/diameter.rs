@@ -0,0 +1,90 @@
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::{DiameterCtx, Scale},
+        position::PositionCtx,
+        ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Side,
+    },
+    domain::{Error, Eval},
+};
+///
+/// Measures absolute diameter of the rope, mm
+/// - Width of the rope in each column converted using camera [Scale]
+/// - Reduction and expansion expressed in % of the nominal diameter,
+///   as [GOST discard limits](design/references/GOST_33718-2015.pdf) are given
+/// - Sample bound to the position of the frame along the rope
+/// - Not measured without the camera [Scale], the diameter left zero
+pub struct Diameter {
+    dbg: Dbg,
+    scale: Option<Scale>,
+    nominal: f64,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Diameter {
+    ///
+    /// New instance [Diameter]
+    /// - `scale` - pixel to millimetre calibration of the camera, if known
+    /// - `nominal` - nominal diameter of the rope, mm
+    pub fn new(scale: Option<Scale>, nominal: f64, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Diameter"),
+            scale,
+            nominal,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Diameter {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let widths: Vec<f64> = match self.scale {
+                    Some(scale) => edges.get(Side::Upper).iter()
+                        .zip(edges.get(Side::Lower).iter())
+                        .map(|(upper, lower)| scale.mm((upper.y - lower.y).abs()))
+                        .collect(),
+                    None => {
+                        log::debug!("{}.eval | Camera scale unknown, diameter not measured", self.dbg);
+                        vec![]
+                    }
+                };
+                let position = ContextRead::<PositionCtx>::read(&ctx);
+                let (position, direction) = (position.position, position.direction);
+                let result = match widths.len() {
+                    0 => DiameterCtx { nominal: self.nominal, position, direction, ..Default::default() },
+                    len => {
+                        let min = widths.iter().cloned().fold(f64::MAX, f64::min);
+                        let max = widths.iter().cloned().fold(f64::MIN, f64::max);
+                        let (reduction, expansion) = if self.nominal > 0.0 {
+                            (
+                                ((self.nominal - min) * 100.0 / self.nominal).max(0.0),
+                                ((max - self.nominal) * 100.0 / self.nominal).max(0.0),
+                            )
+                        } else {
+                            (0.0, 0.0)
+                        };
+                        DiameterCtx {
+                            mean: widths.iter().sum::<f64>() / len as f64,
+                            min,
+                            max,
+                            nominal: self.nominal,
+                            reduction,
+                            expansion,
+                            position,
+                            direction,
+                        }
+                    }
+                };
+                log::debug!("{}.eval | Diameter: {:?}", self.dbg, result);
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
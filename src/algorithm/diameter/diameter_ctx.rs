@@ -0,0 +1,34 @@
+use crate::algorithm::position::Direction;
+///
+/// Store result of `Diameter`
+/// - `mean`, `min`, `max` - diameter of the rope on the frame, mm
+/// - `nominal` - nominal diameter of the rope, mm
+/// - `reduction` - reduction of the `min` diameter, % of the `nominal`
+/// - `expansion` - expansion of the `max` diameter, % of the `nominal`
+/// - `position` - position of the frame along the rope, m, if known
+/// - `direction` - direction of the rope travel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiameterCtx {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub nominal: f64,
+    pub reduction: f64,
+    pub expansion: f64,
+    pub position: Option<f64>,
+    pub direction: Direction,
+}
+//
+//
+impl DiameterCtx {
+    ///
+    /// Returns `diameter` deviation from the `nominal`, % of the `nominal`
+    /// - negative for the reduction, positive for the expansion
+    pub fn percent(&self, diameter: f64) -> f64 {
+        if self.nominal > 0.0 {
+            (diameter - self.nominal) * 100.0 / self.nominal
+        } else {
+            0.0
+        }
+    }
+}
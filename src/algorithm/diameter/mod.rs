@@ -0,0 +1,11 @@
+//!
+//! Absolute rope diameter in millimetres
+//! - [Scale] - pixel to millimetre calibration of the camera
+//! - [Diameter] - mean, min and max diameter of the rope on the frame
+mod diameter_ctx;
+mod diameter;
+mod scale;
+
+pub use diameter_ctx::*;
+pub use diameter::*;
+pub use scale::*;
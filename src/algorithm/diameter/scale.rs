@@ -0,0 +1,80 @@
+use std::{fs, path::{Path, PathBuf}};
+use serde::{Deserialize, Serialize};
+use crate::domain::Error;
+///
+/// Pixel to millimetre calibration of the camera
+/// - `mm_per_px` - size of the pixel on the rope surface, mm
+/// - `distance` - working distance the scale was calibrated at, mm,
+///   used to compensate the scale if the rope distance differs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Scale {
+    pub mm_per_px: f64,
+    pub distance: Option<f64>,
+}
+//
+//
+impl Scale {
+    ///
+    /// Returns [Scale] new instance
+    pub fn new(mm_per_px: f64, distance: Option<f64>) -> Self {
+        Self { mm_per_px, distance }
+    }
+    ///
+    /// Returns [Scale] calibrated by the reference gauge or the rod of known diameter
+    /// - `diameter` - known diameter of the reference, mm
+    /// - `width` - measured width of the reference on the frame, px
+    /// - `distance` - working distance of the reference, mm
+    pub fn from_reference(diameter: f64, width: f64, distance: Option<f64>) -> Result<Self, Error> {
+        match width > 0.0 && width.is_finite() {
+            true => Ok(Self::new(diameter / width, distance)),
+            false => Err(Error::new("Scale", "from_reference").err(format!("Invalid width of the reference: {} px", width))),
+        }
+    }
+    ///
+    /// Returns [Scale] calculated from the lens data (thin lens model)
+    /// - `focal_length` - lens focal length, mm
+    /// - `pixel_size` - sensor pixel size, mm
+    /// - `distance` - working distance, mm
+    pub fn from_lens(focal_length: f64, pixel_size: f64, distance: f64) -> Self {
+        Self::new(pixel_size * (distance - focal_length) / focal_length, Some(distance))
+    }
+    ///
+    /// Returns [Scale] compensated for the other working distance, mm
+    /// - scale grows proportionally to the distance
+    pub fn with_distance(&self, distance: f64) -> Self {
+        match self.distance {
+            Some(calibrated) if calibrated > 0.0 => Self::new(self.mm_per_px * distance / calibrated, Some(distance)),
+            _ => *self,
+        }
+    }
+    ///
+    /// Returns length in millimetres of the `px` pixels
+    pub fn mm(&self, px: f64) -> f64 {
+        px * self.mm_per_px
+    }
+    ///
+    /// Returns path of the scale file of the `camera` in the `dir`
+    pub fn path(dir: impl AsRef<Path>, camera: &str) -> PathBuf {
+        dir.as_ref().join(format!("{}.scale.json", camera.trim_start_matches('/').replace('/', "-")))
+    }
+    ///
+    /// Reads [Scale] from the json file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let error = Error::new("Scale", "read");
+        match fs::read_to_string(path.as_ref()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|err| error.pass_with(format!("Parse {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with(format!("Read {:?} error", path.as_ref()), err.to_string())),
+        }
+    }
+    ///
+    /// Writes [Scale] into the json file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let error = Error::new("Scale", "write");
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => fs::write(path.as_ref(), json)
+                .map_err(|err| error.pass_with(format!("Write {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with("Serialize error", err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        defect_tracker::{DefectEvent, DefectTrackerCtx, EventStatus},
+        diameter::DiameterCtx,
+        discard::{DiscardConf, DiscardCriteria, DiscardCtx, RopeCondition, Severity},
+        geometry_defect::GeometryDefectType,
+        rope_axis::RopeAxisCtx,
+        surface_defect::SurfaceDefectCtx,
+        vibration::VibrationCtx,
+        ContextRead, ContextWrite, EvalResult,
+    },
+    domain::{Error, Eval},
+};
+///
+/// Turns the defects in the view of the camera into the verdict on the rope condition
+/// - Uniform decrease of the diameter taken from the mean diameter of `Diameter`
+/// - Waviness `d1 / d` from `RopeAxis`, assessed only if the full wave observed
+/// - Local defects graded by the confirmed events of `DefectTracker`, in the view or closed on the frame,
+///   the single frame records and the tentative events never discard the rope
+/// - Local expansion and compression - largest [GeometryDefectType::Expansion] and [GeometryDefectType::Compressing]
+///   in % of the nominal diameter, assessed only if the rope measured in mm by `Diameter`, the camera scale known
+/// - Corrosion - largest corrosion score of the `SurfaceDefect` segments
+/// - Broken wires aren't detected yet, so not assessed
+/// - Diameter and waviness of the frame unreliable by the `Vibration` not assessed, distorted by the swing
+/// - Measure of the missing stage, or the stage measured nothing on the frame, reported as not assessed
+/// - Graded by [DiscardCriteria], must be inserted after `DefectTracker` and all the defect detecting algorithms
+pub struct Discard {
+    dbg: Dbg,
+    criteria: DiscardCriteria,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Discard {
+    ///
+    /// New instance [Discard]
+    /// - `conf` - construction of the rope and the mechanism group it works on
+    /// - `waviness_limit` - discard limit of the `d1 / d`, the same `RopeAxis` detects the waviness by
+    pub fn new(conf: DiscardConf, waviness_limit: f64, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Discard"),
+            criteria: DiscardCriteria::new(conf, waviness_limit),
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns the largest change of the diameter by the `events` of the `kind`, % of the nominal,
+    /// None if the rope isn't `measured` in mm
+    fn local(events: &[&DefectEvent], kind: GeometryDefectType, measured: bool) -> Option<f64> {
+        measured.then(|| {
+            events.iter()
+                .filter(|event| event.kind == kind)
+                .filter_map(|event| event.percent)
+                .fold(0.0, f64::max)
+        })
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Discard {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let diameter = ContextRead::<DiameterCtx>::read(&ctx);
+                let tracker = ContextRead::<DefectTrackerCtx>::read(&ctx);
+                let events: Vec<&DefectEvent> = tracker.events.iter()
+                    .filter(|event| event.status == EventStatus::Confirmed)
+                    .chain(tracker.closed.iter())
+                    .collect();
+                let mut deformations: Vec<GeometryDefectType> = vec![];
+                for event in &events {
+                    let measured = matches!(
+                        event.kind,
+                        GeometryDefectType::Expansion | GeometryDefectType::Compressing | GeometryDefectType::Waviness,
+                    );
+                    if !measured && !deformations.contains(&event.kind) {
+                        deformations.push(event.kind.clone());
+                    }
+                }
+                let reliable = ContextRead::<VibrationCtx>::read(&ctx).reliable;
+                let waviness = ContextRead::<RopeAxisCtx>::read(&ctx).waviness;
+                let measured = diameter.nominal > 0.0 && diameter.mean > 0.0;
+                let segments = &ContextRead::<SurfaceDefectCtx>::read(&ctx).segments;
+                let condition = RopeCondition {
+                    diameter_reduction: (reliable && measured)
+                        .then(|| (-diameter.percent(diameter.mean)).max(0.0)),
+                    broken_wires_6d: None,
+                    broken_wires_30d: None,
+                    // The partial wave gives no `d1 / d`
+                    waviness: (reliable && waviness.wavelength > 0.0 && waviness.ratio > 0.0).then_some(waviness.ratio),
+                    local_expansion: Self::local(&events, GeometryDefectType::Expansion, measured),
+                    local_compression: Self::local(&events, GeometryDefectType::Compressing, measured),
+                    corrosion: (!segments.is_empty())
+                        .then(|| segments.iter().fold(0.0, |corrosion: f64, segment| corrosion.max(segment.corrosion))),
+                    deformations,
+                };
+                let result: DiscardCtx = self.criteria.eval(condition);
+                if !result.unassessed.is_empty() {
+                    log::debug!("{}.eval | Not assessed: {:?}", self.dbg, result.unassessed);
+                }
+                match &result.rule {
+                    Some(rule) if result.severity == Severity::Discard => log::warn!("{}.eval | {}", self.dbg, rule),
+                    Some(rule) => log::debug!("{}.eval | {}", self.dbg, rule),
+                    None => log::debug!("{}.eval | {:?}", self.dbg, result.severity),
+                }
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::algorithm::discard::{MechanismGroup, RopeConstruction, RopeLay};
+///
+/// Configuration of the [GOST 33718-2015](design/references/GOST_33718-2015.pdf) discard criteria
+/// - `construction`, `lay` - construction and lay type of the rope
+/// - `group` - classification group of the mechanism, M1...M4 if unknown
+/// - `outer_wires` - number of the load bearing wires in the outer strands of the rope
+/// - `local_expansion`, `local_compression` - discard limits of the local change of the diameter, % of the nominal
+/// - `corrosion` - discard limit of the corrosion score, 0...1
+/// - `monitor` - part of the discard limit, from which the rope is put under the monitoring
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiscardConf {
+    pub construction: RopeConstruction,
+    pub lay: RopeLay,
+    pub group: MechanismGroup,
+    pub outer_wires: usize,
+    pub local_expansion: f64,
+    pub local_compression: f64,
+    pub corrosion: f64,
+    pub monitor: f64,
+}
+//
+//
+impl DiscardConf {
+    /// Upper bound of the outer wires number of the rope category and it's broken wires on 6d and 30d,
+    /// ordinary lay, mechanism groups M1...M4
+    const BROKEN_WIRES: [(usize, usize, usize); 13] = [
+        (50, 2, 4),
+        (75, 3, 6),
+        (100, 4, 8),
+        (120, 5, 10),
+        (140, 6, 11),
+        (160, 6, 13),
+        (180, 7, 14),
+        (200, 8, 16),
+        (220, 9, 18),
+        (240, 10, 19),
+        (260, 10, 21),
+        (280, 11, 22),
+        (300, 12, 24),
+    ];
+    ///
+    /// Returns discard limit of the uniform decrease of the diameter, % of the nominal
+    pub fn diameter_limit(&self) -> f64 {
+        match self.construction {
+            RopeConstruction::FibreCore => 10.0,
+            RopeConstruction::SteelCore => 7.5,
+            RopeConstruction::RotationResistant => 5.0,
+        }
+    }
+    ///
+    /// Returns discard numbers of the visible broken wires on the length of 6d and 30d
+    /// - Lang lay allows the half of the ordinary lay
+    /// - Heavy duty mechanism groups M5...M8 allow the double
+    pub fn broken_wires(&self) -> (usize, usize) {
+        let (on_6d, on_30d) = match self.construction {
+            RopeConstruction::RotationResistant => (2, 4),
+            _ => match Self::BROKEN_WIRES.iter().find(|(wires, _, _)| self.outer_wires <= *wires) {
+                Some(&(_, on_6d, on_30d)) => (on_6d, on_30d),
+                None => (
+                    (0.04 * self.outer_wires as f64).round() as usize,
+                    (0.08 * self.outer_wires as f64).round() as usize,
+                ),
+            },
+        };
+        let (on_6d, on_30d) = match (self.construction, self.lay) {
+            (RopeConstruction::RotationResistant, _) | (_, RopeLay::Ordinary) => (on_6d, on_30d),
+            (_, RopeLay::Lang) => (on_6d.div_ceil(2), on_30d.div_ceil(2)),
+        };
+        match self.group.heavy() {
+            true => (2 * on_6d, 2 * on_30d),
+            false => (on_6d, on_30d),
+        }
+    }
+}
+//
+//
+impl Default for DiscardConf {
+    ///
+    /// Steel core ordinary lay rope with 100 outer wires, running through the sheaves, mechanism group unknown
+    fn default() -> Self {
+        Self {
+            construction: RopeConstruction::SteelCore,
+            lay: RopeLay::Ordinary,
+            group: MechanismGroup::M4,
+            outer_wires: 100,
+            local_expansion: 5.0,
+            local_compression: 5.0,
+            corrosion: 0.8,
+            monitor: 0.5,
+        }
+    }
+}
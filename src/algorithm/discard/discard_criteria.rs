@@ -0,0 +1,106 @@
+use crate::{
+    algorithm::{
+        discard::{DiscardConf, DiscardCtx, DiscardRule, Finding, RopeCondition, Severity},
+        geometry_defect::GeometryDefectType,
+    },
+    domain::Eval,
+};
+///
+/// Grades the [RopeCondition] by the [GOST 33718-2015](design/references/GOST_33718-2015.pdf) discard criteria
+/// - Each measure compared with it's discard limit for the rope construction and the mechanism group,
+///   [Severity::Monitor] from the `monitor` part of the limit
+/// - Deformations put the rope under the monitoring: GOST discards the rope with the protruded core or strand at once,
+///   but the Mound seen by the camera can't be told from the local bulge of the displaced wires,
+///   so it's confirmed by the visual inspection, as the other local deformations and flattening
+/// - Verdict is the worst severity, the first criterion of it cited
+/// - Criteria without the measure listed as not assessed, never graded as serviceable
+pub struct DiscardCriteria {
+    conf: DiscardConf,
+    waviness_limit: f64,
+}
+//
+//
+impl DiscardCriteria {
+    ///
+    /// New instance [DiscardCriteria]
+    /// - `waviness_limit` - discard limit of the `d1 / d`, the same `RopeAxis` detects the waviness by,
+    ///   [Waviness::RUNNING](crate::algorithm::Waviness::RUNNING) or [Waviness::STATIONARY](crate::algorithm::Waviness::STATIONARY)
+    pub fn new(conf: DiscardConf, waviness_limit: f64) -> Self {
+        Self { conf, waviness_limit }
+    }
+    ///
+    /// Returns severity of the `value` against it's discard `limit`
+    fn grade(&self, value: f64, limit: f64) -> Severity {
+        if value >= limit {
+            Severity::Discard
+        } else if value > 0.0 && value >= self.conf.monitor * limit {
+            Severity::Monitor
+        } else {
+            Severity::Serviceable
+        }
+    }
+    ///
+    /// Returns severity of the deformation `kind`
+    /// - Expansion, Compressing and Waviness are measured by their own criteria
+    fn deformation(kind: &GeometryDefectType) -> Severity {
+        match kind {
+            GeometryDefectType::Hill | GeometryDefectType::Pit | GeometryDefectType::Groove | GeometryDefectType::Mound => Severity::Monitor,
+            GeometryDefectType::Expansion | GeometryDefectType::Compressing | GeometryDefectType::Waviness => Severity::Serviceable,
+        }
+    }
+}
+//
+//
+impl Eval<RopeCondition, DiscardCtx> for DiscardCriteria {
+    fn eval(&self, condition: RopeCondition) -> DiscardCtx {
+        let (on_6d, on_30d) = self.conf.broken_wires();
+        let mut unassessed = vec![];
+        let mut findings: Vec<Finding> = [
+            (DiscardRule::DiameterReduction, condition.diameter_reduction, self.conf.diameter_limit()),
+            (DiscardRule::BrokenWires6d, condition.broken_wires_6d.map(|wires| wires as f64), on_6d as f64),
+            (DiscardRule::BrokenWires30d, condition.broken_wires_30d.map(|wires| wires as f64), on_30d as f64),
+            (DiscardRule::LocalExpansion, condition.local_expansion, self.conf.local_expansion),
+            (DiscardRule::LocalCompression, condition.local_compression, self.conf.local_compression),
+            (DiscardRule::Corrosion, condition.corrosion, self.conf.corrosion),
+        ]
+        .into_iter()
+        .filter_map(|(rule, value, limit)| match value {
+            Some(value) => Some(Finding { rule, severity: self.grade(value, limit), value, limit }),
+            None => {
+                unassessed.push(rule);
+                None
+            }
+        })
+        .collect();
+        // Waviness graded by it's excess over the straight rope, d1 / d = 1
+        match condition.waviness {
+            Some(waviness) => findings.push(Finding {
+                rule: DiscardRule::Waviness,
+                severity: self.grade(waviness - 1.0, self.waviness_limit - 1.0),
+                value: waviness,
+                limit: self.waviness_limit,
+            }),
+            None => unassessed.push(DiscardRule::Waviness),
+        }
+        findings.extend(condition.deformations.iter().map(|kind| Finding {
+            rule: DiscardRule::Deformation(kind.clone()),
+            severity: Self::deformation(kind),
+            value: 1.0,
+            limit: 1.0,
+        }));
+        findings.retain(|finding| finding.severity > Severity::Serviceable);
+        let rule = findings.iter()
+            .fold(None, |worst: Option<&Finding>, finding| match worst {
+                Some(worst) if worst.severity >= finding.severity => Some(worst),
+                _ => Some(finding),
+            })
+            .cloned();
+        DiscardCtx {
+            condition,
+            severity: rule.as_ref().map_or(Severity::Serviceable, |rule| rule.severity),
+            rule,
+            findings,
+            unassessed,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::algorithm::discard::{DiscardRule, RopeCondition, Severity};
+///
+/// Single criterion exceeded
+/// - `value` - measured value
+/// - `limit` - discard limit of the criterion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub rule: DiscardRule,
+    pub severity: Severity,
+    pub value: f64,
+    pub limit: f64,
+}
+//
+//
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}, {:.2} of the limit {:.2}", self.severity, self.rule, self.value, self.limit)
+    }
+}
+///
+/// Store result of `Discard`
+/// - `condition` - measured condition of the rope
+/// - `severity` - verdict, the worst severity of the `findings`
+/// - `rule` - finding triggered the verdict, None if the rope is serviceable
+/// - `findings` - all criteria exceeded the monitoring level
+/// - `unassessed` - criteria not assessed, the verdict doesn't cover them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiscardCtx {
+    pub condition: RopeCondition,
+    pub severity: Severity,
+    pub rule: Option<Finding>,
+    pub findings: Vec<Finding>,
+    pub unassessed: Vec<DiscardRule>,
+}
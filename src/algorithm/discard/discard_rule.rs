@@ -0,0 +1,39 @@
+use crate::algorithm::geometry_defect::GeometryDefectType;
+///
+/// Discard criterion of [GOST 33718-2015](design/references/GOST_33718-2015.pdf)
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiscardRule {
+    /// Uniform decrease of the rope diameter, % of the nominal
+    DiameterReduction,
+    /// Visible broken outer wires on the length of 6 rope diameters
+    BrokenWires6d,
+    /// Visible broken outer wires on the length of 30 rope diameters
+    BrokenWires30d,
+    /// Waviness, `d1 / d`
+    Waviness,
+    /// Local increase of the rope diameter, % of the diameter
+    LocalExpansion,
+    /// Local decrease of the rope diameter, % of the diameter
+    LocalCompression,
+    /// External corrosion, score 0...1
+    Corrosion,
+    /// Deformation of the rope structure
+    Deformation(GeometryDefectType),
+}
+//
+//
+impl std::fmt::Display for DiscardRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let criterion = match self {
+            DiscardRule::DiameterReduction => "uniform decrease in rope diameter".to_owned(),
+            DiscardRule::BrokenWires6d => "visible broken wires over a length of 6d".to_owned(),
+            DiscardRule::BrokenWires30d => "visible broken wires over a length of 30d".to_owned(),
+            DiscardRule::Waviness => "waviness".to_owned(),
+            DiscardRule::LocalExpansion => "local increase in rope diameter".to_owned(),
+            DiscardRule::LocalCompression => "local decrease in rope diameter".to_owned(),
+            DiscardRule::Corrosion => "external corrosion".to_owned(),
+            DiscardRule::Deformation(kind) => format!("deformation ({:?})", kind),
+        };
+        write!(f, "GOST 33718-2015, {}", criterion)
+    }
+}
@@ -0,0 +1,23 @@
+//!
+//! Discard criteria of the rope, [GOST 33718-2015](design/references/GOST_33718-2015.pdf)
+//! - [RopeCondition] - measured condition of the rope: diameter, broken wires, waviness, deformations, corrosion
+//! - [DiscardCriteria] - grades each measure against the limits of the standard for the rope construction
+//!   and the mechanism group
+//! - [Discard] - verdict on the confirmed defects in the view: the worst [Severity] and the rule triggered it
+mod severity;
+mod rope_construction;
+mod discard_rule;
+mod discard_conf;
+mod rope_condition;
+mod discard_criteria;
+mod discard_ctx;
+mod discard;
+
+pub use severity::*;
+pub use rope_construction::*;
+pub use discard_rule::*;
+pub use discard_conf::*;
+pub use rope_condition::*;
+pub use discard_criteria::*;
+pub use discard_ctx::*;
+pub use discard::*;
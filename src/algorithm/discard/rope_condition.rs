@@ -0,0 +1,21 @@
+use crate::algorithm::geometry_defect::GeometryDefectType;
+///
+/// Measured condition of the rope, input of the [DiscardCriteria](crate::algorithm::DiscardCriteria)
+/// - `diameter_reduction` - uniform decrease of the diameter, % of the nominal
+/// - `broken_wires_6d`, `broken_wires_30d` - visible broken wires on the length of 6d and 30d
+/// - `waviness` - `d1 / d`
+/// - `local_expansion`, `local_compression` - largest local change of the diameter, % of the nominal
+/// - `corrosion` - largest corrosion score, 0...1
+/// - `deformations` - other deformations of the rope structure found
+/// - None if the measure isn't assessed, the stage providing it is missing or measured nothing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RopeCondition {
+    pub diameter_reduction: Option<f64>,
+    pub broken_wires_6d: Option<usize>,
+    pub broken_wires_30d: Option<usize>,
+    pub waviness: Option<f64>,
+    pub local_expansion: Option<f64>,
+    pub local_compression: Option<f64>,
+    pub corrosion: Option<f64>,
+    pub deformations: Vec<GeometryDefectType>,
+}
@@ -0,0 +1,42 @@
+///
+/// Construction of the rope, defines the limits of the diameter decrease and the broken wires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RopeConstruction {
+    /// Single layer stranded rope with the fibre core
+    FibreCore,
+    /// Single layer stranded rope with the steel core, or parallel closed rope
+    SteelCore,
+    /// Rotation-resistant rope
+    RotationResistant,
+}
+///
+/// Lay type of the rope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RopeLay {
+    /// Ordinary (cross) lay, wires laid opposite to the strands
+    Ordinary,
+    /// Lang lay, wires laid the same direction as the strands
+    Lang,
+}
+///
+/// Classification group of the mechanism the rope works on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MechanismGroup {
+    M1,
+    M2,
+    M3,
+    M4,
+    M5,
+    M6,
+    M7,
+    M8,
+}
+//
+//
+impl MechanismGroup {
+    ///
+    /// Returns true for the heavy duty groups M5...M8
+    pub fn heavy(&self) -> bool {
+        *self >= MechanismGroup::M5
+    }
+}
@@ -0,0 +1,12 @@
+///
+/// Condition grade of the rope, ordered from the best to the worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Severity {
+    /// Rope may be used further
+    #[default]
+    Serviceable,
+    /// Deterioration found, the rope must be inspected more frequently
+    Monitor,
+    /// Rope must be taken out of service
+    Discard,
+}
@@ -1,26 +1,224 @@
+use std::sync::Arc;
 use opencv::core::MatTraitConst;
 use sal_core::error::Error;
 use crate::{
-    algorithm::{Context, ContextRead, ContextWrite, DetectingContoursCvCtx, EvalResult, InitialPoints},
-    domain::{Dot, Eval},
+    algorithm::{
+        diameter::Scale,
+        fast_scan::{EdgeState, EdgeStrength, EdgeTrack},
+        position::PositionCtx,
+        Context, ContextRead, ContextWrite, DetectingContoursCvCtx, EvalResult, InitialPoints, Mad,
+    },
+    domain::{Dot, Eval, Image, RwLock},
 };
 use super::edge_detection_ctx::EdgeDetectionCtx;
 ///
-/// Take [Image]
+/// Take gradient [Image]
 /// Return vectors of [Dot] for upper and lower edges of rope
+/// - Rope boundaries taken from the rope mask, if it's segmented, the longest run of the mask in each column,
+///   otherwise from the first and the last contour pixel of the column
+/// - Edge position refined to sub-pixel by the gradient peak interpolation along the column
+/// - Edges returned in pairs, only for the columns where both edges are found
+/// - Each column of the frame gets it's [EdgeState],
+///   so the frames without rope, with partial rope or foreign objects are handled downstream
+/// - With the [EdgeTrack] the edges of the tracked columns searched in the window around the predicted ones only,
+///   the whole column scanned if not found there, the edges returned as measured and filtered by the track,
+///   the track moved along the columns by the travel of the rope, [PositionCtx::travel_px]
 pub struct EdgeDetection {
+    track: Option<Arc<RwLock<EdgeTrack>>>,
+    scale: Option<Scale>,
     ctx: Box<dyn Eval<(), Result<Context, Error>>>,
 }
 //
 //
 impl EdgeDetection {
+    /// Pixel value of the contour
+    const THRESHOLD: u8 = 1;
+    /// The band wider then the median width of the rope in this times considered as occluded
+    const OCCLUSION: f64 = 2.0;
+    /// Maximum distance from the first contour pixel to the gradient peak, px
+    const WINDOW: usize = 3;
     ///
     /// Returns [EdgeDetection] new instance
-    pub fn new(ctx: impl Eval<(), Result<Context, Error>> + 'static) -> Self {
+    /// - `track` - edges tracked over the frames of the same camera, each frame scanned independently if None
+    /// - `scale` - pixel to millimetre calibration of the camera, used for the feed travel of the rope
+    pub fn new(
+        track: Option<Arc<RwLock<EdgeTrack>>>,
+        scale: Option<Scale>,
+        ctx: impl Eval<(), Result<Context, Error>> + 'static,
+    ) -> Self {
         Self { 
+            track,
+            scale,
             ctx: Box::new(ctx),
         }
     }
+    ///
+    /// Returns gradient values of the column, read within the `ranges` of rows only, zero elsewhere
+    fn values(image: &Image, col: i32, ranges: &[(usize, usize)]) -> Result<Vec<f64>, String> {
+        let mut values = vec![0.0; image.mat.rows().max(0) as usize];
+        for &(first, last) in ranges {
+            for row in first..last.min(values.len()) {
+                values[row] = *image.mat.at_2d::<u8>(row as i32, col).map_err(|err| err.to_string())? as f64;
+            }
+        }
+        Ok(values)
+    }
+    ///
+    /// Returns sub-pixel position and strength of the gradient peak of the edge starting at the `start` row
+    /// - climbs by `step` (+1 from the upper edge, -1 from the lower one) while the gradient grows, at most [Self::WINDOW] px
+    /// - refined by the parabola through the peak and it's neighbours, if the peak is a strict local maximum
+    fn peak(values: &[f64], start: usize, step: isize) -> (f64, f64) {
+        let mut peak = start;
+        for _ in 0..Self::WINDOW {
+            let next = peak as isize + step;
+            if next < 0 || next as usize >= values.len() || values[next as usize] <= values[peak] {
+                break;
+            }
+            peak = next as usize;
+        }
+        Self::refine(values, peak)
+    }
+    ///
+    /// Returns sub-pixel position and strength of the strongest gradient around the mask boundary `start`
+    /// - searched up to [Self::WINDOW] px outward and not further then the middle of the band `inward`
+    /// - equal peaks resolved to the nearest one
+    fn nearest(values: &[f64], start: usize, step: isize, inward: usize) -> (f64, f64) {
+        let (outward, inward) = (Self::WINDOW, inward.min(Self::WINDOW));
+        let mut peak = start;
+        for distance in 1..=outward.max(inward) {
+            for (offset, limit) in [(-step, outward), (step, inward)] {
+                let row = start as isize + offset * distance as isize;
+                if distance <= limit && row >= 0 && (row as usize) < values.len() && values[row as usize] > values[peak] {
+                    peak = row as usize;
+                }
+            }
+        }
+        Self::refine(values, peak)
+    }
+    ///
+    /// Returns the `peak` position refined by the parabola through the peak and it's neighbours,
+    /// if the peak is a strict local maximum, together with it's strength
+    fn refine(values: &[f64], peak: usize) -> (f64, f64) {
+        let value = values[peak];
+        if peak == 0 || peak + 1 >= values.len() {
+            return (peak as f64, value);
+        }
+        let (prev, next) = (values[peak - 1], values[peak + 1]);
+        if value > prev && value > next {
+            (peak as f64 + 0.5 * (prev - next) / (prev - 2.0 * value + next), value)
+        } else {
+            (peak as f64, value)
+        }
+    }
+    ///
+    /// Returns first and last row of the longest run of the rope `mask` in the column,
+    /// together with sub-pixel position and strength of the both edges, None if column is empty
+    fn masked(image: &Image, mask: &Image, col: i32) -> Result<Option<([usize; 2], [(f64, f64); 2])>, String> {
+        let values = Self::values(image, col, &[(0, image.mat.rows().max(0) as usize)])?;
+        let mut longest: Option<(usize, usize)> = None;
+        let mut start = None;
+        for row in 0..=mask.mat.rows().max(0) as usize {
+            let rope = row < mask.mat.rows() as usize
+                && *mask.mat.at_2d::<u8>(row as i32, col).map_err(|err| err.to_string())? > 0;
+            match (rope, start) {
+                (true, None) => start = Some(row),
+                (false, Some(first)) => {
+                    if longest.is_none_or(|(upper, lower)| row - 1 - first > lower - upper) {
+                        longest = Some((first, row - 1));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        match longest {
+            Some((upper, lower)) => {
+                let half = (lower - upper) / 2;
+                Ok(Some((
+                    [upper, lower],
+                    [Self::nearest(&values, upper, 1, half), Self::nearest(&values, lower, -1, half)],
+                )))
+            }
+            None => Ok(None),
+        }
+    }
+    ///
+    /// Returns first and last contour row of the column together with
+    /// sub-pixel position and strength of the both edges, None if column is empty
+    fn column(image: &Image, col: i32) -> Result<Option<([usize; 2], [(f64, f64); 2])>, String> {
+        let values = Self::values(image, col, &[(0, image.mat.rows().max(0) as usize)])?;
+        let threshold = Self::THRESHOLD as f64;
+        match (values.iter().position(|&value| value >= threshold), values.iter().rposition(|&value| value >= threshold)) {
+            (Some(upper), Some(lower)) => Ok(Some((
+                [upper, lower],
+                [Self::peak(&values, upper, 1), Self::peak(&values, lower, -1)],
+            ))),
+            _ => Ok(None),
+        }
+    }
+    ///
+    /// Returns the edges of the column searched in the windows of the half height `window` around the `predicted` ones,
+    /// None if any of the edges isn't found inside of it's window
+    /// - The edge on the border of the window may continue outside, so it isn't trusted
+    fn tracked(
+        image: &Image,
+        mask: &Image,
+        col: i32,
+        predicted: [f64; 2],
+        window: f64,
+    ) -> Result<Option<([usize; 2], [(f64, f64); 2])>, String> {
+        let rows = image.mat.rows().max(0) as usize;
+        if rows == 0 {
+            return Ok(None);
+        }
+        let around = |edge: f64| {
+            let (first, last) = ((edge - window).floor(), (edge + window).ceil());
+            match first <= last && last >= 0.0 && first < rows as f64 {
+                true => Some((first.max(0.0) as usize, (last as usize).min(rows - 1))),
+                false => None,
+            }
+        };
+        let (Some(upper), Some(lower)) = (around(predicted[0]), around(predicted[1])) else {
+            return Ok(None);
+        };
+        let values = Self::values(image, col, &[
+            (upper.0.saturating_sub(Self::WINDOW), upper.1 + Self::WINDOW + 1),
+            (lower.0.saturating_sub(Self::WINDOW), lower.1 + Self::WINDOW + 1),
+        ])?;
+        let rope = |row: usize| -> Result<bool, String> {
+            match mask.mat.empty() {
+                true => Ok(values[row] >= Self::THRESHOLD as f64),
+                false => Ok(*mask.mat.at_2d::<u8>(row as i32, col).map_err(|err| err.to_string())? > 0),
+            }
+        };
+        let mut first = None;
+        for row in upper.0..=upper.1 {
+            if rope(row)? {
+                first = Some(row);
+                break;
+            }
+        }
+        let mut last = None;
+        for row in (lower.0..=lower.1).rev() {
+            if rope(row)? {
+                last = Some(row);
+                break;
+            }
+        }
+        match (first, last) {
+            (Some(first), Some(last)) if first < last && (first > upper.0 || first == 0) && (last < lower.1 || last == rows - 1) => {
+                let edges = match mask.mat.empty() {
+                    true => [Self::peak(&values, first, 1), Self::peak(&values, last, -1)],
+                    false => {
+                        let half = (last - first) / 2;
+                        [Self::nearest(&values, first, 1, half), Self::nearest(&values, last, -1, half)]
+                    }
+                };
+                Ok(Some(([first, last], edges)))
+            }
+            _ => Ok(None),
+        }
+    }
 }
 //
 //
@@ -30,41 +228,87 @@ impl Eval<(), Result<Context, Error>> for EdgeDetection {
         match self.ctx.eval(()) {
             Ok(ctx) => {
                 let image = ContextRead::<DetectingContoursCvCtx>::read(&ctx).result.clone();
-                let rows = image.mat.rows();
-                let cols = image.mat.cols();
-                let threshold = 1;
+                let mask = ContextRead::<DetectingContoursCvCtx>::read(&ctx).mask.clone();
+                let cols = image.mat.cols().max(0) as usize;
+                let travel = ContextRead::<PositionCtx>::read(&ctx).travel_px(self.scale);
+                let mut track = self.track.as_ref().map(|track| track.write());
+                if let Some(track) = track.as_mut() {
+                    track.frame(cols, travel);
+                }
+                let mut columns = Vec::with_capacity(cols);
+                let mut tracked = vec![false; cols];
+                for col in 0..image.mat.cols() {
+                    let predicted = track.as_ref().and_then(|track| track.predict(col as usize));
+                    let column = match predicted {
+                        Some((edges, window)) => Self::tracked(&image, &mask, col, edges, window),
+                        None => Ok(None),
+                    };
+                    let column = match column {
+                        Ok(Some(column)) => {
+                            tracked[col as usize] = true;
+                            Ok(Some(column))
+                        }
+                        Ok(None) => match mask.mat.empty() {
+                            true => Self::column(&image, col),
+                            false => Self::masked(&image, &mask, col),
+                        },
+                        Err(err) => Err(err),
+                    };
+                    match column {
+                        Ok(column) => columns.push(column),
+                        Err(err) => return Err(error.pass_with("Input image format error", err)),
+                    }
+                }
+                let widths: Vec<usize> = columns.iter()
+                    .flatten()
+                    .filter(|([upper, lower], _)| upper != lower)
+                    .map(|([upper, lower], _)| lower - upper)
+                    .collect();
+                let median = Mad::new().eval(widths).median;
                 let mut upper_edge = Vec::new();
                 let mut lower_edge = Vec::new();
-                for col in 0..cols {
-                    for row in 0..rows {
-                        match image.mat.at_2d::<u8>(row, col) {
-                            Ok(&pixel_value) => {
-                                if pixel_value >= threshold {
-                                    upper_edge.push(Dot {x: col as usize, y: row as usize});
-                                    break;
-                                }
-                            }   
-                            Err(err) => {
-                                return Err(error.pass_with("Input image format error", err.to_string()));
-                            }
+                let mut filtered_upper = Vec::new();
+                let mut filtered_lower = Vec::new();
+                let mut strength = Vec::new();
+                let states = columns.into_iter().enumerate().map(|(col, column)| match column {
+                    None => {
+                        if let Some(track) = track.as_mut() {
+                            track.coast(col);
                         }
+                        EdgeState::Missing
                     }
-                    for row in (0..rows).rev() {
-                        match image.mat.at_2d::<u8>(row, col) {
-                            Ok(&pixel_value) => {
-                                if pixel_value >= threshold {
-                                    lower_edge.push(Dot {x: col as usize, y: row as usize});
-                                    break;
-                                }
-                            }
-                            Err(err) => {
-                                return Err(error.pass_with("Input image format error", err.to_string()));
-                            }
+                    Some(([upper, lower], _)) if upper == lower || (lower - upper) as f64 > Self::OCCLUSION * median => {
+                        if let Some(track) = track.as_mut() {
+                            track.coast(col);
                         }
+                        EdgeState::Occluded
                     }
+                    Some((_, [(upper, upper_strength), (lower, lower_strength)])) => {
+                        let [filtered_upper_y, filtered_lower_y] = match track.as_mut() {
+                            Some(track) => track.update(col, [upper, lower], !tracked[col]),
+                            None => [upper, lower],
+                        };
+                        upper_edge.push(Dot { x: col as f64, y: upper });
+                        lower_edge.push(Dot { x: col as f64, y: lower });
+                        filtered_upper.push(Dot { x: col as f64, y: filtered_upper_y });
+                        filtered_lower.push(Dot { x: col as f64, y: filtered_lower_y });
+                        strength.push(EdgeStrength::new(upper_strength, lower_strength));
+                        EdgeState::Found
+                    }
+                }).collect::<Vec<_>>();
+                let missing = states.iter().filter(|state| **state == EdgeState::Missing).count();
+                let occluded = states.iter().filter(|state| **state == EdgeState::Occluded).count();
+                if missing + occluded > 0 {
+                    log::debug!("EdgeDetection.eval | Columns missing: {}, occluded: {} of {}", missing, occluded, states.len());
+                }
+                if track.is_some() {
+                    log::trace!("EdgeDetection.eval | Columns tracked: {} of {}", tracked.iter().filter(|tracked| **tracked).count(), cols);
                 }
                 let result = EdgeDetectionCtx {
                     result: InitialPoints::new(upper_edge, lower_edge),
+                    filtered: InitialPoints::new(filtered_upper, filtered_lower),
+                    strength,
+                    states,
                 };
                 ctx.write(result)
             }
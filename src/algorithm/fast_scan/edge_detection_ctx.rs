@@ -1,16 +1,27 @@
-use crate::{algorithm::InitialPoints};
+use crate::algorithm::{fast_scan::{EdgeState, EdgeStrength}, InitialPoints};
 ///
 /// Context store of [EdgeDetection](src/scan/edge_detection.rs)
+/// - `result` - sub-pixel edge pairs of the columns, where both edges are [EdgeState::Found]
+/// - `filtered` - edge pairs of the same columns as the `result`, filtered over the frames by the `EdgeTrack`,
+///   less jitter, but the short defect smoothed, the measured ones if the edges aren't tracked
+/// - `strength` - [EdgeStrength] of each pair of the `result`
+/// - `states` - state of the edges for each column of the frame
 #[derive(Debug, Clone, PartialEq)]
 pub struct EdgeDetectionCtx {
-    pub result: InitialPoints<usize>,
+    pub result: InitialPoints<f64>,
+    pub filtered: InitialPoints<f64>,
+    pub strength: Vec<EdgeStrength>,
+    pub states: Vec<EdgeState>,
 }
 //
 //
 impl Default for EdgeDetectionCtx {
     fn default() -> Self {
         Self { 
-            result: InitialPoints::default()
+            result: InitialPoints::default(),
+            filtered: InitialPoints::default(),
+            strength: vec![],
+            states: vec![],
          }
     }
 }
@@ -0,0 +1,42 @@
+///
+/// Alpha-beta filter of the edge position in the column over the consecutive frames
+/// - `position` - filtered position of the edge, px
+/// - `velocity` - change of the position per frame, px
+/// - `missed` - number of the last frames the edge wasn't measured on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeFilter {
+    pub position: f64,
+    pub velocity: f64,
+    pub missed: usize,
+}
+//
+//
+impl EdgeFilter {
+    ///
+    /// New instance [EdgeFilter] started from the measured `position`
+    pub fn new(position: f64) -> Self {
+        Self { position, velocity: 0.0, missed: 0 }
+    }
+    ///
+    /// Returns position of the edge expected on the next frame
+    pub fn predict(&self) -> f64 {
+        self.position + self.velocity
+    }
+    ///
+    /// Corrects the prediction by the `measured` position, returns the filtered one
+    /// - `alpha` - gain of the position, `beta` - gain of the velocity
+    pub fn update(&mut self, measured: f64, alpha: f64, beta: f64) -> f64 {
+        let predicted = self.predict();
+        let residual = measured - predicted;
+        self.position = predicted + alpha * residual;
+        self.velocity += beta * residual;
+        self.missed = 0;
+        self.position
+    }
+    ///
+    /// Moves the edge by the prediction, if not measured on the frame
+    pub fn coast(&mut self) {
+        self.position = self.predict();
+        self.missed += 1;
+    }
+}
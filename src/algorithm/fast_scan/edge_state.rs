@@ -0,0 +1,12 @@
+///
+/// State of the rope edges in the column of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeState {
+    /// Both edges of the rope found
+    Found,
+    /// Rope isn't found in the column
+    Missing,
+    /// Rope edges hidden or distorted by the foreign object,
+    /// only one edge found or the band is much wider then the rope
+    Occluded,
+}
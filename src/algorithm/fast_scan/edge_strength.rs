@@ -0,0 +1,25 @@
+///
+/// Strength of the rope edges found in the column
+/// - `upper`, `lower` - gradient value at the edge peak, 0.0..=255.0
+/// - `confidence` - 0.0..=1.0, strength of the weakest edge of the column relative to the maximum gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeStrength {
+    pub upper: f64,
+    pub lower: f64,
+    pub confidence: f64,
+}
+//
+//
+impl EdgeStrength {
+    /// Maximum value of the gradient image
+    const MAX: f64 = 255.0;
+    ///
+    /// Returns [EdgeStrength] new instance
+    pub fn new(upper: f64, lower: f64) -> Self {
+        Self {
+            upper,
+            lower,
+            confidence: (upper.min(lower) / Self::MAX).clamp(0.0, 1.0),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::algorithm::fast_scan::{EdgeFilter, EdgeTrackConf};
+///
+/// Upper and lower edges of the rope tracked in each column over the consecutive frames,
+/// shared between the frames of the same camera
+/// - Edges predicted by the [EdgeFilter]s, searched in the window around the prediction only,
+///   the prediction bounds the search, the edges measured are passed downstream as is,
+///   so the defect passing the column isn't smoothed away, the filtered ones passed alongside
+/// - The tracks moved along the columns by the travel of the rope, so the column keeps the track of the same rope section
+/// - Edge not measured moved by the prediction, the track dropped after `patience` frames
+/// - All the tracks dropped if the width of the frame changes
+#[derive(Debug, Clone)]
+pub struct EdgeTrack {
+    conf: EdgeTrackConf,
+    columns: Vec<Option<[EdgeFilter; 2]>>,
+}
+//
+//
+impl EdgeTrack {
+    ///
+    /// New instance [EdgeTrack], nothing tracked yet
+    pub fn new(conf: EdgeTrackConf) -> Self {
+        Self { conf, columns: vec![] }
+    }
+    ///
+    /// Prepares the track for the frame of the `cols` width
+    /// - `travel` - travel of the rope from the previous frame along the columns, px,
+    ///   the tracks kept in place if unknown, the edges moved away are caught by the whole column scan
+    pub fn frame(&mut self, cols: usize, travel: Option<f64>) {
+        if self.columns.len() != cols {
+            self.columns = vec![None; cols];
+            return;
+        }
+        let shift = travel.unwrap_or(0.0).round() as i64;
+        if shift != 0 {
+            let previous = std::mem::take(&mut self.columns);
+            self.columns = (0..cols as i64)
+                .map(|col| usize::try_from(col - shift).ok().and_then(|col| previous.get(col).copied().flatten()))
+                .collect();
+        }
+    }
+    ///
+    /// Returns upper and lower edges of the column `col` predicted, and the half height of the search window, px,
+    /// None if the column isn't tracked
+    /// - The window grows with each frame the edges missed, so the edges moved away are caught back
+    pub fn predict(&self, col: usize) -> Option<([f64; 2], f64)> {
+        self.columns.get(col).copied().flatten().map(|[upper, lower]| {
+            let missed = upper.missed.max(lower.missed);
+            ([upper.predict(), lower.predict()], self.conf.window * (1 + missed) as f64)
+        })
+    }
+    ///
+    /// Corrects the track of the column `col` by the `measured` edges, returns the filtered upper and lower edges
+    /// - `reset` - the edges found outside of the window, the track started again from them
+    pub fn update(&mut self, col: usize, measured: [f64; 2], reset: bool) -> [f64; 2] {
+        let (alpha, beta) = (self.conf.alpha, self.conf.beta);
+        match self.columns.get_mut(col) {
+            Some(Some([upper, lower])) if !reset => [upper.update(measured[0], alpha, beta), lower.update(measured[1], alpha, beta)],
+            Some(column) => {
+                *column = Some([EdgeFilter::new(measured[0]), EdgeFilter::new(measured[1])]);
+                measured
+            }
+            None => measured,
+        }
+    }
+    ///
+    /// Moves the edges of the column `col` by the prediction, if not measured, drops the track after `patience` frames
+    pub fn coast(&mut self, col: usize) {
+        if let Some(Some([upper, lower])) = self.columns.get_mut(col) {
+            upper.coast();
+            lower.coast();
+            if upper.missed > self.conf.patience {
+                self.columns[col] = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+///
+/// Configuration parameters of the [EdgeTrack](crate::algorithm::EdgeTrack)
+/// - `alpha` - 0.0..=1.0, gain of the edge position, 1.0 - measurement taken as is
+/// - `beta` - 0.0..=1.0, gain of the edge velocity, 0.0 - velocity isn't tracked
+/// - `window` - half height of the search window around the predicted edge, px
+/// - `patience` - frames the edge may be missed before it's track is dropped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeTrackConf {
+    pub alpha: f64,
+    pub beta: f64,
+    pub window: f64,
+    pub patience: usize,
+}
+//
+//
+impl Default for EdgeTrackConf {
+    fn default() -> Self {
+        Self {
+            alpha: 0.5,
+            beta: 0.1,
+            window: 6.0,
+            patience: 3,
+        }
+    }
+}
@@ -1,5 +1,15 @@
 mod edge_detection_ctx;
 mod edge_detection;
+mod edge_state;
+mod edge_strength;
+mod edge_filter;
+mod edge_track_conf;
+mod edge_track;
 
 pub use edge_detection_ctx::*;
 pub use edge_detection::*;
+pub use edge_state::*;
+pub use edge_strength::*;
+pub use edge_filter::*;
+pub use edge_track_conf::*;
+pub use edge_track::*;
@@ -0,0 +1,46 @@
+use crate::domain::Dot;
+///
+/// Straight line `y = slope * x + intercept`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Line {
+    pub slope: f64,
+    pub intercept: f64,
+}
+//
+//
+impl Line {
+    ///
+    /// Returns [Line] new instance
+    pub fn new(slope: f64, intercept: f64) -> Self {
+        Self { slope, intercept }
+    }
+    ///
+    /// Returns least squares [Line] fitted to the `dots`
+    /// - Horizontal line through the mean, if dots are less then 2 or all on the same `x`
+    pub fn fit(dots: &[Dot<f64>]) -> Self {
+        let len = dots.len() as f64;
+        if dots.is_empty() {
+            return Self::default();
+        }
+        let mean_x = dots.iter().map(|dot| dot.x).sum::<f64>() / len;
+        let mean_y = dots.iter().map(|dot| dot.y).sum::<f64>() / len;
+        let sxx: f64 = dots.iter().map(|dot| (dot.x - mean_x).powi(2)).sum();
+        let sxy: f64 = dots.iter().map(|dot| (dot.x - mean_x) * (dot.y - mean_y)).sum();
+        if sxx <= 0.0 {
+            return Self::new(0.0, mean_y);
+        }
+        let slope = sxy / sxx;
+        Self::new(slope, mean_y - slope * mean_x)
+    }
+    ///
+    /// Returns `y` of the line at the `x`
+    pub fn y(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+    ///
+    /// Returns signed distance from the line to the `dot`, perpendicular to the line
+    /// - positive if the `dot` is below the line (greater `y`)
+    pub fn distance(&self, dot: Dot<f64>) -> f64 {
+        (dot.y - self.y(dot.x)) / (1.0 + self.slope * self.slope).sqrt()
+    }
+}
@@ -0,0 +1,9 @@
+//!
+//! Geometry primitives and estimators for the rope edges
+//! - [Line] - straight line, least squares fit
+//! - [Ransac] - robust [Line] fit, insensitive to the defects and the outliers of the edge
+mod line;
+mod ransac;
+
+pub use line::*;
+pub use ransac::*;
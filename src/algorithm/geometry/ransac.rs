@@ -0,0 +1,77 @@
+use crate::{
+    algorithm::geometry::Line,
+    domain::{Dot, Eval},
+};
+///
+/// Robust [Line] fitting, RANdom SAmple Consensus
+/// - Each iteration takes the line through two random dots and counts dots within the `tolerance`
+/// - The line of the largest consensus is refined by least squares on it's inliers
+/// - Samples are pseudo-random with the fixed seed, so the result is reproducible
+pub struct Ransac {
+    iterations: usize,
+    tolerance: f64,
+}
+//
+//
+impl Ransac {
+    /// Seed of the sampling sequence
+    const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+    ///
+    /// New instance [Ransac]
+    /// - `iterations` - number of the random samples
+    /// - `tolerance` - maximum distance of the inlier from the line, px
+    pub fn new(iterations: usize, tolerance: f64) -> Self {
+        Self { iterations, tolerance }
+    }
+    ///
+    /// Returns next pseudo-random value of the linear congruential sequence
+    fn next(seed: &mut u64) -> usize {
+        *seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (*seed >> 33) as usize
+    }
+}
+//
+//
+impl Default for Ransac {
+    ///
+    /// 100 iterations, 2 px tolerance
+    fn default() -> Self {
+        Self::new(100, 2.0)
+    }
+}
+//
+//
+impl Eval<Vec<Dot<f64>>, Line> for Ransac {
+    fn eval(&self, dots: Vec<Dot<f64>>) -> Line {
+        let len = dots.len();
+        if len < 3 {
+            return Line::fit(&dots);
+        }
+        let mut seed = Self::SEED;
+        let mut best: Option<(usize, Line)> = None;
+        for _ in 0..self.iterations {
+            let i = Self::next(&mut seed) % len;
+            let j = (i + 1 + Self::next(&mut seed) % (len - 1)) % len;
+            let (a, b) = (dots[i], dots[j]);
+            if (b.x - a.x).abs() <= f64::EPSILON {
+                continue;
+            }
+            let slope = (b.y - a.y) / (b.x - a.x);
+            let line = Line::new(slope, a.y - slope * a.x);
+            let consensus = dots.iter().filter(|dot| line.distance(**dot).abs() <= self.tolerance).count();
+            if best.is_none_or(|(best, _)| consensus > best) {
+                best = Some((consensus, line));
+            }
+        }
+        match best {
+            Some((_, line)) => {
+                let inliers: Vec<Dot<f64>> = dots.iter()
+                    .filter(|dot| line.distance(**dot).abs() <= self.tolerance)
+                    .cloned()
+                    .collect();
+                Line::fit(&inliers)
+            }
+            None => Line::fit(&dots),
+        }
+    }
+}
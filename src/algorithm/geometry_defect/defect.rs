@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use crate::algorithm::{diameter::Scale, geometry_defect::GeometryDefectType, position::Direction};
+///
+/// Side of the rope affected by the defect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DefectSide {
+    Upper,
+    Lower,
+    Both,
+}
+///
+/// Localised record of the [geometry defect](design/theory/geometry_rope_defects.md) found on the frame
+/// - `kind` - type of the defect
+/// - `side` - affected side of the rope
+/// - `start`, `end` - first and last column of the defect, px
+/// - `start_mm`, `end_mm` - same columns, mm, if the camera [Scale] is calibrated
+/// - `rows` - upper and lower rows of the rope edges over the defect on the frame aligned by `Orientation`, px,
+///   None if the edges not found
+/// - `peak` - maximum deviation of the edge or the width, px
+/// - `relative` - `peak` relative to the median diameter of the rope on the frame
+/// - `percent` - `peak` in % of the nominal diameter of the rope, if the camera [Scale] is calibrated
+/// - `timestamp` - timestamp of the frame, ns
+/// - `camera` - name of the camera the frame taken from
+/// - `confidence` - 0.0..=1.0, how far the defect exceeds it's detection limit
+/// - `position` - position of the frame along the rope, m, if known
+/// - `direction` - direction of the rope travel, if known
+/// - `reliable` - false if the frame was taken while the rope swings over the limit, the geometry may be distorted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Defect {
+    pub kind: GeometryDefectType,
+    pub side: DefectSide,
+    pub start: usize,
+    pub end: usize,
+    pub start_mm: Option<f64>,
+    pub end_mm: Option<f64>,
+    #[serde(default)]
+    pub rows: Option<(f64, f64)>,
+    pub peak: f64,
+    pub relative: f64,
+    #[serde(default)]
+    pub percent: Option<f64>,
+    pub timestamp: usize,
+    pub camera: String,
+    pub confidence: f64,
+    pub position: Option<f64>,
+    pub direction: Option<Direction>,
+    pub reliable: bool,
+}
+//
+//
+impl Defect {
+    ///
+    /// Returns [Defect] new instance, not yet bound to the frame and the camera
+    pub fn new(
+        kind: GeometryDefectType,
+        side: DefectSide,
+        start: usize,
+        end: usize,
+        peak: f64,
+        relative: f64,
+        confidence: f64,
+    ) -> Self {
+        Self {
+            kind,
+            side,
+            start,
+            end,
+            start_mm: None,
+            end_mm: None,
+            rows: None,
+            peak,
+            relative,
+            percent: None,
+            timestamp: 0,
+            camera: String::new(),
+            confidence,
+            position: None,
+            direction: None,
+            reliable: true,
+        }
+    }
+    ///
+    /// Returns the record bound to the frame `timestamp` and the `camera`,
+    /// with position converted to mm and the `peak` to % of the `nominal` diameter of the rope, mm, if the `scale` is known
+    pub fn locate(self, timestamp: usize, camera: &str, scale: Option<Scale>, nominal: f64) -> Self {
+        Self {
+            start_mm: scale.map(|scale| scale.mm(self.start as f64)),
+            end_mm: scale.map(|scale| scale.mm(self.end as f64)),
+            percent: scale.filter(|_| nominal > 0.0).map(|scale| scale.mm(self.peak) * 100.0 / nominal),
+            timestamp,
+            camera: camera.to_owned(),
+            ..self
+        }
+    }
+    ///
+    /// Returns the record bound to the `position` of the frame along the rope, m, and the `direction` of travel
+    pub fn place(self, position: Option<f64>, direction: Direction) -> Self {
+        Self {
+            position,
+            direction: Some(direction),
+            ..self
+        }
+    }
+    ///
+    /// Returns confidence of the deviation exceeding the `limit`
+    /// - 0.0 on the limit, approaches 1.0 as the deviation grows
+    pub fn confidence(deviation: f64, limit: f64) -> f64 {
+        match deviation.abs() {
+            deviation if deviation > 0.0 => (1.0 - limit.abs() / deviation).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+}
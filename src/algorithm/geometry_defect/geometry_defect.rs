@@ -1,18 +1,31 @@
 use sal_core::dbg::Dbg;
 use crate::{
     algorithm::{
-        geometry_defect::{
-            GeometryDefectCtx, GeometryDefectType, Threshold
-        }, mad::{Bond, MadCtx}, width_emissions::WidthEmissionsCtx, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Side
+        baseline::BaselineCtx, diameter::{DiameterCtx, Scale}, geometry::Line, geometry_defect::{
+            Defect, DefectSide, GeometryDefectCtx, GeometryDefectType, Threshold
+        }, mad::{Bond, MadCtx, OutlierCtx}, position::PositionCtx, profile_defect::ProfileDefectCtx, roi::RoiCtx, rope_axis::RopeAxisCtx, vibration::VibrationCtx, width_emissions::{EmissionSegment, WidthEmissionsCtx}, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, Side
     }, 
-    domain::{Error, Eval}
+    domain::{Dot, Error, Eval}
 };
 ///
 /// Represents detecting [geometry defect's](design/theory/geometry_rope_defects.md)
+/// - Edge deviations measured perpendicular to the upper and lower edge lines fitted by the robust estimator,
+///   so the tilted or swinging rope doesn't produce false Hill / Pit
+/// - Each [EmissionSegment] classified once as a whole, by the largest deviation of each edge inside of it,
+///   so the one defect gives the one [Defect] record
+/// - Outward deviation of the edge over the columns of the Mound on the same side dropped,
+///   the Mound found by `ProfileDefect` is the outward deviation of the edge as well, so the one bulge gives the one record
+/// - Records of the frame are bound to the frame timestamp, the camera and the position along the rope,
+///   the peak expressed in % of the nominal diameter of `Diameter`, if the camera [Scale] is known,
+///   the rows of the edges over the defect moved onto the frame aligned by `Orientation` by the [RoiCtx]
+/// - Records of the frame swinging over the limit of `Vibration` flagged unreliable
 pub struct GeometryDefect {
     dbg: Dbg,
     threshold: Threshold,
-    mad: Box<dyn Eval<Vec<usize>, MadCtx>>,
+    scale: Option<Scale>,
+    camera: String,
+    mad: Box<dyn Eval<Vec<f64>, OutlierCtx>>,
+    fit: Box<dyn Eval<Vec<Dot<f64>>, Line>>,
     ctx: Box<dyn Eval<(), EvalResult>>,
 }
 //
@@ -20,23 +33,33 @@ pub struct GeometryDefect {
 impl GeometryDefect {
     ///
     /// New instance [GeometryDefect]
+    /// - `scale` - pixel to millimetre calibration of the camera, if known
+    /// - `camera` - name of the camera the frames taken from
+    /// - `mad` - outlier detector of the edges and the widths, [OutlierDetector](crate::algorithm::OutlierDetector) for example
+    /// - `fit` - line estimator of the rope edges, [Ransac](crate::algorithm::Ransac) for example
     pub fn new(
         threshold: Threshold,
-        mad: impl Eval<Vec<usize>, MadCtx> + 'static,
+        scale: Option<Scale>,
+        camera: &str,
+        mad: impl Eval<Vec<f64>, OutlierCtx> + 'static,
+        fit: impl Eval<Vec<Dot<f64>>, Line> + 'static,
         ctx: impl Eval<(), EvalResult> + 'static,
     ) -> Self {
         Self {
             dbg: Dbg::own("GeometryDefect"),
             threshold,
+            scale,
+            camera: camera.to_owned(),
             mad: Box::new(mad),
+            fit: Box::new(fit),
             ctx: Box::new(ctx),
         }
     }
     ///
     /// Detecting [both sides width growing](design/references/GOST_33718-2015.pdf)
-    fn expansion(&self, upper_point: Bond<usize>, lower_point: Bond<usize>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
-        let deviation_upper = upper_point.y as f64 - mad_of_upper_points.median;
-        let deviation_lower = lower_point.y as f64 - mad_of_lower_points.median;
+    fn expansion(&self, upper_point: Bond<f64>, lower_point: Bond<f64>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
+        let deviation_upper = upper_point.y - mad_of_upper_points.median;
+        let deviation_lower = lower_point.y - mad_of_lower_points.median;
         if (deviation_upper > self.threshold.0 * mad_of_upper_points.mad) &&
             (deviation_lower < -self.threshold.0 * mad_of_lower_points.mad) {
             return Some(());
@@ -45,9 +68,9 @@ impl GeometryDefect {
     }
     ///
     /// Detecting [both sides width reduction](design/references/GOST_33718-2015.pdf)
-    fn compressing(&self, upper_point: Bond<usize>, lower_point: Bond<usize>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
-        let deviation_upper = upper_point.y as f64 - mad_of_upper_points.median;
-        let deviation_lower = lower_point.y as f64 - mad_of_lower_points.median;
+    fn compressing(&self, upper_point: Bond<f64>, lower_point: Bond<f64>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
+        let deviation_upper = upper_point.y - mad_of_upper_points.median;
+        let deviation_lower = lower_point.y - mad_of_lower_points.median;
         if (deviation_upper < -self.threshold.0 * mad_of_upper_points.mad) &&
             (deviation_lower > self.threshold.0 * mad_of_lower_points.mad) {
             return Some(());
@@ -56,9 +79,9 @@ impl GeometryDefect {
     }
     ///
     /// Detecting [one side drooping](design/references/GOST_33718-2015.pdf)
-    fn pit(&self, upper_point: Bond<usize>, lower_point: Bond<usize>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
-        let deviation_upper = upper_point.y as f64 - mad_of_upper_points.median;
-        let deviation_lower = lower_point.y as f64 - mad_of_lower_points.median;
+    fn pit(&self, upper_point: Bond<f64>, lower_point: Bond<f64>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
+        let deviation_upper = upper_point.y - mad_of_upper_points.median;
+        let deviation_lower = lower_point.y - mad_of_lower_points.median;
         if (deviation_upper.abs() < self.threshold.0 * mad_of_upper_points.mad) &&
         (deviation_lower > self.threshold.0 * mad_of_lower_points.mad) {
             return Some(());
@@ -70,9 +93,9 @@ impl GeometryDefect {
     }
     ///
     /// Detecting [one side raising](design/references/GOST_33718-2015.pdf)
-    fn hill(&self, upper_point: Bond<usize>, lower_point: Bond<usize>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
-        let deviation_upper = upper_point.y as f64 - mad_of_upper_points.median;
-        let deviation_lower = lower_point.y as f64 - mad_of_lower_points.median;
+    fn hill(&self, upper_point: Bond<f64>, lower_point: Bond<f64>, mad_of_upper_points: &MadCtx, mad_of_lower_points: &MadCtx) -> Option<()> {
+        let deviation_upper = upper_point.y - mad_of_upper_points.median;
+        let deviation_lower = lower_point.y - mad_of_lower_points.median;
         if (deviation_upper.abs() < self.threshold.0 * mad_of_upper_points.mad) &&
             (deviation_lower < -self.threshold.0 * mad_of_lower_points.mad) {
             return Some(());
@@ -81,7 +104,80 @@ impl GeometryDefect {
             return Some(());
         }
         None
-    }   
+    }
+    ///
+    /// Returns the largest deviation of the edge `points` inside the `segment`, perpendicular to the fitted `line`,
+    /// and center and spread of the edge at that point
+    fn extreme(segment: &EmissionSegment, points: &[Dot<f64>], line: &Line, outliers: &OutlierCtx) -> (Bond<f64>, MadCtx) {
+        let index = (segment.first..=segment.last)
+            .max_by(|a, b| {
+                let deviation = |i: usize| (line.distance(points[i]) - outliers.at(i).median).abs();
+                deviation(*a).total_cmp(&deviation(*b))
+            })
+            .unwrap_or(segment.first);
+        (Bond { x: points[index].x, y: line.distance(points[index]) }, outliers.at(index))
+    }
+    ///
+    /// Returns affected side, peak deviation and it's detection limit of the defect `kind` in the column
+    /// - Expansion and Compressing measured by the width change, sum of the both sides deviation
+    /// - Hill and Pit measured by the deviation of the `side` moved over the segment, [EmissionSegment::side],
+    ///   by the side exceeding it's limit at the column if the segment moves the both sides
+    fn measure(
+        &self,
+        kind: &GeometryDefectType,
+        side: DefectSide,
+        upper_point: Bond<f64>,
+        lower_point: Bond<f64>,
+        mad_of_upper_points: &MadCtx,
+        mad_of_lower_points: &MadCtx,
+    ) -> (DefectSide, f64, f64) {
+        let deviation_upper = (upper_point.y - mad_of_upper_points.median).abs();
+        let limit_upper = self.threshold.0 * mad_of_upper_points.mad;
+        let deviation_lower = (lower_point.y - mad_of_lower_points.median).abs();
+        let limit_lower = self.threshold.0 * mad_of_lower_points.mad;
+        match (kind, side) {
+            (GeometryDefectType::Expansion | GeometryDefectType::Compressing, _) => (
+                DefectSide::Both,
+                ((upper_point.y - mad_of_upper_points.median) - (lower_point.y - mad_of_lower_points.median)).abs(),
+                limit_upper + limit_lower,
+            ),
+            (_, DefectSide::Upper) => (DefectSide::Upper, deviation_upper, limit_upper),
+            (_, DefectSide::Lower) => (DefectSide::Lower, deviation_lower, limit_lower),
+            (_, DefectSide::Both) => match deviation_upper > limit_upper {
+                true => (DefectSide::Upper, deviation_upper, limit_upper),
+                false => (DefectSide::Lower, deviation_lower, limit_lower),
+            },
+        }
+    }
+    ///
+    /// Returns true if the `defect` is the outward deviation of the edge, overlapping the Mound of the `profile_defects` on the same side
+    /// - `top` - side of the rope of the smaller rows, outside of it is the row decreasing, as in `ProfileDefect`
+    /// - Hill moves the edge to the smaller rows, Pit to the greater ones
+    fn mound(defect: &Defect, profile_defects: &[Defect], top: DefectSide) -> bool {
+        let outward = match defect.kind {
+            GeometryDefectType::Hill => defect.side == top,
+            GeometryDefectType::Pit => defect.side != top && defect.side != DefectSide::Both,
+            _ => false,
+        };
+        outward && profile_defects.iter().any(|mound| {
+            mound.kind == GeometryDefectType::Mound &&
+                mound.side == defect.side &&
+                mound.start <= defect.end && defect.start <= mound.end
+        })
+    }
+    ///
+    /// Returns the first and the last rows of the rope edges over the columns of the `defect`,
+    /// None if the edges not found over it
+    fn rows(defect: &Defect, upper: &[Dot<f64>], lower: &[Dot<f64>]) -> Option<(f64, f64)> {
+        let columns = defect.start as f64..=defect.end as f64;
+        upper.iter()
+            .chain(lower.iter())
+            .filter(|dot| columns.contains(&dot.x))
+            .fold(None, |rows, dot| match rows {
+                Some((first, last)) => Some((dot.y.min(first), dot.y.max(last))),
+                None => Some((dot.y, dot.y)),
+            })
+    }
 }
 //
 //
@@ -90,63 +186,100 @@ impl Eval<(), EvalResult> for GeometryDefect {
         let error = Error::new(&self.dbg, "eval");
         match self.ctx.eval(()) {
             Ok(ctx) => {
-                let mut result: Vec<GeometryDefectType> = Vec::new();
+                let mut result: Vec<Defect> = Vec::new();
+                let timestamp = ContextRead::<InitialCtx>::read(&ctx).src_frame.timestamp;
+                let roi = *ContextRead::<RoiCtx>::read(&ctx);
+                let position = ContextRead::<PositionCtx>::read(&ctx).clone();
+                let reliable = ContextRead::<VibrationCtx>::read(&ctx).reliable;
+                let nominal = ContextRead::<DiameterCtx>::read(&ctx).nominal;
                 let initial_points = ContextRead::<EdgeDetectionCtx>::read(&ctx);
                 let initial_points_upper = initial_points.result.get(Side::Upper);
                 let initial_points_lower = initial_points.result.get(Side::Lower);
+                // Rows of the cropped strip mapped back to the aligned frame, the columns are the same
+                let locate = |defect: Defect| Defect {
+                    rows: Self::rows(&defect, initial_points_upper, initial_points_lower)
+                        .map(|(upper, lower)| (roi.aligned_row(upper), roi.aligned_row(lower))),
+                    reliable,
+                    ..defect
+                }.locate(timestamp, &self.camera, self.scale, nominal).place(position.position, position.direction);
+                let waviness = ContextRead::<RopeAxisCtx>::read(&ctx).result.clone();
+                let profile_defects = ContextRead::<ProfileDefectCtx>::read(&ctx).result.clone();
+                let baseline = ContextRead::<BaselineCtx>::read(&ctx).result.clone();
                 let width_emissions_result = ContextRead::<WidthEmissionsCtx>::read(&ctx).result.clone();
                 if width_emissions_result.is_empty() {
+                    result.extend(waviness);
+                    result.extend(profile_defects);
+                    result.extend(baseline);
                     let result = GeometryDefectCtx {
-                        result,
+                        result: result.into_iter().map(locate).collect(),
                     };
                     log::debug!("Frame without defect's");
                     return ctx.write(result)
                 }
-                let mad_of_upper_points = self.mad.eval(
+                let upper_line = self.fit.eval(initial_points_upper.clone());
+                let lower_line = self.fit.eval(initial_points_lower.clone());
+                let outliers_upper = self.mad.eval(
                     initial_points_upper.iter()
-                        .map(|dot| dot.y)
+                        .map(|dot| upper_line.distance(*dot))
                         .collect(),
                 );
-                let mad_of_lower_points = self.mad.eval(
+                let outliers_lower = self.mad.eval(
                     initial_points_lower
                         .iter()
-                        .map(|dot| dot.y)
+                        .map(|dot| lower_line.distance(*dot))
                         .collect()
                 );
-                for i in (0..width_emissions_result.len()-1).step_by(2) {
-                    let upper_point = width_emissions_result[i];
-                    let lower_point = width_emissions_result[i+1];
-                    match self.expansion(upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points) {
-                        Some(_) => result.push(GeometryDefectType::Expansion),
+                let outliers_widths = self.mad.eval(
+                    initial_points_upper.iter()
+                        .zip(initial_points_lower.iter())
+                        .map(|(upper, lower)| (upper.y - lower.y).abs())
+                        .collect()
+                );
+                for segment in &width_emissions_result {
+                    // Largest deviation of each edge over the segment, center and spread of the edge at it
+                    let (upper_point, mad_of_upper_points) = Self::extreme(segment, &initial_points_upper, &upper_line, &outliers_upper);
+                    let (lower_point, mad_of_lower_points) = Self::extreme(segment, &initial_points_lower, &lower_line, &outliers_lower);
+                    let kind = match self.expansion(upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points) {
+                        Some(_) => Some(GeometryDefectType::Expansion),
                         None => match self.compressing(upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points) {
-                            Some(_) => result.push(GeometryDefectType::Compressing),
+                            Some(_) => Some(GeometryDefectType::Compressing),
                             None => match self.hill(upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points) {
-                                Some(_) => result.push(GeometryDefectType::Hill),
+                                Some(_) => Some(GeometryDefectType::Hill),
                                 None => match self.pit(upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points) {
-                                    Some(_) => result.push(GeometryDefectType::Pit),
-                                    None => {}
+                                    Some(_) => Some(GeometryDefectType::Pit),
+                                    None => None,
                                 }
                             }
                         }
+                    };
+                    if let Some(kind) = kind {
+                        let (side, peak, limit) = self.measure(&kind, segment.side, upper_point, lower_point, &mad_of_upper_points, &mad_of_lower_points);
+                        let mad_of_widths = outliers_widths.at(segment.first);
+                        result.push(Defect::new(
+                            kind,
+                            side,
+                            segment.start,
+                            segment.end,
+                            peak,
+                            if mad_of_widths.median > 0.0 { peak / mad_of_widths.median } else { 0.0 },
+                            Defect::confidence(peak, limit),
+                        ));
                     }
                 }
-                result = result.into_iter().fold(vec![], |mut acc, defect| {
-                    match acc.last() {
-                        Some(prev) => {
-                            if prev != &defect {
-                                acc.push(defect);
-                            }
-                        }
-                        None => acc.push(defect),
-                    }
-                    acc
-                });
+                let top = match (initial_points_upper.first(), initial_points_lower.first()) {
+                    (Some(upper), Some(lower)) if upper.y > lower.y => DefectSide::Lower,
+                    _ => DefectSide::Upper,
+                };
+                result.retain(|defect| !Self::mound(defect, &profile_defects, top));
+                result.extend(waviness);
+                result.extend(profile_defects);
+                result.extend(baseline);
                 let result = GeometryDefectCtx {
-                    result,
+                    result: result.into_iter().map(locate).collect(),
                 };
                 ctx.write(result)
             },
             Err(err) => Err(error.pass(err)),
         }
     }
-}
\ No newline at end of file
+}
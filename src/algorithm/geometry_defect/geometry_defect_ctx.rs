@@ -1,10 +1,8 @@
-use crate::algorithm::geometry_defect::GeometryDefectType;
+use crate::algorithm::geometry_defect::Defect;
 ///
 /// Store result of `GeometryDefect`
-/// - Index of the frame containing the defect
-/// - The kind of the geometry defect
+/// - [Defect] records of the frame, ordered by kind of detection, each ordered by position
 #[derive(Debug, Clone, Default)]
 pub struct GeometryDefectCtx {
-    pub result: Vec<GeometryDefectType>,
-
-}
\ No newline at end of file
+    pub result: Vec<Defect>,
+}
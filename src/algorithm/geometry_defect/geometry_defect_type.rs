@@ -1,7 +1,7 @@
+use serde::{Deserialize, Serialize};
 ///
 /// Enum of [geometry defect type's](design/theory/geometry_rope_defects.md)
-/// containing the position of defect withing a frame
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GeometryDefectType {
     /// Detecting both sides width growing
     Expansion,
@@ -11,4 +11,10 @@ pub enum GeometryDefectType {
     Hill,
     /// Detecting one side drooping
     Pit,
+    /// Detecting waviness of the rope axis
+    Waviness,
+    /// Detecting longitudinal trough from the sheave wear
+    Groove,
+    /// Detecting local bulge from the displaced inner wires
+    Mound,
 }
\ No newline at end of file
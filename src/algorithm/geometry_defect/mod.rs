@@ -1,8 +1,10 @@
+mod defect;
 mod geometry_defect_ctx;
 mod geometry_defect_type;
 mod geometry_defect;
 mod threshold;
 
+pub use defect::*;
 pub use geometry_defect_ctx::*;
 pub use geometry_defect_type::*;
 pub use geometry_defect::*;
@@ -0,0 +1,9 @@
+///
+/// Brightness of the frames smoothed over the time
+/// - `level` - smoothed mean brightness of the luma
+/// - `measured` - false until the first frame is processed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Brightness {
+    pub level: f64,
+    pub measured: bool,
+}
@@ -0,0 +1,60 @@
+use opencv::{
+    core::{self, Mat, MatTrait, MatTraitConst, Size},
+    imgproc,
+};
+use crate::domain::Image;
+///
+/// Flat-field correction of the uneven lighting and the lens vignetting
+/// - Built once from the reference frame of the evenly lit uniform target
+/// - Holds per pixel gain bringing the reference to it's mean brightness
+#[derive(Debug, Clone)]
+pub struct FlatField {
+    gain: Mat,
+}
+//
+//
+impl FlatField {
+    ///
+    /// Returns [FlatField] new instance built from the `reference` frame
+    pub fn new(reference: &Image) -> Result<Self, opencv::Error> {
+        let gray = match reference.mat.channels() {
+            1 => reference.mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                imgproc::cvt_color(&reference.mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut blurred = Mat::default();
+        imgproc::gaussian_blur(&gray, &mut blurred, Size::new(5, 5), 0.0, 0.0, core::BORDER_REPLICATE)?;
+        let mean = core::mean(&blurred, &core::no_array())?[0];
+        let mut gain = Mat::new_rows_cols_with_default(blurred.rows(), blurred.cols(), core::CV_32FC1, core::Scalar::all(1.0))?;
+        for row in 0..blurred.rows() {
+            for col in 0..blurred.cols() {
+                let value = *blurred.at_2d::<u8>(row, col)? as f64;
+                // Black pixels of the reference carry no information, left as is
+                if value > 0.0 {
+                    *gain.at_2d_mut::<f32>(row, col)? = (mean / value) as f32;
+                }
+            }
+        }
+        Ok(Self { gain })
+    }
+    ///
+    /// Returns the `luma` of the frame corrected by the gain
+    pub fn apply(&self, luma: &Mat) -> Result<Mat, opencv::Error> {
+        if luma.size()? != self.gain.size()? {
+            return Err(opencv::Error::new(
+                core::StsUnmatchedSizes,
+                format!("Flat field {:?} doesn't match the frame {:?}", self.gain.size()?, luma.size()?),
+            ));
+        }
+        let mut src = Mat::default();
+        luma.convert_to(&mut src, core::CV_32F, 1.0, 0.0)?;
+        let mut corrected = Mat::default();
+        core::multiply(&src, &self.gain, &mut corrected, 1.0, -1)?;
+        let mut dst = Mat::default();
+        corrected.convert_to(&mut dst, core::CV_8U, 1.0, 0.0)?;
+        Ok(dst)
+    }
+}
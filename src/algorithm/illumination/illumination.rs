@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use opencv::{
+    core::{self, Mat, MatTrait, MatTraitConst, Size, Vector},
+    imgproc::{self, CLAHETrait},
+};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        illumination::{Brightness, FlatField, IlluminationConf, IlluminationCtx},
+        ContextRead, ContextWrite, EvalResult, InitialCtx,
+    },
+    domain::{Error, Eval, Image, RwLock},
+};
+///
+/// Stabilises brightness, contrast and gamma of the frame,
+/// so the edge thresholds behave the same in the daylight, at night and with flickering lighting
+/// - Flat-field correction, if [FlatField] given
+/// - Brightness brought to the level smoothed over the frames, suppresses the flicker
+/// - Gamma correction
+/// - Contrast limited adaptive histogram equalization (CLAHE)
+/// - Normalised frame stored in the [IlluminationCtx], the `src_frame` of the [InitialCtx] left untouched,
+///   it's original colour and brightness needed by the surface analysis
+/// - Must be inserted before `Orientation`, the [FlatField] is built in the camera coordinates
+pub struct Illumination {
+    dbg: Dbg,
+    conf: IlluminationConf,
+    flat_field: Option<Arc<FlatField>>,
+    brightness: Arc<RwLock<Brightness>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Illumination {
+    ///
+    /// New instance [Illumination]
+    /// - `flat_field` - correction of the uneven lighting, built from the reference frame of the camera
+    /// - `brightness` - smoothed brightness, shared between the frames of the same camera
+    pub fn new(
+        conf: IlluminationConf,
+        flat_field: Option<Arc<FlatField>>,
+        brightness: Arc<RwLock<Brightness>>,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("Illumination"),
+            conf,
+            flat_field,
+            brightness,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns the look-up table of the gamma correction
+    fn gamma(gamma: f64) -> Result<Mat, opencv::Error> {
+        let mut lut = Mat::new_rows_cols_with_default(1, 256, core::CV_8UC1, core::Scalar::all(0.0))?;
+        for value in 0..256 {
+            *lut.at_2d_mut::<u8>(0, value)? = (255.0 * (value as f64 / 255.0).powf(1.0 / gamma)).round() as u8;
+        }
+        Ok(lut)
+    }
+    ///
+    /// Returns the corrected `luma` of the frame and the brightness measured
+    fn correct(&self, luma: Mat) -> Result<(Mat, IlluminationCtx), opencv::Error> {
+        let mut luma = match &self.flat_field {
+            Some(flat_field) => flat_field.apply(&luma)?,
+            None => luma,
+        };
+        let brightness = core::mean(&luma, &core::no_array())?[0];
+        let level = {
+            let mut state = self.brightness.write();
+            state.level = match state.measured {
+                true => state.level + self.conf.smoothing.clamp(0.0, 1.0) * (brightness - state.level),
+                false => brightness,
+            };
+            state.measured = true;
+            state.level
+        };
+        let gain = if brightness > 0.0 { level / brightness } else { 1.0 };
+        if (gain - 1.0).abs() > f64::EPSILON {
+            let mut dst = Mat::default();
+            luma.convert_to(&mut dst, core::CV_8U, gain, 0.0)?;
+            luma = dst;
+        }
+        if (self.conf.gamma - 1.0).abs() > f64::EPSILON && self.conf.gamma > 0.0 {
+            let mut dst = Mat::default();
+            core::lut(&luma, &Self::gamma(self.conf.gamma)?, &mut dst)?;
+            luma = dst;
+        }
+        if self.conf.clahe_clip > 0.0 {
+            let tiles = self.conf.clahe_tiles.max(1) as i32;
+            let mut clahe = imgproc::create_clahe(self.conf.clahe_clip, Size::new(tiles, tiles))?;
+            let mut dst = Mat::default();
+            clahe.apply(&luma, &mut dst)?;
+            luma = dst;
+        }
+        Ok((luma, IlluminationCtx { brightness, level, gain, frame: Image::default() }))
+    }
+    ///
+    /// Returns the frame normalised
+    fn normalise(&self, image: &Image) -> Result<(Mat, IlluminationCtx), opencv::Error> {
+        match image.mat.channels() {
+            1 => self.correct(image.mat.clone()),
+            _ => {
+                // Luma of YCrCb is exactly the gray of the frame
+                let mut ycrcb = Mat::default();
+                imgproc::cvt_color(&image.mat, &mut ycrcb, imgproc::COLOR_BGR2YCrCb, 0)?;
+                let mut channels = Vector::<Mat>::new();
+                core::split(&ycrcb, &mut channels)?;
+                let (luma, result) = self.correct(channels.get(0)?)?;
+                channels.set(0, luma)?;
+                core::merge(&channels, &mut ycrcb)?;
+                let mut dst = Mat::default();
+                imgproc::cvt_color(&ycrcb, &mut dst, imgproc::COLOR_YCrCb2BGR, 0)?;
+                Ok((dst, result))
+            }
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Illumination {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let src = ContextRead::<InitialCtx>::read(&ctx).src_frame.clone();
+                let (mat, result) = match self.normalise(&src) {
+                    Ok(normalised) => normalised,
+                    Err(err) => return Err(error.pass_with("Illumination normalisation error", err.to_string())),
+                };
+                log::debug!(
+                    "{}.eval | Brightness: {:.1}, level: {:.1}, gain: {:.3}",
+                    self.dbg, result.brightness, result.level, result.gain,
+                );
+                let frame = Image {
+                    width: src.width,
+                    height: src.height,
+                    timestamp: src.timestamp,
+                    mat,
+                    bytes: src.bytes,
+                };
+                ctx.write(IlluminationCtx { frame, ..result })
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
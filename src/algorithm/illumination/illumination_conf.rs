@@ -0,0 +1,25 @@
+///
+/// Configuration parameters of the `Illumination`
+/// - `clahe_clip` - contrast limit of the adaptive histogram equalization (CLAHE), 0.0 - disabled
+/// - `clahe_tiles` - number of the CLAHE tiles along the each side of the frame
+/// - `gamma` - gamma correction `out = in ^ (1 / gamma)`, 1.0 - disabled, greater brightens the shadows
+/// - `smoothing` - 0.0..=1.0, weight of the current frame in the smoothed brightness, 1.0 - disabled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IlluminationConf {
+    pub clahe_clip: f64,
+    pub clahe_tiles: usize,
+    pub gamma: f64,
+    pub smoothing: f64,
+}
+//
+//
+impl Default for IlluminationConf {
+    fn default() -> Self {
+        Self {
+            clahe_clip: 2.0,
+            clahe_tiles: 8,
+            gamma: 1.0,
+            smoothing: 0.2,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use opencv::core::MatTraitConst;
+use crate::domain::Image;
+///
+/// Store result of `Illumination`
+/// - `brightness` - mean brightness of the luma before correction
+/// - `level` - smoothed brightness the frame brought to
+/// - `gain` - brightness gain applied to the frame
+/// - `frame` - the frame normalised, in the coordinates of the `src_frame` of the `InitialCtx`,
+///   rotated and cropped along with it, empty if the frame isn't normalised
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IlluminationCtx {
+    pub brightness: f64,
+    pub level: f64,
+    pub gain: f64,
+    pub frame: Image,
+}
+//
+//
+impl IlluminationCtx {
+    ///
+    /// Returns the normalised frame, the `src_frame` if the frame isn't normalised
+    pub fn frame_or<'a>(&'a self, src_frame: &'a Image) -> &'a Image {
+        match self.frame.mat.empty() {
+            true => src_frame,
+            false => &self.frame,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//!
+//! Illumination normalisation
+//! - Brightness, contrast and gamma of the frame stabilised, to compensate the lighting changes
+//! - Processed only the luma of the frame, so the colors are kept
+//! - Normalised frame kept aside of the source one, the edges and the texture found on it,
+//!   the surface analysis takes the original colour and brightness
+mod brightness;
+mod flat_field;
+mod illumination_conf;
+mod illumination_ctx;
+mod illumination;
+
+pub use brightness::*;
+pub use flat_field::*;
+pub use illumination_conf::*;
+pub use illumination_ctx::*;
+pub use illumination::*;
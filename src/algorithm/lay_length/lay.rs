@@ -0,0 +1,25 @@
+///
+/// Lay of the rope, measured on the single frame
+/// - `pitch` - distance between neighbouring strands along the rope, px
+/// - `length` - lay length, distance of one full turn of the strand, px
+/// - `strands` - number of the outer strands
+/// - `direction` - lay direction
+/// - `pitch_mm`, `length_mm` - the same in mm, None if the camera scale unknown
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lay {
+    pub pitch: f64,
+    pub length: f64,
+    pub strands: usize,
+    pub direction: LayDirection,
+    pub pitch_mm: Option<f64>,
+    pub length_mm: Option<f64>,
+}
+///
+/// Direction of the strands lay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayDirection {
+    /// Right hand lay (Z)
+    Right,
+    /// Left hand lay (S)
+    Left,
+}
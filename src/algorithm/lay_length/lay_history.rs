@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+use crate::algorithm::lay_length::Lay;
+///
+/// Lay of the section of the rope averaged over the frames passed it
+/// - `length` - mean lay length, px
+/// - `pitch` - mean distance between neighbouring strands, px
+/// - `frames` - number of the frames measured
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LaySection {
+    pub length: f64,
+    pub pitch: f64,
+    pub frames: usize,
+}
+///
+/// Lay measured along the rope, by the [LayHistory::SECTION] long sections
+/// - `sections` - lay of the sections, by the index of the section
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LayHistory {
+    pub sections: BTreeMap<i64, LaySection>,
+}
+//
+//
+impl LayHistory {
+    /// Length of the section of the rope, m
+    pub const SECTION: f64 = 1.0;
+    ///
+    /// Returns index of the section at the `position` along the rope, m
+    fn section(position: f64) -> i64 {
+        (position / Self::SECTION).floor() as i64
+    }
+    ///
+    /// Adds the `lay` measured at the `position` along the rope, m, returns the section updated
+    pub fn push(&mut self, position: f64, lay: &Lay) -> LaySection {
+        let section = self.sections.entry(Self::section(position)).or_default();
+        let rate = 1.0 / (section.frames + 1) as f64;
+        section.length += rate * (lay.length - section.length);
+        section.pitch += rate * (lay.pitch - section.pitch);
+        section.frames += 1;
+        *section
+    }
+    ///
+    /// Returns nominal lay length of the rope, median of the sections, px, None if nothing measured,
+    /// the median isn't pulled by the few elongated sections
+    pub fn nominal(&self) -> Option<f64> {
+        let mut lengths: Vec<f64> = self.sections.values().map(|section| section.length).collect();
+        lengths.sort_by(f64::total_cmp);
+        let len = lengths.len();
+        match len {
+            0 => None,
+            _ if len % 2 == 1 => Some(lengths[len / 2]),
+            _ => Some((lengths[len / 2 - 1] + lengths[len / 2]) / 2.0),
+        }
+    }
+}
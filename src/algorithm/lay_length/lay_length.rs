@@ -0,0 +1,215 @@
+use std::{f64::consts::PI, sync::Arc};
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::Scale,
+        lay_length::{Lay, LayDirection, LayHistory, LayLengthCtx},
+        position::PositionCtx,
+        ContextRead, ContextWrite, DetectingContoursCvCtx, EdgeDetectionCtx, EvalResult, Side,
+    },
+    domain::{Error, Eval, Image, RwLock},
+};
+///
+/// Measures lay length, strand count and lay direction of the rope
+/// - `pitch` - period of the strand pattern along the static axis, from the autocorrelation of the profile
+/// - Strands orientation - from the structure tensor of the gradient image inside the rope band
+/// - Near the rope axis strand slope `dx/dy` equals to `length / (π * D)`,
+///   so `length = π * D * |dx/dy|`, and `strands = length / pitch`
+/// - Along the rope - the lay of the frame averaged over the section of the rope at the [PositionCtx::position],
+///   the elongation of the section taken over the nominal lay, the median of the sections measured
+/// - Must be inserted after `EdgeDetection` and `Position`
+pub struct LayLength {
+    dbg: Dbg,
+    scale: Option<Scale>,
+    history: Arc<RwLock<LayHistory>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl LayLength {
+    /// Minimal normalized autocorrelation of the pitch peak
+    const MIN_CORRELATION: f64 = 0.1;
+    ///
+    /// New instance [LayLength]
+    /// - `scale` - pixel to millimetre calibration of the camera, if known
+    /// - `history` - lay along the rope, shared between the frames of the same camera
+    pub fn new(scale: Option<Scale>, history: Arc<RwLock<LayHistory>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("LayLength"),
+            scale,
+            history,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns pixel value as f64
+    fn at(image: &Image, row: usize, col: usize) -> Result<f64, String> {
+        image.mat.at_2d::<u8>(row as i32, col as i32)
+            .map(|&value| value as f64)
+            .map_err(|err| err.to_string())
+    }
+    ///
+    /// Returns texture profile along the static axis
+    /// - mean of the 3 rows around the center of the band for each column
+    fn profile(image: &Image, band: &[[usize; 3]]) -> Result<Vec<f64>, String> {
+        let mut profile = Vec::with_capacity(band.len());
+        for &[col, top, bottom] in band {
+            let center = (top + bottom) / 2;
+            let rows = center.saturating_sub(1).max(top)..=(center + 1).min(bottom);
+            let mut sum = 0.0;
+            for row in rows.clone() {
+                sum += Self::at(image, row, col)?;
+            }
+            profile.push(sum / rows.count() as f64);
+        }
+        Ok(profile)
+    }
+    ///
+    /// Returns period of the profile, px
+    /// - highest peak of the normalized autocorrelation after it's first zero crossing
+    /// - refined by parabolic interpolation
+    fn pitch(profile: &[f64]) -> Option<f64> {
+        let len = profile.len();
+        if len < 4 {
+            return None;
+        }
+        let mean = profile.iter().sum::<f64>() / len as f64;
+        let values: Vec<f64> = profile.iter().map(|value| value - mean).collect();
+        let energy: f64 = values.iter().map(|value| value * value).sum();
+        if energy <= 0.0 {
+            return None;
+        }
+        let correlation: Vec<f64> = (0..len / 2).map(|lag| {
+            values.iter().zip(values.iter().skip(lag)).map(|(a, b)| a * b).sum::<f64>() / energy
+        }).collect();
+        let crossing = correlation.iter().position(|&value| value < 0.0)?;
+        let (lag, &peak) = correlation.iter()
+            .enumerate()
+            .skip(crossing)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if peak < Self::MIN_CORRELATION {
+            return None;
+        }
+        match (correlation.get(lag - 1), correlation.get(lag + 1)) {
+            (Some(&prev), Some(&next)) => {
+                let denominator = prev - 2.0 * peak + next;
+                if denominator.abs() > f64::EPSILON {
+                    Some(lag as f64 + 0.5 * (prev - next) / denominator)
+                } else {
+                    Some(lag as f64)
+                }
+            }
+            _ => Some(lag as f64),
+        }
+    }
+    ///
+    /// Returns slope `dx/dy` of the strands inside the rope band
+    /// - dominant orientation from the structure tensor of the image
+    /// - rows next to the edges are skipped
+    fn slope(image: &Image, band: &[[usize; 3]]) -> Result<Option<f64>, String> {
+        let (mut jxx, mut jyy, mut jxy) = (0.0, 0.0, 0.0);
+        let cols = image.mat.cols() as usize;
+        for &[col, top, bottom] in band {
+            if col == 0 || col + 1 >= cols || bottom < top + 4 {
+                continue;
+            }
+            for row in (top + 2)..=(bottom - 2) {
+                let gx = (Self::at(image, row, col + 1)? - Self::at(image, row, col - 1)?) / 2.0;
+                let gy = (Self::at(image, row + 1, col)? - Self::at(image, row - 1, col)?) / 2.0;
+                jxx += gx * gx;
+                jyy += gy * gy;
+                jxy += gx * gy;
+            }
+        }
+        if jxx + jyy <= 0.0 {
+            return Ok(None);
+        }
+        let theta = 0.5 * (2.0 * jxy).atan2(jxx - jyy);
+        Ok(Some(-theta.tan()))
+    }
+    ///
+    /// Returns first and last column of each lay length along the band
+    fn windows(band: &[[usize; 3]], length: f64) -> Vec<(usize, usize)> {
+        match (band.first(), band.last()) {
+            (Some(first), Some(last)) if length >= 1.0 => {
+                let (first, last) = (first[0], last[0]);
+                let mut windows = vec![];
+                let mut start = first as f64;
+                while start <= last as f64 {
+                    let end = (start + length).ceil() as usize - 1;
+                    windows.push((start.round() as usize, end.min(last)));
+                    start += length;
+                }
+                windows
+            }
+            _ => vec![],
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for LayLength {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let image = &ContextRead::<DetectingContoursCvCtx>::read(&ctx).result;
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let band: Vec<[usize; 3]> = edges.get(Side::Upper).iter()
+                    .zip(edges.get(Side::Lower).iter())
+                    .map(|(upper, lower)| [upper.x as usize, upper.y.min(lower.y).round() as usize, upper.y.max(lower.y).round() as usize])
+                    .collect();
+                let profile = match Self::profile(image, &band) {
+                    Ok(profile) => profile,
+                    Err(err) => return Err(error.pass_with("Input image format error", err)),
+                };
+                let slope = match Self::slope(image, &band) {
+                    Ok(slope) => slope,
+                    Err(err) => return Err(error.pass_with("Input image format error", err)),
+                };
+                let diameter = match band.len() {
+                    0 => 0.0,
+                    len => band.iter().map(|[_, top, bottom]| (bottom - top) as f64).sum::<f64>() / len as f64,
+                };
+                let result = match (Self::pitch(&profile), slope) {
+                    (Some(pitch), Some(slope)) if slope != 0.0 && diameter > 0.0 => {
+                        let length = PI * diameter * slope.abs();
+                        Some(Lay {
+                            pitch,
+                            length,
+                            strands: ((length / pitch).round() as usize).max(1),
+                            direction: if slope > 0.0 { LayDirection::Right } else { LayDirection::Left },
+                            pitch_mm: self.scale.map(|scale| scale.mm(pitch)),
+                            length_mm: self.scale.map(|scale| scale.mm(length)),
+                        })
+                    }
+                    _ => None,
+                };
+                let windows = match &result {
+                    Some(lay) => Self::windows(&band, lay.length),
+                    None => vec![],
+                };
+                let position = ContextRead::<PositionCtx>::read(&ctx).position;
+                let mut history = self.history.write();
+                let section = match (position, &result) {
+                    (Some(position), Some(lay)) => Some(history.push(position, lay)),
+                    _ => None,
+                };
+                let elongation = section
+                    .zip(history.nominal())
+                    .filter(|(_, nominal)| *nominal > 0.0)
+                    .map(|(section, nominal)| section.length / nominal - 1.0);
+                log::debug!("{}.eval | Lay: {:?}, section: {:?}, elongation: {:?}", self.dbg, result, section, elongation);
+                ctx.write(LayLengthCtx {
+                    result,
+                    windows,
+                    section,
+                    section_mm: section.zip(self.scale).map(|(section, scale)| scale.mm(section.length)),
+                    elongation,
+                })
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::algorithm::lay_length::{Lay, LaySection};
+///
+/// Store result of `LayLength`
+/// - `result` - measured lay, None if strand pattern isn't recognized
+/// - `windows` - first and last column of each lay length along the frame,
+///   used to count defect's per lay length
+/// - `section` - lay of the section of the rope the frame at, averaged over the frames, None if the position unknown
+/// - `section_mm` - lay length of the section, mm, None if the camera scale unknown
+/// - `elongation` - relative elongation of the lay of the section over the nominal lay of the rope,
+///   a sign of the core damage and the rope stretch
+#[derive(Debug, Clone, Default)]
+pub struct LayLengthCtx {
+    pub result: Option<Lay>,
+    pub windows: Vec<(usize, usize)>,
+    pub section: Option<LaySection>,
+    pub section_mm: Option<f64>,
+    pub elongation: Option<f64>,
+}
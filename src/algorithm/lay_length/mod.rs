@@ -0,0 +1,13 @@
+//!
+//! Measuring lay length of the rope
+//! using the periodic strand pattern inside the rope band
+//! and aggregating it along the rope
+mod lay_history;
+mod lay_length_ctx;
+mod lay_length;
+mod lay;
+
+pub use lay_history::*;
+pub use lay_length_ctx::*;
+pub use lay_length::*;
+pub use lay::*;
@@ -0,0 +1,40 @@
+use crate::domain::Eval;
+use super::{OutlierCtx, RollingMad};
+///
+/// Hampel filter
+/// - The points deviating from the sliding window median over [Hampel::CLEAN] sigmas
+///   replaced by that median, then the sliding window median and MAD taken again over the cleaned sample,
+///   so the neighbour outliers don't inflate the spread of each other's windows
+/// - Scores of the original points against the cleaned windows
+pub struct Hampel {
+    window: usize,
+}
+//
+//
+impl Hampel {
+    /// Deviation in the sigmas, the point replaced by the window median in the first pass
+    pub(super) const CLEAN: f64 = 3.0;
+    ///
+    /// New instance [Hampel]
+    /// - `window` - number of the points in the window, made odd to be centered
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+}
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for Hampel {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        let (center, spread) = RollingMad::rolling(&sample, self.window);
+        let cleaned: Vec<f64> = sample.iter().zip(center.iter().zip(spread.iter()))
+            .map(|(&point, (&center, &spread))| {
+                match OutlierCtx::score((point - center).abs(), spread) > Self::CLEAN {
+                    true => center,
+                    false => point,
+                }
+            })
+            .collect();
+        let (center, spread) = RollingMad::rolling(&cleaned, self.window);
+        OutlierCtx::new(&sample, center, spread)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::domain::Eval;
+use super::{Mad, OutlierCtx};
+///
+/// Interquartile range, Tukey's fences
+/// - The points outside of the quartiles scored by the distance to the nearest quartile,
+///   the points between the quartiles scored linearly from the median up to the quartile
+/// - The spread is the half of the range, the MAD of the normal distribution,
+///   so the threshold 2.7 sigmas is the classic fence 1.5 ranges off the quartile
+pub struct Iqr;
+//
+//
+impl Iqr {
+    /// Quartile of the normal distribution in the sigmas
+    const QUARTILE: f64 = 0.6745;
+    ///
+    /// New instance [Iqr]
+    pub fn new() -> Self {
+        Self {}
+    }
+    ///
+    /// Returns the quantile `p` of the sorted `values`, linear interpolation between the closest ranks
+    fn quantile(values: &[f64], p: f64) -> f64 {
+        let position = (values.len() - 1) as f64 * p;
+        let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+        values[lower] + (values[upper] - values[lower]) * (position - lower as f64)
+    }
+    ///
+    /// Returns the score in the sigmas of the `point` against the quartiles `q1`, `q3` and the `median`
+    fn score(point: f64, (q1, median, q3): (f64, f64, f64), spread: f64) -> f64 {
+        let inner = |deviation: f64, quartile: f64| match quartile > 0.0 {
+            true => Self::QUARTILE * deviation / quartile,
+            false => 0.0,
+        };
+        match point {
+            _ if point > q3 => Self::QUARTILE + OutlierCtx::score(point - q3, spread),
+            _ if point < q1 => Self::QUARTILE + OutlierCtx::score(q1 - point, spread),
+            _ if point >= median => inner(point - median, q3 - median),
+            _ => inner(median - point, median - q1),
+        }
+    }
+}
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for Iqr {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        if sample.is_empty() {
+            return OutlierCtx::default();
+        }
+        let mut sorted = sample.clone();
+        sorted.sort_by(f64::total_cmp);
+        let quartiles = (Self::quantile(&sorted, 0.25), Mad::median(&sample), Self::quantile(&sorted, 0.75));
+        let spread = (quartiles.2 - quartiles.0) / 2.0;
+        let score = sample.iter().map(|&point| Self::score(point, quartiles, spread)).collect();
+        OutlierCtx { center: vec![quartiles.1; sample.len()], spread: vec![spread; sample.len()], score }
+    }
+}
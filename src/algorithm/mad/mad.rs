@@ -1,5 +1,5 @@
 use crate::domain::Eval;
-use super::MadCtx;
+use super::{MadCtx, OutlierCtx};
 ///
 /// Median Absolute Deviation
 pub struct Mad;
@@ -13,14 +13,9 @@ impl Mad {
     }
     ///
     /// Calculate median
-    fn median(points: &[usize]) -> f64 {
-        let mut values: Vec<f64> = points
-            .iter()
-            .map(|point| *point as f64)
-        .collect();
-        values
-            .sort_by(|a, b| a.partial_cmp(b)
-        .unwrap());
+    pub(super) fn median(points: &[f64]) -> f64 {
+        let mut values: Vec<f64> = points.to_vec();
+        values.sort_by(f64::total_cmp);
         let len = values.len();
         if len % 2 == 1 {
             values[len / 2]
@@ -30,13 +25,11 @@ impl Mad {
     }
     ///
     /// Calculate Median Absolute Deviation
-    fn mad(sample: &[usize], median: f64) -> f64 {
+    pub(super) fn mad(sample: &[f64], median: f64) -> f64 {
         let mut deviations: Vec<f64> = sample.iter()
-            .map(|point| (*point as f64 - median).abs())
+            .map(|point| (point - median).abs())
         .collect();
-        deviations
-            .sort_by(|a, b| a.partial_cmp(b)
-        .unwrap());
+        deviations.sort_by(f64::total_cmp);
         let len = deviations.len();
         if len % 2 == 1 {
             deviations[len / 2]
@@ -49,8 +42,26 @@ impl Mad {
 //
 impl Eval<Vec<usize>, MadCtx> for Mad {
     fn eval(&self, sample: Vec<usize>) -> MadCtx {
+        Eval::<Vec<f64>, MadCtx>::eval(self, sample.into_iter().map(|point| point as f64).collect())
+    }
+}
+//
+//
+impl Eval<Vec<f64>, MadCtx> for Mad {
+    fn eval(&self, sample: Vec<f64>) -> MadCtx {
+        if sample.is_empty() {
+            return MadCtx::default();
+        }
         let median = Self::median(&sample);
         let mad = Self::mad(&sample, median);
         MadCtx { median, mad }
     }
 }
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for Mad {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        let MadCtx { median, mad } = Eval::<Vec<f64>, MadCtx>::eval(self, sample.clone());
+        OutlierCtx::new(&sample, vec![median; sample.len()], vec![mad; sample.len()])
+    }
+}
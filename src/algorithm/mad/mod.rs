@@ -2,10 +2,29 @@
 //! In this module realises algorithm's that search outliners in the sample
 //! Realized algorithm's:
 //! - `MAD` (Mediana Absolute Deviation)
+//! - `RollingMad` - MAD in the sliding window
+//! - `Hampel` - Hampel filter, sliding window MAD over the sample cleaned from the outliers
+//! - `ZScore` - robust z-score
+//! - `Iqr` - interquartile range, Tukey's fences
+//!
+//! Each detector scores every point of the sample in the sigmas and gives the spread in the MADs,
+//! so the same threshold means the same for each detector, see [OutlierCtx]
 mod bond;
+mod hampel;
+mod iqr;
 mod mad_ctx;
 mod mad;
+mod outlier_ctx;
+mod outlier_detector;
+mod rolling_mad;
+mod z_score;
 
 pub use bond::*;
+pub use hampel::*;
+pub use iqr::*;
 pub use mad_ctx::*;
 pub use mad::*;
+pub use outlier_ctx::*;
+pub use outlier_detector::*;
+pub use rolling_mad::*;
+pub use z_score::*;
@@ -0,0 +1,46 @@
+use super::MadCtx;
+///
+/// Store per point result of the outlier detectors
+/// - `center` - robust center of the sample around the point
+/// - `spread` - robust spread of the sample around the point, in the MADs,
+///   so the thresholds of the consumers set in the MADs means the same for each detector
+/// - `score` - deviation of the point from the `center` in the sigmas of the normal distribution,
+///   the point is outlier if it's score exceeds the threshold
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OutlierCtx {
+    pub center: Vec<f64>,
+    pub spread: Vec<f64>,
+    pub score: Vec<f64>,
+}
+//
+//
+impl OutlierCtx {
+    /// MAD to the standard deviation of the normal distribution
+    pub const SIGMA: f64 = 1.4826;
+    ///
+    /// Returns [OutlierCtx] new instance, scores of the `sample` calculated
+    /// - deviation from the zero spread scored as infinite
+    pub fn new(sample: &[f64], center: Vec<f64>, spread: Vec<f64>) -> Self {
+        let score = sample.iter().zip(center.iter().zip(spread.iter()))
+            .map(|(point, (center, spread))| Self::score((point - center).abs(), *spread))
+            .collect();
+        Self { center, spread, score }
+    }
+    ///
+    /// Returns the `deviation` in the sigmas of the `spread` given in the MADs
+    pub(super) fn score(deviation: f64, spread: f64) -> f64 {
+        match spread > 0.0 {
+            true => deviation / (Self::SIGMA * spread),
+            false if deviation > 0.0 => f64::INFINITY,
+            false => 0.0,
+        }
+    }
+    ///
+    /// Returns center and spread at the point `index` as [MadCtx], default if out of the sample
+    pub fn at(&self, index: usize) -> MadCtx {
+        match (self.center.get(index), self.spread.get(index)) {
+            (Some(&median), Some(&mad)) => MadCtx { median, mad },
+            _ => MadCtx::default(),
+        }
+    }
+}
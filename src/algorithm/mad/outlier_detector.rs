@@ -0,0 +1,31 @@
+use crate::domain::Eval;
+use super::{Hampel, Iqr, Mad, OutlierCtx, RollingMad, ZScore};
+///
+/// Outlier detector selectable in the configuration
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutlierDetector {
+    /// [Mad] of the whole sample
+    #[default]
+    Mad,
+    /// [RollingMad] in the `window` points
+    RollingMad { window: usize },
+    /// [Hampel] filter in the `window` points
+    Hampel { window: usize },
+    /// Robust [ZScore] of the whole sample
+    ZScore,
+    /// [Iqr] of the whole sample
+    Iqr,
+}
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for OutlierDetector {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        match *self {
+            OutlierDetector::Mad => Eval::<Vec<f64>, OutlierCtx>::eval(&Mad::new(), sample),
+            OutlierDetector::RollingMad { window } => RollingMad::new(window).eval(sample),
+            OutlierDetector::Hampel { window } => Hampel::new(window).eval(sample),
+            OutlierDetector::ZScore => ZScore::new().eval(sample),
+            OutlierDetector::Iqr => Iqr::new().eval(sample),
+        }
+    }
+}
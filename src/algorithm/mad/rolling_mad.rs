@@ -0,0 +1,44 @@
+use crate::domain::Eval;
+use super::{Mad, OutlierCtx};
+///
+/// Median Absolute Deviation in the sliding window
+/// - Center and spread of each point taken from the `window` points around it,
+///   so the gradual taper along the frame doesn't hide the short local bulges
+/// - Spread of the window limited below by [RollingMad::FLOOR],
+///   so the single pixel step on the flat edge isn't scored as the infinite outlier
+pub struct RollingMad {
+    window: usize,
+}
+//
+//
+impl RollingMad {
+    /// Lowest spread of the window in the MADs, the half of the pixel, quantisation of the edges
+    pub(super) const FLOOR: f64 = 0.5;
+    ///
+    /// New instance [RollingMad]
+    /// - `window` - number of the points in the window, made odd to be centered
+    pub fn new(window: usize) -> Self {
+        Self { window }
+    }
+    ///
+    /// Returns local median and local MAD, not less than [RollingMad::FLOOR], of each point of the `sample`
+    pub(super) fn rolling(sample: &[f64], window: usize) -> (Vec<f64>, Vec<f64>) {
+        let half = window / 2;
+        sample.iter()
+            .enumerate()
+            .map(|(i, _)| {
+                let window = &sample[i.saturating_sub(half)..(i + half + 1).min(sample.len())];
+                let median = Mad::median(window);
+                (median, Mad::mad(window, median).max(Self::FLOOR))
+            })
+            .unzip()
+    }
+}
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for RollingMad {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        let (center, spread) = Self::rolling(&sample, self.window);
+        OutlierCtx::new(&sample, center, spread)
+    }
+}
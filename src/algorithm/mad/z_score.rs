@@ -0,0 +1,38 @@
+use crate::domain::Eval;
+use super::{Mad, OutlierCtx};
+///
+/// Robust z-score, modified z-score of Iglewicz and Hoaglin
+/// - Deviation from the median of the whole sample in the sigmas estimated by the scaled MAD
+/// - If the half of the sample is equal and the MAD is zero,
+///   the sigma is estimated by the scaled mean absolute deviation from the median
+pub struct ZScore;
+//
+//
+impl ZScore {
+    /// Mean absolute deviation to the standard deviation of the normal distribution
+    const MEAN: f64 = 1.2533;
+    ///
+    /// New instance [ZScore]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+//
+//
+impl Eval<Vec<f64>, OutlierCtx> for ZScore {
+    fn eval(&self, sample: Vec<f64>) -> OutlierCtx {
+        if sample.is_empty() {
+            return OutlierCtx::default();
+        }
+        let median = Mad::median(&sample);
+        let mad = Mad::mad(&sample, median);
+        let spread = match mad > 0.0 {
+            true => mad,
+            false => {
+                let mean = sample.iter().map(|point| (point - median).abs()).sum::<f64>() / sample.len() as f64;
+                Self::MEAN * mean / OutlierCtx::SIGMA
+            }
+        };
+        OutlierCtx::new(&sample, vec![median; sample.len()], vec![spread; sample.len()])
+    }
+}
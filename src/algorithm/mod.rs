@@ -2,20 +2,56 @@ mod context;
 mod detecting_contours;
 mod detecting_contours_cv;
 mod fast_scan;
+mod geometry;
 mod geometry_defect;
 mod graham;
 mod initial_ctx;
 mod mad;
+mod surface_defect;
 mod width_emissions;
 mod initial_points;
+mod lay_length;
+mod diameter;
+mod undistort;
+mod rope_axis;
+mod profile_defect;
+mod orientation;
+mod roi;
+mod illumination;
+mod background;
+mod baseline;
+mod discard;
+mod position;
+mod speed;
+mod panorama;
+mod defect_tracker;
+mod vibration;
 
 pub use context::*;
 pub use fast_scan::*;
+pub use geometry::*;
 pub use geometry_defect::*;
 pub use graham::*;
 pub use detecting_contours::*;
 pub use detecting_contours_cv::*;
 pub use initial_ctx::*;
 pub use mad::*;
+pub use surface_defect::*;
 pub use width_emissions::*;
 pub use initial_points::*;
+pub use lay_length::*;
+pub use diameter::*;
+pub use undistort::*;
+pub use rope_axis::*;
+pub use profile_defect::*;
+pub use orientation::*;
+pub use roi::*;
+pub use illumination::*;
+pub use background::*;
+pub use baseline::*;
+pub use discard::*;
+pub use position::*;
+pub use speed::*;
+pub use panorama::*;
+pub use defect_tracker::*;
+pub use vibration::*;
@@ -0,0 +1,9 @@
+//!
+//! Orientation of the rope in the frame
+//! - The rope axis angle estimated by PCA of the strong gradient pixels
+//! - The frame rotated into the rope-aligned strip, so the rope runs left to right
+mod orientation_ctx;
+mod orientation;
+
+pub use orientation_ctx::*;
+pub use orientation::*;
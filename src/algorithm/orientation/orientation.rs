@@ -0,0 +1,189 @@
+use opencv::{
+    core::{self, Mat, MatTrait, MatTraitConst, Point2f, Size},
+    imgproc,
+};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{illumination::IlluminationCtx, orientation::OrientationCtx, ContextRead, ContextWrite, EvalResult, InitialCtx},
+    domain::{Error, Eval, Image},
+};
+///
+/// Detects the angle of the rope axis and normalises the frame, so the rope runs left to right
+/// - Axis angle - principal direction (PCA) of the pixels with the strong gradient,
+///   the rope boundaries dominate them along the whole frame
+/// - Frame rotated around it's center into the strip of the fixed size - size of the source frame,
+///   swapped if the rope runs closer to the columns, so the rows of the strip don't jump with the angle of the frame
+/// - Corners of the strip outside of the source frame filled by the replicated border of the frame,
+///   so they don't add the false edges to the following stages
+/// - Angle detected on the normalised frame of the [IlluminationCtx], if given
+/// - Replaces `src_frame` of the [InitialCtx] and the normalised frame of the [IlluminationCtx],
+///   so must be inserted before `DetectingContoursCv`, all the following stages work in the rope coordinates
+pub struct Orientation {
+    dbg: Dbg,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Orientation {
+    /// Angles less then this are not corrected, degrees
+    const MIN_ANGLE: f64 = 0.5;
+    /// Pixel considered as the strong gradient if it exceeds this part of the maximum gradient
+    const GRADIENT: f64 = 0.5;
+    /// Minimal number of the strong gradient pixels to estimate the angle
+    const MIN_PIXELS: usize = 16;
+    /// Rope steeper then this runs closer to the columns, the strip is transposed, degrees
+    const VERTICAL: f64 = 45.0;
+    ///
+    /// New instance [Orientation]
+    pub fn new(ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Orientation"),
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns gradient magnitude of the frame
+    fn gradient(image: &Image) -> Result<Mat, opencv::Error> {
+        let gray = match image.mat.channels() {
+            1 => image.mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                imgproc::cvt_color(&image.mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut blurred = Mat::default();
+        imgproc::gaussian_blur(&gray, &mut blurred, Size::new(3, 3), 0.0, 0.0, core::BORDER_DEFAULT)?;
+        let (mut sobelx, mut sobely) = (Mat::default(), Mat::default());
+        imgproc::sobel(&blurred, &mut sobelx, core::CV_16S, 1, 0, 3, 1.0, 0.0, core::BORDER_DEFAULT)?;
+        imgproc::sobel(&blurred, &mut sobely, core::CV_16S, 0, 1, 3, 1.0, 0.0, core::BORDER_DEFAULT)?;
+        let (mut absx, mut absy) = (Mat::default(), Mat::default());
+        core::convert_scale_abs(&sobelx, &mut absx, 1.0, 0.0)?;
+        core::convert_scale_abs(&sobely, &mut absy, 1.0, 0.0)?;
+        let mut grad = Mat::default();
+        core::add_weighted(&absx, 0.5, &absy, 0.5, 0.0, &mut grad, -1)?;
+        Ok(grad)
+    }
+    ///
+    /// Returns angle of the principal axis of the strong gradient pixels, degrees,
+    /// counter-clockwise from the rows, None if there are not enough pixels
+    fn angle(grad: &Mat) -> Result<Option<f64>, opencv::Error> {
+        let mut max = 0.0;
+        core::min_max_loc(grad, None, Some(&mut max), None, None, &core::no_array())?;
+        if max <= 0.0 {
+            return Ok(None);
+        }
+        let threshold = max * Self::GRADIENT;
+        let mut dots = vec![];
+        for row in 0..grad.rows() {
+            for col in 0..grad.cols() {
+                if *grad.at_2d::<u8>(row, col)? as f64 >= threshold {
+                    dots.push((col as f64, row as f64));
+                }
+            }
+        }
+        if dots.len() < Self::MIN_PIXELS {
+            return Ok(None);
+        }
+        let len = dots.len() as f64;
+        let (mean_x, mean_y) = dots.iter().fold((0.0, 0.0), |(x, y), dot| (x + dot.0 / len, y + dot.1 / len));
+        let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+        for (x, y) in &dots {
+            sxx += (x - mean_x).powi(2);
+            syy += (y - mean_y).powi(2);
+            sxy += (x - mean_x) * (y - mean_y);
+        }
+        // Rows go down, so the counter-clockwise angle is negative of the image one
+        Ok(Some(-0.5 * (2.0 * sxy).atan2(sxx - syy).to_degrees()))
+    }
+    ///
+    /// Returns the frame rotated by the `angle` clockwise, so the rope axis becomes horizontal,
+    /// together with the affine transform used
+    fn rotate(image: &Image, angle: f64) -> Result<(Mat, [f64; 6]), opencv::Error> {
+        let (width, height) = (image.mat.cols() as f64, image.mat.rows() as f64);
+        let size = match angle.abs() > Self::VERTICAL {
+            true => Size::new(image.mat.rows(), image.mat.cols()),
+            false => Size::new(image.mat.cols(), image.mat.rows()),
+        };
+        let center = Point2f::new(width as f32 / 2.0, height as f32 / 2.0);
+        // OpenCV rotates counter-clockwise by the positive angle
+        let mut matrix = imgproc::get_rotation_matrix_2d(center, -angle, 1.0)?;
+        *matrix.at_2d_mut::<f64>(0, 2)? += size.width as f64 / 2.0 - width / 2.0;
+        *matrix.at_2d_mut::<f64>(1, 2)? += size.height as f64 / 2.0 - height / 2.0;
+        let mut dst = Mat::default();
+        imgproc::warp_affine(
+            &image.mat,
+            &mut dst,
+            &matrix,
+            size,
+            imgproc::INTER_LINEAR,
+            core::BORDER_REPLICATE,
+            core::Scalar::default(),
+        )?;
+        let mut values = [0.0; 6];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = *matrix.at_2d::<f64>(i as i32 / 3, i as i32 % 3)?;
+        }
+        Ok((dst, values))
+    }
+    ///
+    /// Returns the `image` rotated by the `angle`, see [Orientation::rotate], together with the affine transform used
+    fn turn(image: &Image, angle: f64) -> Result<(Image, [f64; 6]), opencv::Error> {
+        let (mat, matrix) = Self::rotate(image, angle)?;
+        let frame = Image {
+            width: mat.cols() as usize,
+            height: mat.rows() as usize,
+            timestamp: image.timestamp,
+            mat,
+            bytes: image.bytes,
+        };
+        Ok((frame, matrix))
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Orientation {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let src = ContextRead::<InitialCtx>::read(&ctx).src_frame.clone();
+                let illumination = ContextRead::<IlluminationCtx>::read(&ctx).clone();
+                let angle = match Self::gradient(illumination.frame_or(&src)).and_then(|grad| Self::angle(&grad)) {
+                    Ok(angle) => angle,
+                    Err(err) => return Err(error.pass_with("Input image format error", err.to_string())),
+                };
+                match angle {
+                    Some(angle) if angle.abs() >= Self::MIN_ANGLE => {
+                        let (frame, matrix) = match Self::turn(&src, angle) {
+                            Ok(rotated) => rotated,
+                            Err(err) => return Err(error.pass_with("Rotation error", err.to_string())),
+                        };
+                        // The normalised frame rotated along, the same size gives the same transform
+                        let normalised = match illumination.frame.mat.empty() {
+                            true => Image::default(),
+                            false => match Self::turn(&illumination.frame, angle) {
+                                Ok((normalised, _)) => normalised,
+                                Err(err) => return Err(error.pass_with("Rotation error", err.to_string())),
+                            },
+                        };
+                        log::debug!("{}.eval | Rope angle: {:.2} deg, frame rotated", self.dbg, angle);
+                        match ctx.write(InitialCtx::new(frame)) {
+                            Ok(ctx) => match ctx.write(IlluminationCtx { frame: normalised, ..illumination }) {
+                                Ok(ctx) => ctx.write(OrientationCtx { angle, rotated: true, matrix }),
+                                Err(err) => Err(error.pass(err)),
+                            },
+                            Err(err) => Err(error.pass(err)),
+                        }
+                    }
+                    angle => ctx.write(OrientationCtx {
+                        angle: angle.unwrap_or(0.0),
+                        rotated: false,
+                        matrix: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    }),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
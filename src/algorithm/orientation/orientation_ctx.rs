@@ -0,0 +1,11 @@
+///
+/// Store result of `Orientation`
+/// - `angle` - angle of the rope axis in the source frame, degrees, counter-clockwise from the rows
+/// - `rotated` - true if the frame was rotated into the rope-aligned strip
+/// - `matrix` - affine transform from the source frame into the strip, row by row
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OrientationCtx {
+    pub angle: f64,
+    pub rotated: bool,
+    pub matrix: [f64; 6],
+}
@@ -0,0 +1,17 @@
+//!
+//! Unrolled panorama of the rope
+//! - [PanoramaStrip] - the rope band of the consecutive frames stitched into the continuous strip,
+//!   split into the [Tile]s, each column of the strip indexed by the position along the rope
+//! - [Panorama] - stitches the frames of the camera into it's strip
+//! - [SurfaceMap] - strips of the perimeter cameras placed side by side, the unrolled surface of the rope
+mod tile;
+mod panorama_strip;
+mod panorama_ctx;
+mod panorama;
+mod surface_map;
+
+pub use tile::*;
+pub use panorama_strip::*;
+pub use panorama_ctx::*;
+pub use panorama::*;
+pub use surface_map::*;
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::Scale,
+        panorama::{PanoramaCtx, PanoramaStrip},
+        position::PositionCtx,
+        ContextRead, ContextWrite, EvalResult, InitialCtx,
+    },
+    domain::{Error, Eval, RwLock},
+};
+///
+/// Stitches the rope band of the frame into the [PanoramaStrip] of the camera
+/// - Placed by the travel of the rope from the previous frame, [PositionCtx::travel_px],
+///   the travel unknown or extrapolated over the [PositionCtx::gap] starts the new segment of the strip
+/// - Columns of the strip indexed by the position of the frame, so the defects located on the strip
+/// - Must be inserted after `Position`, the rope band cropped by `Roi`
+pub struct Panorama {
+    dbg: Dbg,
+    scale: Option<Scale>,
+    strip: Arc<RwLock<PanoramaStrip>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Panorama {
+    ///
+    /// New instance [Panorama]
+    /// - `scale` - pixel to millimetre calibration of the camera, used to index each column of the frame
+    /// - `strip` - panorama of the camera, shared between the frames of the same camera
+    pub fn new(scale: Option<Scale>, strip: Arc<RwLock<PanoramaStrip>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Panorama"),
+            scale,
+            strip,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Panorama {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let frame = &ContextRead::<InitialCtx>::read(&ctx).src_frame.mat;
+                let position = ContextRead::<PositionCtx>::read(&ctx);
+                let mut strip = self.strip.write();
+                let travel = position.travel_px(self.scale).filter(|_| !position.gap);
+                let stitched = match strip.stitch(frame, travel, position.position, self.scale) {
+                    Ok(stitched) => stitched,
+                    Err(err) => return Err(error.pass(err)),
+                };
+                let located = match frame.empty() {
+                    true => None,
+                    false => strip.cursor(),
+                };
+                let result = PanoramaCtx {
+                    tile: located.map(|(tile, _)| tile),
+                    column: located.map(|(_, column)| column),
+                    stitched: (stitched.end - stitched.start) as usize,
+                };
+                log::debug!("{}.eval | Stitched {} columns of {}, tile: {:?}", self.dbg, result.stitched, frame.cols(), result.tile);
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
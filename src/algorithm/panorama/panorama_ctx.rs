@@ -0,0 +1,11 @@
+///
+/// Store result of `Panorama`
+/// - `tile` - index of the [Tile](crate::algorithm::Tile) containing the center of the frame, None if the frame is empty
+/// - `column` - column of the center of the frame in the `tile`
+/// - `stitched` - number of the columns stitched from the frame
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PanoramaCtx {
+    pub tile: Option<i64>,
+    pub column: Option<usize>,
+    pub stitched: usize,
+}
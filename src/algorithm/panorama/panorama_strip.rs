@@ -0,0 +1,310 @@
+use std::{collections::{BTreeMap, VecDeque}, fs, ops::Range, path::{Path, PathBuf}};
+use opencv::{
+    core::{self, Mat, MatTrait, MatTraitConst, Rect, Scalar, Vector},
+    imgcodecs,
+};
+use serde::{Deserialize, Serialize};
+use crate::{algorithm::{diameter::Scale, panorama::Tile}, domain::Error};
+///
+/// The rope band of the consecutive frames of the camera stitched into the continuous strip
+/// - Column `u` of the strip - column `x` of the frame shifted by the travel of the rope,
+///   the strip keeps orientation of the frame, so the rope travelling forward (to the right of the frame)
+///   grows the strip to the left
+/// - Each frame stitches the slit between the centers of the previous and the current frames,
+///   the center of the frame is the least distorted by the lens, the first frame stitched whole
+/// - Split into the [Tile]s of the `width` columns, tile `i` keeps the columns `i * width..(i + 1) * width`,
+///   the tiles left behind are flushed into the files, so the strip of the whole rope isn't held in memory
+/// - Each column indexed by the position along the rope, so the defect found on the frame located on the strip
+/// - Frame with the unknown travel starts the new segment of the strip, stitched whole
+///   [PanoramaStrip::GAP] blank columns apart in the direction of the last travel, so the unknown travel
+///   isn't stitched as stationary, the columns of the gap aren't indexed by any position
+/// - Frame of the height different from the strip centered on it, cropped or padded with black, not resized,
+///   so the texture isn't stretched
+#[derive(Debug)]
+pub struct PanoramaStrip {
+    dir: PathBuf,
+    camera: String,
+    width: usize,
+    height: usize,
+    typ: i32,
+    offset: Option<f64>,
+    cursor: Option<i64>,
+    forward: bool,
+    breaks: Vec<i64>,
+    tiles: BTreeMap<i64, Tile>,
+    loaded: VecDeque<i64>,
+}
+//
+//
+impl PanoramaStrip {
+    /// Default width of the tile, px
+    pub const WIDTH: usize = 2048;
+    /// Tiles held in memory, the least recently stitched are flushed into the files
+    pub const CAPACITY: usize = 8;
+    /// Blank columns between the segments of the strip, px
+    pub const GAP: i64 = 16;
+    ///
+    /// New instance [PanoramaStrip]
+    /// - `dir` - directory the tiles flushed into
+    /// - `camera` - name of the camera, prefix of the files
+    /// - `width` - width of the tile, px
+    pub fn new(dir: impl AsRef<Path>, camera: &str, width: usize) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            camera: camera.trim_start_matches('/').replace('/', "-"),
+            width: width.max(1),
+            height: 0,
+            typ: 0,
+            offset: None,
+            cursor: None,
+            forward: true,
+            breaks: vec![],
+            tiles: BTreeMap::new(),
+            loaded: VecDeque::new(),
+        }
+    }
+    ///
+    /// Returns height of the strip, px, 0 until the first frame stitched
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    ///
+    /// Returns type of the strip image, of the first frame stitched
+    pub fn typ(&self) -> i32 {
+        self.typ
+    }
+    ///
+    /// Returns columns of the strip the segments started at, the travel before them unknown
+    pub fn breaks(&self) -> &[i64] {
+        &self.breaks
+    }
+    ///
+    /// Returns indexes of the tiles stitched
+    pub fn indexes(&self) -> Vec<i64> {
+        self.tiles.keys().copied().collect()
+    }
+    ///
+    /// Returns path of the index file of the `camera` strip in the `dir`
+    pub fn path(dir: impl AsRef<Path>, camera: &str) -> PathBuf {
+        dir.as_ref().join(format!("{}.panorama.json", camera.trim_start_matches('/').replace('/', "-")))
+    }
+    ///
+    /// Returns path of the image of the tile `index`
+    pub fn tile_path(&self, index: i64) -> PathBuf {
+        self.dir.join(format!("{}.panorama.{}.png", self.camera, index))
+    }
+    ///
+    /// Stitches the `frame` into the strip, returns columns of the strip stitched
+    /// - `shift` - shift of the strand texture from the previous frame, px, the new segment started if None
+    /// - `position` - position of the center of the frame along the rope, m
+    /// - `scale` - pixel to millimetre calibration of the camera, without it all the columns of the frame
+    ///   indexed by the `position` of the frame
+    /// - Frame of the type different from the first frame rejected
+    pub fn stitch(
+        &mut self,
+        frame: &Mat,
+        shift: Option<f64>,
+        position: Option<f64>,
+        scale: Option<Scale>,
+    ) -> Result<Range<i64>, Error> {
+        let error = Error::new("PanoramaStrip", "stitch");
+        if frame.empty() {
+            return Ok(0..0);
+        }
+        if self.height == 0 {
+            self.height = frame.rows() as usize;
+            self.typ = frame.typ();
+        }
+        if frame.typ() != self.typ {
+            return Err(error.err(format!("Frame type {} differs from the strip type {}", frame.typ(), self.typ)));
+        }
+        let cols = frame.cols() as i64;
+        let center = (cols / 2) as f64;
+        let (offset, range) = match (self.offset, shift) {
+            (None, _) => (0.0, 0..cols),
+            (Some(previous), Some(shift)) => {
+                let offset = previous - shift;
+                let (a, b) = ((previous + center).round() as i64, (offset + center).round() as i64);
+                let origin = offset.round() as i64;
+                if shift != 0.0 {
+                    self.forward = shift > 0.0;
+                }
+                (offset, a.min(b).max(origin)..a.max(b).min(origin + cols))
+            }
+            (Some(previous), None) => {
+                let previous = (previous + center).round() as i64;
+                let origin = match self.forward {
+                    true => previous - Self::GAP - cols,
+                    false => previous + Self::GAP,
+                };
+                self.breaks.push(origin);
+                log::debug!("PanoramaStrip.stitch | Travel unknown, new segment at the column {}", origin);
+                (origin as f64, origin..origin + cols)
+            }
+        };
+        self.offset = Some(offset);
+        self.cursor = Some((offset + center).round() as i64);
+        let origin = offset.round() as i64;
+        let label = |x: i64| position.map(|position| position + scale.map_or(0.0, |scale| scale.mm(center - x as f64) / 1000.0));
+        let mut u = range.start;
+        while u < range.end {
+            let index = u.div_euclid(self.width as i64);
+            let column = u.rem_euclid(self.width as i64);
+            let count = (range.end - u).min(self.width as i64 - column);
+            let x = u - origin;
+            self.paste(index, column as i32, frame, Rect::new(x as i32, 0, count as i32, frame.rows()))
+                .map_err(|err| error.pass_with(format!("Paste into the tile {} error", index), err.to_string()))?;
+            let tile = self.tiles.get_mut(&index).unwrap();
+            for i in 0..count {
+                tile.place((column + i) as usize, label(x + i));
+            }
+            u += count;
+        }
+        Ok(range)
+    }
+    ///
+    /// Returns the tile and the column of the center of the last frame stitched
+    pub fn cursor(&self) -> Option<(i64, usize)> {
+        self.cursor.map(|u| (u.div_euclid(self.width as i64), u.rem_euclid(self.width as i64) as usize))
+    }
+    ///
+    /// Returns the tile and the column of the strip, stitched nearest to the `position`, m,
+    /// None if nothing stitched within the `tolerance`, m
+    pub fn locate(&self, position: f64, tolerance: f64) -> Option<(i64, usize)> {
+        self.tiles
+            .iter()
+            .filter(|(_, tile)| tile.contains(position, tolerance))
+            .flat_map(|(index, tile)| {
+                tile.positions.iter().enumerate().filter_map(move |(column, p)| p.map(|p| ((p - position).abs(), *index, column)))
+            })
+            .filter(|(distance, _, _)| *distance <= tolerance)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, index, column)| (index, column))
+    }
+    ///
+    /// Returns the tile `index` and it's image, restored from the file if flushed, None if not stitched
+    pub fn tile(&mut self, index: i64) -> Result<Option<(&Tile, &Mat)>, Error> {
+        let error = Error::new("PanoramaStrip", "tile");
+        if !self.tiles.contains_key(&index) {
+            return Ok(None);
+        }
+        self.load(index).map_err(|err| error.pass_with(format!("Load the tile {} error", index), err.to_string()))?;
+        let tile = &self.tiles[&index];
+        Ok(tile.mat.as_ref().map(|mat| (tile, mat)))
+    }
+    ///
+    /// Writes the tiles held in memory and the index of the strip into the files
+    pub fn flush(&self) -> Result<(), Error> {
+        let error = Error::new("PanoramaStrip", "flush");
+        for (index, tile) in &self.tiles {
+            if let Some(mat) = &tile.mat {
+                Self::save(&self.tile_path(*index), mat)
+                    .map_err(|err| error.pass_with(format!("Write the tile {} error", index), err.to_string()))?;
+            }
+        }
+        let index = PanoramaIndex {
+            width: self.width,
+            height: self.height,
+            breaks: self.breaks.clone(),
+            tiles: self.tiles.iter().map(|(index, tile)| TileIndex {
+                index: *index,
+                file: format!("{}.panorama.{}.png", self.camera, index),
+                start: tile.start,
+                end: tile.end,
+                positions: tile.positions.clone(),
+            }).collect(),
+        };
+        let path = Self::path(&self.dir, &self.camera);
+        match serde_json::to_string(&index) {
+            Ok(json) => fs::write(&path, json)
+                .map_err(|err| error.pass_with(format!("Write {:?} error", path), err.to_string())),
+            Err(err) => Err(error.pass_with("Serialize error", err.to_string())),
+        }
+    }
+    ///
+    /// Copies the `rect` of the `frame` into the tile `index` at the `column`,
+    /// the frame centered on the strip, the rows beyond the strip cropped, the rows missing left black
+    fn paste(&mut self, index: i64, column: i32, frame: &Mat, rect: Rect) -> Result<(), opencv::Error> {
+        self.load(index)?;
+        let height = self.height as i32;
+        let rows = rect.height.min(height);
+        let src = Mat::roi(frame, Rect::new(rect.x, rect.y + (rect.height - rows) / 2, rect.width, rows))?;
+        let mat = self.tiles.get_mut(&index).and_then(|tile| tile.mat.as_mut()).unwrap();
+        if rows < height {
+            Mat::roi_mut(mat, Rect::new(column, 0, rect.width, height))?.set_to(&Scalar::all(0.0), &core::no_array())?;
+        }
+        let mut dst = Mat::roi_mut(mat, Rect::new(column, (height - rows) / 2, rect.width, rows))?;
+        src.copy_to(&mut dst)
+    }
+    ///
+    /// Brings the tile `index` into memory, new blank one if not stitched yet,
+    /// flushes the least recently used tiles beyond the [PanoramaStrip::CAPACITY]
+    fn load(&mut self, index: i64) -> Result<(), opencv::Error> {
+        match self.tiles.get_mut(&index) {
+            Some(tile) if tile.mat.is_some() => {
+                self.loaded.retain(|loaded| *loaded != index);
+            }
+            Some(tile) => {
+                let path = self.dir.join(format!("{}.panorama.{}.png", self.camera, index));
+                let mat = imgcodecs::imread(&path.to_string_lossy(), imgcodecs::IMREAD_UNCHANGED)?;
+                tile.mat = match mat.empty() {
+                    true => Some(Self::blank(self.width, self.height, self.typ)?),
+                    false => Some(mat),
+                };
+            }
+            None => {
+                let mat = Self::blank(self.width, self.height, self.typ)?;
+                self.tiles.insert(index, Tile::new(mat, self.width));
+            }
+        }
+        self.loaded.push_back(index);
+        while self.loaded.len() > Self::CAPACITY {
+            if let Some(oldest) = self.loaded.pop_front() {
+                if let Some(mat) = self.tiles.get_mut(&oldest).and_then(|tile| tile.mat.take()) {
+                    Self::save(&self.tile_path(oldest), &mat)?;
+                }
+            }
+        }
+        Ok(())
+    }
+    ///
+    /// Returns the black image of the tile
+    fn blank(width: usize, height: usize, typ: i32) -> Result<Mat, opencv::Error> {
+        Mat::new_rows_cols_with_default(height as i32, width as i32, typ, Scalar::all(0.0))
+    }
+    ///
+    /// Writes the image of the tile into the file
+    fn save(path: &Path, mat: &Mat) -> Result<(), opencv::Error> {
+        match imgcodecs::imwrite(&path.to_string_lossy(), mat, &Vector::new())? {
+            true => Ok(()),
+            false => Err(opencv::Error::new(core::StsError, format!("Image {:?} isn't written", path))),
+        }
+    }
+}
+///
+/// Index of the [PanoramaStrip] written next to the tile images
+/// - `width`, `height` - size of the tile, px
+/// - `breaks` - columns of the strip the segments started at, the travel before them unknown
+/// - `tiles` - the tiles stitched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanoramaIndex {
+    pub width: usize,
+    pub height: usize,
+    #[serde(default)]
+    pub breaks: Vec<i64>,
+    pub tiles: Vec<TileIndex>,
+}
+///
+/// Entry of the [PanoramaIndex]
+/// - `index` - index of the tile on the strip
+/// - `file` - name of the tile image
+/// - `start`, `end` - range of the positions of the tile, m
+/// - `positions` - position of each column of the tile, m
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileIndex {
+    pub index: i64,
+    pub file: String,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub positions: Vec<Option<f64>>,
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use opencv::{
+    core::{self, Mat, MatTraitConst, Rect, Scalar, Vector},
+    imgproc,
+};
+use crate::{algorithm::panorama::PanoramaStrip, domain::{Error, RwLock}};
+///
+/// Unrolled surface of the rope
+/// - Strips of the perimeter cameras placed one under another in the order around the rope
+/// - Aligned by the position along the rope, not by the columns of the strips,
+///   so the cameras started or registered the travel differently are still matched
+pub struct SurfaceMap {
+    views: Vec<Arc<RwLock<PanoramaStrip>>>,
+}
+//
+//
+impl SurfaceMap {
+    ///
+    /// New instance [SurfaceMap]
+    /// - `views` - strips of the cameras in the order around the rope
+    pub fn new(views: Vec<Arc<RwLock<PanoramaStrip>>>) -> Self {
+        Self { views }
+    }
+    ///
+    /// Returns the surface of the rope between the positions `from` and `to`, m, `columns` px wide
+    /// - Each column shows the column of the strip stitched nearest to it's position within the `tolerance`, m,
+    ///   black if nothing stitched there
+    /// - Grayscale strips converted into color, if mixed with the color ones
+    pub fn render(&self, from: f64, to: f64, columns: usize, tolerance: f64) -> Result<Mat, Error> {
+        let error = Error::new("SurfaceMap", "render");
+        let step = (to - from) / columns.max(1) as f64;
+        let mut bands = vec![];
+        for view in &self.views {
+            let mut strip = view.write();
+            if strip.height() == 0 || columns == 0 {
+                continue;
+            }
+            let band = Self::band(&mut strip, from, step, columns, tolerance)
+                .map_err(|err| error.pass_with("Render the strip error", err.to_string()))?;
+            bands.push(band);
+        }
+        let color = bands.iter().any(|band| band.channels() > 1);
+        let mut src = Vector::<Mat>::new();
+        for band in bands {
+            match color && band.channels() == 1 {
+                true => {
+                    let mut dst = Mat::default();
+                    imgproc::cvt_color(&band, &mut dst, imgproc::COLOR_GRAY2BGR, 0)
+                        .map_err(|err| error.pass_with("Convert the strip error", err.to_string()))?;
+                    src.push(dst);
+                }
+                false => src.push(band),
+            }
+        }
+        let mut dst = Mat::default();
+        if !src.is_empty() {
+            core::vconcat(&src, &mut dst).map_err(|err| error.pass_with("Concatenate the strips error", err.to_string()))?;
+        }
+        Ok(dst)
+    }
+    ///
+    /// Returns the band of the `strip` of the `columns` px wide, starting at the position `from`, m, by the `step`, m
+    fn band(strip: &mut PanoramaStrip, from: f64, step: f64, columns: usize, tolerance: f64) -> Result<Mat, Error> {
+        let error = Error::new("SurfaceMap", "band");
+        let height = strip.height() as i32;
+        let mut band = Mat::new_rows_cols_with_default(height, columns as i32, strip.typ(), Scalar::all(0.0))
+            .map_err(|err| error.pass_with("Create the band error", err.to_string()))?;
+        for column in 0..columns {
+            let position = from + step * (column as f64 + 0.5);
+            if let Some((index, at)) = strip.locate(position, tolerance) {
+                if let Some((_, tile)) = strip.tile(index)? {
+                    let copied = Mat::roi(tile, Rect::new(at as i32, 0, 1, height)).and_then(|src| {
+                        let mut dst = Mat::roi_mut(&mut band, Rect::new(column as i32, 0, 1, height))?;
+                        src.copy_to(&mut dst)
+                    });
+                    copied.map_err(|err| error.pass_with(format!("Copy the column {} of the tile {} error", at, index), err.to_string()))?;
+                }
+            }
+        }
+        Ok(band)
+    }
+}
@@ -0,0 +1,39 @@
+use opencv::core::Mat;
+///
+/// Part of the [PanoramaStrip](crate::algorithm::PanoramaStrip) of the fixed width
+/// - `mat` - image of the tile, None if flushed into the file
+/// - `positions` - position along the rope of each column, m, None if the column isn't stitched or the position unknown
+/// - `start`, `end` - range of the `positions`, m
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub mat: Option<Mat>,
+    pub positions: Vec<Option<f64>>,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+}
+//
+//
+impl Tile {
+    ///
+    /// New instance [Tile] of the `width` columns, nothing stitched yet
+    pub fn new(mat: Mat, width: usize) -> Self {
+        Self { mat: Some(mat), positions: vec![None; width], start: None, end: None }
+    }
+    ///
+    /// Assigns the `position` to the `column`
+    pub fn place(&mut self, column: usize, position: Option<f64>) {
+        self.positions[column] = position;
+        if let Some(position) = position {
+            self.start = Some(self.start.map_or(position, |start| start.min(position)));
+            self.end = Some(self.end.map_or(position, |end| end.max(position)));
+        }
+    }
+    ///
+    /// Returns true if the `position` within the range of the tile extended by the `tolerance`
+    pub fn contains(&self, position: f64, tolerance: f64) -> bool {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => start - tolerance <= position && position <= end + tolerance,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+///
+/// Direction of the rope travel
+/// - `Forward` - position grows, the strand texture moves to the right of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Direction {
+    Forward,
+    Backward,
+    #[default]
+    Stationary,
+}
+//
+//
+impl Direction {
+    ///
+    /// Returns direction of the `travel`, [Direction::Stationary] within the `tolerance`
+    pub fn of(travel: f64, tolerance: f64) -> Self {
+        if travel > tolerance {
+            Direction::Forward
+        } else if travel < -tolerance {
+            Direction::Backward
+        } else {
+            Direction::Stationary
+        }
+    }
+}
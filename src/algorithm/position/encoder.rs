@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+use crate::{algorithm::position::PositionFeed, domain::RwLock};
+///
+/// Incremental encoder on the sheave, as the [PositionFeed]
+/// - Counts are pushed with their timestamps by the thread polling the encoder
+/// - Position at the frame timestamp interpolated between the neighbouring counts,
+///   held at the nearest count outside of the received ones
+pub struct Encoder {
+    metres_per_count: f64,
+    capacity: usize,
+    samples: RwLock<VecDeque<(usize, i64)>>,
+}
+//
+//
+impl Encoder {
+    ///
+    /// New instance [Encoder]
+    /// - `metres_per_count` - rope travel per one count of the encoder, m
+    /// - `capacity` - number of the latest counts kept
+    pub fn new(metres_per_count: f64, capacity: usize) -> Self {
+        Self {
+            metres_per_count,
+            capacity: capacity.max(2),
+            samples: RwLock::new(VecDeque::new()),
+        }
+    }
+    ///
+    /// Stores the `count` of the encoder received at the `timestamp`, ns
+    pub fn push(&self, timestamp: usize, count: i64) {
+        let mut samples = self.samples.write();
+        if samples.len() >= self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back((timestamp, count));
+    }
+}
+//
+//
+impl PositionFeed for Encoder {
+    fn read(&self, timestamp: usize) -> Option<f64> {
+        let samples = self.samples.read();
+        let count = match samples.iter().position(|(time, _)| *time >= timestamp) {
+            Some(0) => samples.front().map(|(_, count)| *count as f64),
+            Some(index) => {
+                let ((t0, c0), (t1, c1)) = (samples[index - 1], samples[index]);
+                let part = (timestamp - t0) as f64 / (t1 - t0) as f64;
+                Some(c0 as f64 + part * (c1 - c0) as f64)
+            }
+            None => samples.back().map(|(_, count)| *count as f64),
+        };
+        count.map(|count| count * self.metres_per_count)
+    }
+}
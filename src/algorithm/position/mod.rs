@@ -0,0 +1,21 @@
+//!
+//! Longitudinal position of the rope
+//! - [PositionFeed] - pluggable external position source, [Encoder] for example
+//! - [Registration] - optical shift of the strand texture between consecutive frames
+//! - [Position] - position of the frame along the rope in metres and the direction of travel,
+//!   the external feed and the optical shift fused together
+mod direction;
+mod position_feed;
+mod encoder;
+mod registration;
+mod rope_track;
+mod position_ctx;
+mod position;
+
+pub use direction::*;
+pub use position_feed::*;
+pub use encoder::*;
+pub use registration::*;
+pub use rope_track::*;
+pub use position_ctx::*;
+pub use position::*;
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::Scale,
+        position::{Direction, PositionCtx, PositionFeed, PositionSource, Registration, RopeTrack},
+        ContextRead, ContextWrite, EvalResult, IlluminationCtx, InitialCtx,
+    },
+    domain::{Error, Eval, RwLock},
+};
+///
+/// Assigns the frame it's position along the rope and the direction of travel
+/// - Optical travel - shift of the strand texture from the previous frame, converted by the camera [Scale]
+/// - External travel - position of the [PositionFeed] at the frame timestamp
+/// - Both available - fused, `weight` of the feed against the previous position moved by the optical travel,
+///   so the feed keeps the optical drift bounded and the optical travel smooths the feed
+/// - Optical position without the feed counted from the first registered frame
+/// - Neither measured, the registration failed and no feed, the position extrapolated by the last speed of the rope,
+///   or held if the speed unknown, and the gap marked in [PositionCtx::gap]
+/// - Must be inserted after `Roi`, the strand texture of the rope band registered only,
+///   on the normalised frame of the [IlluminationCtx], if given, so the flicker doesn't shift the texture
+pub struct Position {
+    dbg: Dbg,
+    scale: Option<Scale>,
+    feed: Option<Arc<dyn PositionFeed>>,
+    weight: f64,
+    track: Arc<RwLock<RopeTrack>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Position {
+    /// Travel below this is considered as stationary rope, m
+    const STATIONARY: f64 = 1e-4;
+    /// Optical shift below this is considered as stationary rope, if the [Scale] unknown, px
+    const STATIONARY_SHIFT: f64 = 0.5;
+    ///
+    /// New instance [Position]
+    /// - `scale` - pixel to millimetre calibration of the camera, optical travel isn't measured without it
+    /// - `feed` - external position source, if connected
+    /// - `weight` - weight of the feed in the fused position, 0.0...1.0
+    /// - `track` - state of the travel, shared between the frames of the same camera
+    pub fn new(
+        scale: Option<Scale>,
+        feed: Option<Arc<dyn PositionFeed>>,
+        weight: f64,
+        track: Arc<RwLock<RopeTrack>>,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("Position"),
+            scale,
+            feed,
+            weight: weight.clamp(0.0, 1.0),
+            track,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Position {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let initial_ctx = ContextRead::<InitialCtx>::read(&ctx);
+                let src = ContextRead::<IlluminationCtx>::read(&ctx).frame_or(&initial_ctx.src_frame);
+                let mut track = self.track.write();
+                let frame = match src.mat.empty() {
+                    true => None,
+                    false => match Registration::prepare(&src.mat) {
+                        Ok(frame) => Some(frame),
+                        Err(err) => return Err(error.pass_with("Input image format error", err.to_string())),
+                    },
+                };
+                let shift = match (&track.frame, &frame) {
+                    (Some(previous), Some(current)) => match Registration::shift(previous, current) {
+                        Ok(shift) => shift.map(|shift| shift.x),
+                        Err(err) => return Err(error.pass_with("Registration error", err.to_string())),
+                    },
+                    _ => None,
+                };
+                let previous = track.position;
+                let interval = track.timestamp.map_or(0, |timestamp| src.timestamp.saturating_sub(timestamp));
+                let optical = shift.zip(self.scale).map(|(shift, scale)| scale.mm(shift) / 1000.0);
+                let feed = self.feed.as_ref().and_then(|feed| feed.read(src.timestamp));
+                let (position, source) = match (feed, optical) {
+                    (Some(feed), Some(optical)) => (
+                        Some(previous.map_or(feed, |previous| self.weight * feed + (1.0 - self.weight) * (previous + optical))),
+                        PositionSource::Fused,
+                    ),
+                    (Some(feed), None) => (Some(feed), PositionSource::Feed),
+                    (None, Some(optical)) => (Some(previous.unwrap_or(0.0) + optical), PositionSource::Optical),
+                    (None, None) => match (previous, track.speed) {
+                        (Some(previous), Some(speed)) => (Some(previous + speed * interval as f64 / 1e9), PositionSource::Extrapolated),
+                        _ => (previous, PositionSource::None),
+                    },
+                };
+                let gap = previous.is_some() && matches!(source, PositionSource::None | PositionSource::Extrapolated);
+                let travel = match (previous, position) {
+                    (Some(previous), Some(position)) => position - previous,
+                    _ => 0.0,
+                };
+                let direction = match source {
+                    PositionSource::None => Direction::of(shift.unwrap_or(0.0), Self::STATIONARY_SHIFT),
+                    _ => Direction::of(travel, Self::STATIONARY),
+                };
+                if !gap && previous.is_some() && interval > 0 {
+                    track.speed = Some(travel / (interval as f64 / 1e9));
+                }
+                track.position = position;
+                track.timestamp = Some(src.timestamp);
+                if frame.is_some() {
+                    track.frame = frame;
+                }
+                let result = PositionCtx { position, shift, travel, interval, direction, source, gap };
+                match gap {
+                    true => log::warn!("{}.eval | Position gap, travel not measured, {:?}: {:?}", self.dbg, source, position),
+                    false => log::debug!("{}.eval | Position: {:?}, travel: {:.4} m, {:?}", self.dbg, result.position, result.travel, result.direction),
+                }
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::algorithm::{diameter::Scale, position::Direction};
+///
+/// Source the position of the frame obtained from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionSource {
+    /// Position unknown
+    #[default]
+    None,
+    /// External [PositionFeed](crate::algorithm::PositionFeed) only
+    Feed,
+    /// Optical shift of the strand texture only
+    Optical,
+    /// External feed and optical shift fused
+    Fused,
+    /// Neither measured, the previous position moved by the last speed of the rope
+    Extrapolated,
+}
+///
+/// Store result of `Position`
+/// - `position` - position of the frame along the rope, m, None if unknown
+/// - `shift` - optical shift of the strand texture from the previous frame along the rope, px, None if not registered
+/// - `travel` - travel of the rope from the previous frame, m
+/// - `interval` - time from the previous frame, ns, 0 for the first frame
+/// - `direction` - direction of the travel
+/// - `source` - source the position obtained from
+/// - `gap` - true if the travel from the previous frame isn't measured,
+///   the position extrapolated by the last speed or held at the previous one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PositionCtx {
+    pub position: Option<f64>,
+    pub shift: Option<f64>,
+    pub travel: f64,
+    pub interval: usize,
+    pub direction: Direction,
+    pub source: PositionSource,
+    pub gap: bool,
+}
+//
+//
+impl PositionCtx {
+    ///
+    /// Returns travel of the rope on the frame from the previous one, px, None if unknown
+    /// - Travel of the external feed, if connected and the camera `scale` known,
+    ///   the feed isn't limited by the half of the frame as the phase correlation,
+    ///   or the travel extrapolated by the last speed of the rope
+    /// - Optical shift of the strand texture otherwise
+    pub fn travel_px(&self, scale: Option<Scale>) -> Option<f64> {
+        match (self.optical(scale), scale) {
+            (false, Some(scale)) => Some(self.travel * 1000.0 / scale.mm_per_px),
+            _ => self.shift,
+        }
+    }
+    ///
+    /// Returns true if the [PositionCtx::travel_px] is the optical shift of the strand texture,
+    /// limited by the half of the frame
+    pub fn optical(&self, scale: Option<Scale>) -> bool {
+        let feed = matches!(self.source, PositionSource::Feed | PositionSource::Fused | PositionSource::Extrapolated);
+        !(feed && scale.is_some_and(|scale| scale.mm_per_px > 0.0))
+    }
+}
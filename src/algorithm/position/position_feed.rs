@@ -0,0 +1,9 @@
+///
+/// External source of the rope position: encoder, winch controller, PLC
+/// - Implementations are shared between the frame processing and the thread receiving the feed
+pub trait PositionFeed: Send + Sync {
+    ///
+    /// Returns position of the rope in metres at the `timestamp` of the frame, ns,
+    /// None if the feed has no data
+    fn read(&self, timestamp: usize) -> Option<f64>;
+}
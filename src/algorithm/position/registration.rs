@@ -0,0 +1,51 @@
+use opencv::{
+    core::{self, Mat, MatTraitConst, Point2d, Rect, Size},
+    imgproc,
+};
+use crate::domain::Dot;
+///
+/// Registration of the strand texture between consecutive frames by the phase correlation
+pub struct Registration;
+//
+//
+impl Registration {
+    /// Minimal response of the phase correlation, weaker peak is not trusted
+    const RESPONSE: f64 = 0.05;
+    ///
+    /// Returns the frame prepared for the registration, single channel 64-bit float
+    pub fn prepare(mat: &Mat) -> Result<Mat, opencv::Error> {
+        let gray = match mat.channels() {
+            1 => mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                imgproc::cvt_color(mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut dst = Mat::default();
+        gray.convert_to(&mut dst, core::CV_64F, 1.0, 0.0)?;
+        Ok(dst)
+    }
+    ///
+    /// Returns shift of the `current` frame relative to the `previous` one, px,
+    /// both prepared by [Registration::prepare]
+    /// - Frames of the different size compared by their common top left part
+    /// - None if the texture isn't registered reliably
+    pub fn shift(previous: &Mat, current: &Mat) -> Result<Option<Dot<f64>>, opencv::Error> {
+        let size = Size::new(previous.cols().min(current.cols()), previous.rows().min(current.rows()));
+        if size.width < 2 || size.height < 2 {
+            return Ok(None);
+        }
+        let rect = Rect::new(0, 0, size.width, size.height);
+        let previous = Mat::roi(previous, rect)?.try_clone()?;
+        let current = Mat::roi(current, rect)?.try_clone()?;
+        let mut window = Mat::default();
+        imgproc::create_hanning_window(&mut window, size, core::CV_64F)?;
+        let mut response = 0.0;
+        let Point2d { x, y } = imgproc::phase_correlate(&previous, &current, &window, &mut response)?;
+        match response >= Self::RESPONSE {
+            true => Ok(Some(Dot { x, y })),
+            false => Ok(None),
+        }
+    }
+}
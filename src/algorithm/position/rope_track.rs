@@ -0,0 +1,14 @@
+use opencv::core::Mat;
+///
+/// State of the rope travel, shared between the frames of the same camera
+/// - `frame` - previous frame, prepared by [Registration](crate::algorithm::Registration)
+/// - `position` - position of the previous frame, m, None until known
+/// - `timestamp` - timestamp of the previous frame, ns
+/// - `speed` - speed of the rope last measured, m/s, None until measured
+#[derive(Debug, Clone, Default)]
+pub struct RopeTrack {
+    pub frame: Option<Mat>,
+    pub position: Option<f64>,
+    pub timestamp: Option<usize>,
+    pub speed: Option<f64>,
+}
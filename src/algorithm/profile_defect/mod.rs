@@ -0,0 +1,10 @@
+//!
+//! Detecting [Groove and Mound](design/theory/geometry_rope_defects.md) defect's
+//! by the edge profile shape combined with the shading inside the rope band
+mod profile_defect_ctx;
+mod profile_defect;
+mod profile_threshold;
+
+pub use profile_defect_ctx::*;
+pub use profile_defect::*;
+pub use profile_threshold::*;
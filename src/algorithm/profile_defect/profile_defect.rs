@@ -0,0 +1,173 @@
+use opencv::core::{MatTraitConst, Vec3b};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        geometry_defect::{Defect, DefectSide, GeometryDefectType},
+        profile_defect::{GrooveThreshold, MoundThreshold, ProfileDefectCtx},
+        ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, Side,
+    },
+    domain::{Error, Eval, Image},
+};
+///
+/// Detects [Groove and Mound](design/theory/geometry_rope_defects.md) on each side of the rope
+/// - `Groove` - long shallow inward deviation of the edge with the changed shading along it
+/// - `Mound` - short outward deviation of the edge, brightened as a convex surface
+/// - Edge deviation is taken from the median edge of the side,
+///   shading is the brightness of the strip inside the band next to the edge relative to it's median
+pub struct ProfileDefect {
+    dbg: Dbg,
+    groove: GrooveThreshold,
+    mound: MoundThreshold,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl ProfileDefect {
+    /// Distance from the edge to the strip, px
+    const INSET: usize = 2;
+    ///
+    /// New instance [ProfileDefect]
+    pub fn new(
+        groove: GrooveThreshold,
+        mound: MoundThreshold,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("ProfileDefect"),
+            groove,
+            mound,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns brightness of the pixel
+    fn brightness(image: &Image, row: usize, col: usize) -> Result<f64, String> {
+        match image.mat.channels() {
+            1 => image.mat.at_2d::<u8>(row as i32, col as i32)
+                .map(|&value| value as f64)
+                .map_err(|err| err.to_string()),
+            _ => image.mat.at_2d::<Vec3b>(row as i32, col as i32)
+                .map(|value| (value[0] as f64 + value[1] as f64 + value[2] as f64) / 3.0)
+                .map_err(|err| err.to_string()),
+        }
+    }
+    ///
+    /// Returns median of the values, 0.0 if empty
+    fn median(values: &[f64]) -> f64 {
+        let mut values = values.to_vec();
+        values.sort_by(f64::total_cmp);
+        let len = values.len();
+        match len {
+            0 => 0.0,
+            _ if len % 2 == 1 => values[len / 2],
+            _ => (values[len / 2 - 1] + values[len / 2]) / 2.0,
+        }
+    }
+    ///
+    /// Returns ranges [first, last] of the indexes, where `mask` is true
+    fn runs(mask: &[bool]) -> Vec<(usize, usize)> {
+        let mut runs = vec![];
+        let mut start = None;
+        for (i, &value) in mask.iter().chain([false].iter()).enumerate() {
+            match (value, start) {
+                (true, None) => start = Some(i),
+                (false, Some(first)) => {
+                    runs.push((first, i - 1));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        runs
+    }
+    ///
+    /// Returns defect's found on the one side of the rope
+    /// - `band` - [column, edge row of the side, opposite edge row]
+    /// - `side` - side of the rope the `band` belongs to
+    /// - `outward` - +1 if outside of the rope is `row` decreasing, -1 otherwise
+    /// - `diameter` - mean diameter of the rope, px
+    fn side(&self, image: &Image, band: &[[usize; 3]], side: DefectSide, outward: isize, diameter: f64) -> Result<Vec<Defect>, String> {
+        let depth = ((diameter / 8.0) as usize).max(2);
+        let edges: Vec<f64> = band.iter().map(|[_, edge, _]| *edge as f64).collect();
+        let median_edge = Self::median(&edges);
+        let deviations: Vec<f64> = edges.iter().map(|edge| (median_edge - edge) * outward as f64 / diameter).collect();
+        let mut strips = Vec::with_capacity(band.len());
+        for &[col, edge, opposite] in band {
+            let rows: Vec<usize> = (Self::INSET..Self::INSET + depth)
+                .map(|offset| edge as isize + offset as isize * outward)
+                .filter(|row| *row >= 0 && (*row as usize).abs_diff(edge) < edge.abs_diff(opposite))
+                .map(|row| row as usize)
+                .collect();
+            let mut sum = 0.0;
+            for &row in &rows {
+                sum += Self::brightness(image, row, col)?;
+            }
+            strips.push(if rows.is_empty() { 0.0 } else { sum / rows.len() as f64 });
+        }
+        let median_strip = Self::median(&strips);
+        let shading: Vec<f64> = strips.iter().map(|strip| if median_strip > 0.0 { strip / median_strip } else { 1.0 }).collect();
+        let mean = |first: usize, last: usize| shading[first..=last].iter().sum::<f64>() / (last - first + 1) as f64;
+        let length = |first: usize, last: usize| (band[last][0] - band[first][0] + 1) as f64 / diameter;
+        let peak = |first: usize, last: usize| deviations[first..=last].iter().fold(0.0, |peak: f64, deviation| peak.max(deviation.abs()));
+        let defect = |kind, first: usize, last: usize, limit: f64| {
+            let peak = peak(first, last);
+            Defect::new(kind, side, band[first][0], band[last][0], peak * diameter, peak, Defect::confidence(peak, limit))
+        };
+        let mut result = vec![];
+        let mounds: Vec<bool> = deviations.iter().map(|deviation| *deviation >= self.mound.height).collect();
+        for (first, last) in Self::runs(&mounds) {
+            if length(first, last) <= self.mound.length && mean(first, last) >= 1.0 + self.mound.shading {
+                result.push(defect(GeometryDefectType::Mound, first, last, self.mound.height));
+            }
+        }
+        let grooves: Vec<bool> = deviations.iter().map(|deviation| -deviation >= self.groove.depth).collect();
+        for (first, last) in Self::runs(&grooves) {
+            if length(first, last) >= self.groove.length && (mean(first, last) - 1.0).abs() >= self.groove.shading {
+                result.push(defect(GeometryDefectType::Groove, first, last, self.groove.depth));
+            }
+        }
+        Ok(result)
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for ProfileDefect {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let image = &ContextRead::<InitialCtx>::read(&ctx).src_frame;
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let (top, bottom): (Vec<[usize; 3]>, Vec<[usize; 3]>) = edges.get(Side::Upper).iter()
+                    .zip(edges.get(Side::Lower).iter())
+                    .map(|(upper, lower)| {
+                        let (top, bottom) = (upper.y.min(lower.y).round() as usize, upper.y.max(lower.y).round() as usize);
+                        ([upper.x as usize, top, bottom], [upper.x as usize, bottom, top])
+                    })
+                    .unzip();
+                let (top_side, bottom_side) = match (edges.get(Side::Upper).first(), edges.get(Side::Lower).first()) {
+                    (Some(upper), Some(lower)) if upper.y > lower.y => (DefectSide::Lower, DefectSide::Upper),
+                    _ => (DefectSide::Upper, DefectSide::Lower),
+                };
+                let diameter = match top.len() {
+                    0 => 0.0,
+                    len => top.iter().map(|[_, top, bottom]| (bottom - top) as f64).sum::<f64>() / len as f64,
+                };
+                let mut result = vec![];
+                if diameter > 0.0 {
+                    for (band, side, outward) in [(&top, top_side, 1), (&bottom, bottom_side, -1)] {
+                        match self.side(image, band, side, outward, diameter) {
+                            Ok(defects) => result.extend(defects),
+                            Err(err) => return Err(error.pass_with("Input image format error", err)),
+                        }
+                    }
+                }
+                result.sort_by_key(|defect| defect.start);
+                let result = ProfileDefectCtx { result };
+                log::debug!("{}.eval | Profile defect's: {:?}", self.dbg, result.result);
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
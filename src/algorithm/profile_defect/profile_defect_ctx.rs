@@ -0,0 +1,8 @@
+use crate::algorithm::geometry_defect::{Defect, GeometryDefectType};
+///
+/// Store result of `ProfileDefect`
+/// - [Defect]'s of [GeometryDefectType::Groove] and [GeometryDefectType::Mound] found, ordered by position
+#[derive(Debug, Clone, Default)]
+pub struct ProfileDefectCtx {
+    pub result: Vec<Defect>,
+}
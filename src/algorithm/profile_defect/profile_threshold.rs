@@ -0,0 +1,36 @@
+///
+/// Store threshodls values for the [Groove](design/theory/geometry_rope_defects.md) detecting
+/// - `depth` - minimal inward deviation of the edge, fraction of the rope diameter
+/// - `length` - minimal length of the groove, fraction of the rope diameter
+/// - `shading` - minimal relative change of the brightness along the groove
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrooveThreshold {
+    pub depth: f64,
+    pub length: f64,
+    pub shading: f64,
+}
+//
+//
+impl Default for GrooveThreshold {
+    fn default() -> Self {
+        Self { depth: 0.02, length: 2.0, shading: 0.1 }
+    }
+}
+///
+/// Store threshodls values for the [Mound](design/theory/geometry_rope_defects.md) detecting
+/// - `height` - minimal outward deviation of the edge, fraction of the rope diameter
+/// - `length` - maximal length of the mound, fraction of the rope diameter
+/// - `shading` - minimal relative brightening of the mound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoundThreshold {
+    pub height: f64,
+    pub length: f64,
+    pub shading: f64,
+}
+//
+//
+impl Default for MoundThreshold {
+    fn default() -> Self {
+        Self { height: 0.03, length: 1.0, shading: 0.1 }
+    }
+}
@@ -0,0 +1,11 @@
+//!
+//! Rope region of interest
+//! - The rope band located on the frame once and tracked over the following frames
+//! - The frame cropped to the margin-padded strip, the following stages process only it
+mod roi_band;
+mod roi_ctx;
+mod roi;
+
+pub use roi_band::*;
+pub use roi_ctx::*;
+pub use roi::*;
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+use opencv::{
+    core::{self, Mat, MatTraitConst, Rect},
+    imgproc,
+};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{illumination::IlluminationCtx, roi::{RoiBand, RoiCtx}, ContextRead, ContextWrite, EvalResult, InitialCtx},
+    domain::{Error, Eval, Image, RwLock},
+};
+///
+/// Crops the frame to the rope working area
+/// - Rope band - rows where the rope boundaries give the strong vertical gradient along the frame
+/// - Band located over the whole frame once, on the following frames it's searched
+///   only near the band of the previous frame, and relocated if lost
+/// - Band located on the normalised frame of the [IlluminationCtx], if given
+/// - Replaces `src_frame` of the [InitialCtx] and the normalised frame of the [IlluminationCtx]
+///   with the `margin` padded strip, full width, so must be inserted after `Orientation` and before `DetectingContoursCv`
+/// - Position of the strip stored in [RoiCtx] to map the results back to the frame aligned by `Orientation`
+pub struct Roi {
+    dbg: Dbg,
+    margin: usize,
+    band: Arc<RwLock<RoiBand>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Roi {
+    /// Row belongs to the rope boundary if it's mean gradient exceeds this part of the maximum one
+    const GRADIENT: f64 = 0.5;
+    /// Minimal mean gradient of the row to be considered as the rope boundary
+    const MIN_GRADIENT: f64 = 8.0;
+    ///
+    /// New instance [Roi]
+    /// - `margin` - rows added above and below the rope band, px
+    /// - `band` - rope band, shared between the frames of the same camera
+    pub fn new(margin: usize, band: Arc<RwLock<RoiBand>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Roi"),
+            margin,
+            band,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns mean absolute vertical gradient of each row of the frame
+    fn profile(mat: &Mat) -> Result<Vec<f64>, opencv::Error> {
+        let gray = match mat.channels() {
+            1 => mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                imgproc::cvt_color(mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut sobel = Mat::default();
+        imgproc::sobel(&gray, &mut sobel, core::CV_16S, 0, 1, 3, 1.0, 0.0, core::BORDER_REPLICATE)?;
+        let mut grad = Mat::default();
+        core::convert_scale_abs(&sobel, &mut grad, 1.0, 0.0)?;
+        let mut profile = Vec::with_capacity(grad.rows() as usize);
+        for row in 0..grad.rows() {
+            let mut sum = 0.0;
+            for col in 0..grad.cols() {
+                sum += *grad.at_2d::<u8>(row, col)? as f64;
+            }
+            profile.push(sum / grad.cols().max(1) as f64);
+        }
+        Ok(profile)
+    }
+    ///
+    /// Returns first and last rows of the rope boundaries inside the rows `from..to` of the frame,
+    /// None if there is no boundaries or the band is cut by the search window
+    fn locate(image: &Image, from: usize, to: usize) -> Result<Option<(usize, usize)>, opencv::Error> {
+        if to <= from {
+            return Ok(None);
+        }
+        let window = Mat::roi(&image.mat, Rect::new(0, from as i32, image.mat.cols(), (to - from) as i32))?.try_clone()?;
+        let profile = Self::profile(&window)?;
+        let max = profile.iter().fold(0.0, |max: f64, value| max.max(*value));
+        if max < Self::MIN_GRADIENT {
+            return Ok(None);
+        }
+        let rows: Vec<usize> = profile.iter()
+            .enumerate()
+            .filter(|(_, value)| **value >= max * Self::GRADIENT)
+            .map(|(row, _)| row)
+            .collect();
+        let whole = from == 0 && to == image.mat.rows() as usize;
+        match (rows.first(), rows.last()) {
+            (Some(&top), Some(&bottom)) if whole || (top > 0 && bottom < profile.len() - 1) => {
+                Ok(Some((from + top, from + bottom)))
+            }
+            _ => Ok(None),
+        }
+    }
+    ///
+    /// Returns the strip of the `image` cropped by the `roi`
+    fn crop(image: &Image, roi: &RoiCtx) -> Result<Image, opencv::Error> {
+        let mat = Mat::roi(&image.mat, Rect::new(0, roi.y as i32, roi.width as i32, roi.height as i32))?.try_clone()?;
+        Ok(Image {
+            width: roi.width,
+            height: roi.height,
+            timestamp: image.timestamp,
+            mat,
+            bytes: image.bytes,
+        })
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Roi {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let src = ContextRead::<InitialCtx>::read(&ctx).src_frame.clone();
+                let illumination = ContextRead::<IlluminationCtx>::read(&ctx).clone();
+                let (width, height) = (src.mat.cols() as usize, src.mat.rows() as usize);
+                let previous = *self.band.read();
+                // Band of the previous frame searched first, padded by the doubled margin
+                let tracked = match previous.located {
+                    true => match Self::locate(
+                        illumination.frame_or(&src),
+                        previous.top.saturating_sub(2 * self.margin),
+                        (previous.bottom + 2 * self.margin + 1).min(height),
+                    ) {
+                        Ok(band) => band,
+                        Err(err) => return Err(error.pass_with("Input image format error", err.to_string())),
+                    }
+                    false => None,
+                };
+                let band = match tracked {
+                    Some(band) => Some(band),
+                    None => match Self::locate(illumination.frame_or(&src), 0, height) {
+                        Ok(band) => band,
+                        Err(err) => return Err(error.pass_with("Input image format error", err.to_string())),
+                    }
+                };
+                match band {
+                    Some((top, bottom)) => {
+                        *self.band.write() = RoiBand { top, bottom, located: true };
+                        let y = top.saturating_sub(self.margin);
+                        let roi = RoiCtx {
+                            y,
+                            width,
+                            height: (bottom + self.margin + 1).min(height) - y,
+                            tracked: tracked.is_some(),
+                        };
+                        let frame = match Self::crop(&src, &roi) {
+                            Ok(frame) => frame,
+                            Err(err) => return Err(error.pass_with("Crop error", err.to_string())),
+                        };
+                        // The normalised frame cropped along
+                        let normalised = match illumination.frame.mat.empty() {
+                            true => Image::default(),
+                            false => match Self::crop(&illumination.frame, &roi) {
+                                Ok(normalised) => normalised,
+                                Err(err) => return Err(error.pass_with("Crop error", err.to_string())),
+                            },
+                        };
+                        log::debug!("{}.eval | Rope band: {}..{}, tracked: {}", self.dbg, top, bottom, roi.tracked);
+                        match ctx.write(InitialCtx::new(frame)) {
+                            Ok(ctx) => match ctx.write(IlluminationCtx { frame: normalised, ..illumination }) {
+                                Ok(ctx) => ctx.write(roi),
+                                Err(err) => Err(error.pass(err)),
+                            },
+                            Err(err) => Err(error.pass(err)),
+                        }
+                    }
+                    None => {
+                        log::debug!("{}.eval | Rope band not found, full frame used", self.dbg);
+                        ctx.write(RoiCtx { y: 0, width, height, tracked: false })
+                    }
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+///
+/// Rows of the rope band tracked over the frames
+/// - `top`, `bottom` - first and last row of the rope boundaries on the last frame
+/// - `located` - false until the band is found for the first time
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RoiBand {
+    pub top: usize,
+    pub bottom: usize,
+    pub located: bool,
+}
@@ -0,0 +1,35 @@
+use crate::{algorithm::geometry::Line, domain::Dot};
+///
+/// Store result of `Roi`
+/// - `y` - first row of the cropped strip on the aligned frame, px,
+///   the strip is cropped by the rows only, so the columns of the strip and of the frame are the same
+/// - Aligned frame - full frame rotated by `Orientation` so the rope runs along the rows,
+///   the captured frame if not rotated, mapped back to the captured one by the inverse of the `OrientationCtx::matrix`
+/// - `width`, `height` - size of the cropped strip, px
+/// - `tracked` - true if the rope band found near the band of the previous frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RoiCtx {
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub tracked: bool,
+}
+//
+//
+impl RoiCtx {
+    ///
+    /// Returns the row of the aligned frame for the `row` of the strip
+    pub fn aligned_row(&self, row: f64) -> f64 {
+        row + self.y as f64
+    }
+    ///
+    /// Returns position on the aligned frame for the `dot` of the strip
+    pub fn aligned(&self, dot: Dot<f64>) -> Dot<f64> {
+        Dot { x: dot.x, y: self.aligned_row(dot.y) }
+    }
+    ///
+    /// Returns the `line` of the strip on the aligned frame
+    pub fn aligned_line(&self, line: Line) -> Line {
+        Line::new(line.slope, self.aligned_row(line.intercept))
+    }
+}
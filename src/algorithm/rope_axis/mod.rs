@@ -0,0 +1,11 @@
+//!
+//! Axes of the rope, [part05_defect_detection](design/algorithm/part05_defect_detection.md)
+//! - Dynamic axis - centres of the rope cross-sections
+//! - Static axis - averaged straight line of the dynamic axis
+mod rope_axis_ctx;
+mod rope_axis;
+mod waviness;
+
+pub use rope_axis_ctx::*;
+pub use rope_axis::*;
+pub use waviness::*;
@@ -0,0 +1,123 @@
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        geometry::Line,
+        geometry_defect::{Defect, DefectSide, GeometryDefectType},
+        roi::RoiCtx,
+        rope_axis::{RopeAxisCtx, Waviness},
+        ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Side,
+    },
+    domain::{Dot, Error, Eval},
+};
+///
+/// Computes [dynamic and static axes](design/algorithm/part05_defect_detection.md) of the rope
+/// and detects waviness (волнистость) by the deviation of the dynamic axis from the static one
+/// - Waviness amplitude `A` gives envelope diameter `d1 = d + 2A`
+/// - Waviness detected if `d1 / d` reaches the `limit` and at least one full wave found
+pub struct RopeAxis {
+    dbg: Dbg,
+    limit: f64,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl RopeAxis {
+    ///
+    /// New instance [RopeAxis]
+    /// - `limit` - discard limit of `d1 / d`, [Waviness::RUNNING] or [Waviness::STATIONARY]
+    pub fn new(limit: f64, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("RopeAxis"),
+            limit,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns length of the wave of the `deviations`, 0.0 if no full wave found
+    /// - Crossings of the static axis are counted with hysteresis of the half `amplitude`,
+    ///   so the edge noise doesn't produce false crossings
+    fn wavelength(dynamic: &[Dot<f64>], deviations: &[f64], amplitude: f64) -> f64 {
+        let hysteresis = amplitude / 2.0;
+        if hysteresis <= 0.0 {
+            return 0.0;
+        }
+        let mut crossings = vec![];
+        let mut sign = 0;
+        for (dot, &deviation) in dynamic.iter().zip(deviations) {
+            let current = if deviation > hysteresis {
+                1
+            } else if deviation < -hysteresis {
+                -1
+            } else {
+                continue;
+            };
+            if sign != 0 && current != sign {
+                crossings.push(dot.x);
+            }
+            sign = current;
+        }
+        match (crossings.first(), crossings.last()) {
+            (Some(first), Some(last)) if crossings.len() >= 2 => 2.0 * (last - first) / (crossings.len() - 1) as f64,
+            _ => 0.0,
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for RopeAxis {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let upper = edges.get(Side::Upper);
+                let lower = edges.get(Side::Lower);
+                let dynamic: Vec<Dot<f64>> = upper.iter()
+                    .zip(lower.iter())
+                    .map(|(upper, lower)| Dot { x: upper.x, y: (upper.y + lower.y) / 2.0 })
+                    .collect();
+                let diameter = match dynamic.len() {
+                    0 => 0.0,
+                    len => upper.iter().zip(lower.iter())
+                        .map(|(upper, lower)| (upper.y - lower.y).abs())
+                        .sum::<f64>() / len as f64,
+                };
+                let static_axis = Line::fit(&dynamic);
+                let deviations: Vec<f64> = dynamic.iter().map(|dot| static_axis.distance(*dot)).collect();
+                let max = deviations.iter().cloned().fold(0.0, f64::max);
+                let min = deviations.iter().cloned().fold(0.0, f64::min);
+                let amplitude = (max - min) / 2.0;
+                let waviness = Waviness {
+                    amplitude,
+                    wavelength: Self::wavelength(&dynamic, &deviations, amplitude),
+                    ratio: if diameter > 0.0 { (diameter + 2.0 * amplitude) / diameter } else { 0.0 },
+                };
+                let result = match (dynamic.first(), dynamic.last()) {
+                    (Some(first), Some(last)) if waviness.wavelength > 0.0 && waviness.ratio >= self.limit => {
+                        log::debug!("{}.eval | Waviness detected: {:?}", self.dbg, waviness);
+                        Some(Defect::new(
+                            GeometryDefectType::Waviness,
+                            DefectSide::Both,
+                            first.x as usize,
+                            last.x as usize,
+                            amplitude,
+                            amplitude / diameter,
+                            Defect::confidence(waviness.ratio - 1.0, self.limit - 1.0),
+                        ))
+                    }
+                    _ => None,
+                };
+                let result = RopeAxisCtx {
+                    dynamic,
+                    static_axis,
+                    frame_axis: ContextRead::<RoiCtx>::read(&ctx).aligned_line(static_axis),
+                    deviation: max.max(-min),
+                    waviness,
+                    result,
+                };
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{
+    algorithm::{geometry::Line, geometry_defect::{Defect, GeometryDefectType}, rope_axis::Waviness},
+    domain::Dot,
+};
+///
+/// Store result of `RopeAxis`
+/// - `dynamic` - dynamic axis, centres of the rope cross-sections, on the strip cropped by `Roi`
+/// - `static_axis` - static axis, averaged straight line of the dynamic axis
+/// - `frame_axis` - static axis on the frame aligned by `Orientation`, moved from the strip cropped by `Roi`, see [RoiCtx](crate::algorithm::RoiCtx)
+/// - `deviation` - maximum distance between dynamic and static axes, px
+/// - `waviness` - measured waviness of the rope
+/// - `result` - [Defect] of [GeometryDefectType::Waviness] along the frame, if waviness exceeds the limit
+#[derive(Debug, Clone, Default)]
+pub struct RopeAxisCtx {
+    pub dynamic: Vec<Dot<f64>>,
+    pub static_axis: Line,
+    pub frame_axis: Line,
+    pub deviation: f64,
+    pub waviness: Waviness,
+    pub result: Option<Defect>,
+}
@@ -0,0 +1,21 @@
+///
+/// Waviness (волнистость) of the rope
+/// - `amplitude` - half of the peak to peak deviation of the dynamic axis from the static axis, px
+/// - `wavelength` - length of the wave along the static axis, px, 0.0 if no full wave found
+/// - `ratio` - `d1 / d`, where `d1` is diameter of the envelope of the wavy rope, `d` is rope diameter
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Waviness {
+    pub amplitude: f64,
+    pub wavelength: f64,
+    pub ratio: f64,
+}
+//
+//
+impl Waviness {
+    /// [GOST](design/references/GOST_33718-2015.pdf) discard limit of the `d1 / d`
+    /// for the rope sections running through the sheaves or on the drum
+    pub const RUNNING: f64 = 1.1;
+    /// [GOST](design/references/GOST_33718-2015.pdf) discard limit of the `d1 / d`
+    /// for the rope sections never running through the sheaves
+    pub const STATIONARY: f64 = 4.0 / 3.0;
+}
@@ -0,0 +1,10 @@
+//!
+//! Speed of the rope
+//! - Travel of the strand texture between consecutive frames divided by the frame interval
+//! - Stationary rope reported, so the repeated frames can be skipped
+//! - Coverage of the rope checked against the frame rate and the field of view of the camera
+mod speed_ctx;
+mod speed;
+
+pub use speed_ctx::*;
+pub use speed::*;
@@ -0,0 +1,99 @@
+use opencv::core::MatTraitConst;
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::Scale,
+        position::{Direction, PositionCtx},
+        speed::SpeedCtx,
+        ContextRead, ContextWrite, EvalResult, InitialCtx,
+    },
+    domain::{Error, Eval},
+};
+///
+/// Estimates speed and direction of the rope from the consecutive frames
+/// - Travel of the frame - [PositionCtx::travel_px], of the external feed if connected and the camera scale known,
+///   the optical shift otherwise
+/// - Speed - travel divided by the interval between the frame timestamps
+/// - Coverage - the consecutive frames must overlap by the `overlap` part of the frame width,
+///   so the whole length of the rope is inspected,
+///   the overlap below [Speed::OPTICAL] verified by the feed travel only, the optical shift aliases beyond the half
+///   of the frame, so the overlap by it never falls below and the speed `limit` keeps at least [Speed::OPTICAL],
+///   the frame with the unknown travel is not covered, the gap to the previous frame can't be excluded,
+///   nor the frame in the [PositionCtx::gap], its travel only extrapolated
+/// - Must be inserted after `Position`
+pub struct Speed {
+    dbg: Dbg,
+    scale: Option<Scale>,
+    overlap: f64,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Speed {
+    /// Travel below this is considered as stationary rope, px
+    const STATIONARY: f64 = 0.5;
+    /// Least overlap of the frames measured by the optical shift, part of the frame width,
+    /// the phase correlation aliases the travel past the half of the frame into the opposite shift
+    pub const OPTICAL: f64 = 0.5;
+    ///
+    /// New instance [Speed]
+    /// - `scale` - pixel to millimetre calibration of the camera, speed measured in px/s only without it
+    /// - `overlap` - required overlap of the consecutive frames, part of the frame width, 0.0...1.0
+    pub fn new(scale: Option<Scale>, overlap: f64, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Speed"),
+            scale,
+            overlap: overlap.clamp(0.0, 1.0),
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Speed {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let width = ContextRead::<InitialCtx>::read(&ctx).src_frame.mat.cols() as f64;
+                let position = ContextRead::<PositionCtx>::read(&ctx);
+                let seconds = position.interval as f64 / 1e9;
+                let travel = position.travel_px(self.scale);
+                let required = match position.optical(self.scale) {
+                    true => self.overlap.max(Self::OPTICAL),
+                    false => self.overlap,
+                };
+                let rate = match travel {
+                    Some(travel) if seconds > 0.0 => Some(travel / seconds),
+                    _ => None,
+                };
+                let overlap = travel.filter(|_| width > 0.0).map(|travel| 1.0 - travel.abs() / width);
+                let limit = match (self.scale, seconds > 0.0 && width > 0.0) {
+                    (Some(scale), true) => Some(scale.mm(width * (1.0 - required)) / seconds),
+                    _ => None,
+                };
+                let direction = Direction::of(travel.unwrap_or(0.0), Self::STATIONARY);
+                let result = SpeedCtx {
+                    speed: rate.zip(self.scale).map(|(rate, scale)| scale.mm(rate)),
+                    rate: rate.unwrap_or(0.0),
+                    direction,
+                    stationary: travel.is_some() && direction == Direction::Stationary,
+                    overlap,
+                    limit,
+                    covered: !position.gap && overlap.is_some_and(|overlap| overlap >= required),
+                };
+                match (result.covered, overlap) {
+                    (true, _) => log::debug!("{}.eval | Speed: {:?} mm/s, {:.1} px/s, {:?}", self.dbg, result.speed, result.rate, result.direction),
+                    (false, None) => log::warn!("{}.eval | Coverage unknown: travel of the frame isn't measured", self.dbg),
+                    (false, Some(_)) if position.gap => log::warn!("{}.eval | Coverage unknown: travel of the frame extrapolated over the position gap", self.dbg),
+                    (false, Some(overlap)) => log::warn!(
+                        "{}.eval | Coverage gap: frames overlap {:.2} of the frame, required {:.2}, speed {:?} mm/s exceeds {:?} mm/s",
+                        self.dbg, overlap, required, result.speed, result.limit,
+                    ),
+                }
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
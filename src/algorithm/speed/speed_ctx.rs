@@ -0,0 +1,37 @@
+use crate::algorithm::position::Direction;
+///
+/// Store result of `Speed`
+/// - `speed` - speed of the rope, mm/s, None if the travel or the camera scale unknown
+/// - `rate` - speed of the rope on the frame, px/s
+/// - `direction` - direction of the travel
+/// - `stationary` - true if the rope is measured not moving, the frame repeats the previous one
+/// - `overlap` - part of the frame overlapping the previous one, None if the travel unknown
+/// - `limit` - maximal speed keeping the required overlap at the current frame rate, mm/s,
+///   keeping at least a half of the frame, if the travel is the optical shift only, see [Speed::OPTICAL](crate::algorithm::Speed::OPTICAL)
+/// - `covered` - false if the rope travels too fast for the frame rate and the field of view,
+///   the gaps between the frames are not inspected, or if the travel unknown
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedCtx {
+    pub speed: Option<f64>,
+    pub rate: f64,
+    pub direction: Direction,
+    pub stationary: bool,
+    pub overlap: Option<f64>,
+    pub limit: Option<f64>,
+    pub covered: bool,
+}
+//
+//
+impl Default for SpeedCtx {
+    fn default() -> Self {
+        Self {
+            speed: None,
+            rate: 0.0,
+            direction: Direction::Stationary,
+            stationary: false,
+            overlap: None,
+            limit: None,
+            covered: false,
+        }
+    }
+}
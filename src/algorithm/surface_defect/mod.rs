@@ -0,0 +1,14 @@
+//!
+//! Detecting surface defect's inside the rope band
+//! Realized algorithm's:
+//! - `Corrosion` - colour (rust tint) and texture change
+//! - `Wear` - flattened, shiny outer wires
+mod surface_defect_ctx;
+mod surface_defect_type;
+mod surface_defect;
+mod surface_threshold;
+
+pub use surface_defect_ctx::*;
+pub use surface_defect_type::*;
+pub use surface_defect::*;
+pub use surface_threshold::*;
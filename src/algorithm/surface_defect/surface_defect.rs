@@ -0,0 +1,195 @@
+use opencv::core::{MatTraitConst, Vec3b};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        surface_defect::{SurfaceDefectCtx, SurfaceDefectType, SurfaceRegion, SurfaceSegment, SurfaceThreshold},
+        ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, Side,
+    },
+    domain::{Error, Eval, Image},
+};
+///
+/// Estimates [corrosion and surface wear](design/algorithm/part02_overview.md) inside the rope band
+/// - The rope band between upper and lower edges is splitted into segments of `segment` columns
+/// - Each segment gets corrosion score (rust tint, rough texture) and wear score (shiny, smooth texture)
+/// - Neighbouring segments of the same kind are merged into [SurfaceRegion]
+/// - Rust tint requires the colour BGR frame, the Bayer mosaic of the camera demosaiced on the capture,
+///   see `PixelFormat::demosaic`, the monochrome frame scored by the texture only
+pub struct SurfaceDefect {
+    dbg: Dbg,
+    segment: usize,
+    threshold: SurfaceThreshold,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl SurfaceDefect {
+    /// Minimal saturation of the rust tinted pixel
+    const RUST_SATURATION: f64 = 0.25;
+    /// Minimal brightness of each channel of the shiny pixel
+    const SHINE: f64 = 220.0;
+    ///
+    /// New instance [SurfaceDefect]
+    /// - `segment` - length of the segment in columns
+    /// - `threshold` - scores to classify the segment
+    pub fn new(
+        segment: usize,
+        threshold: SurfaceThreshold,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("SurfaceDefect"),
+            segment: segment.max(1),
+            threshold,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns pixel channels [blue, green, red], monochrome pixel returned in all channels
+    fn pixel(image: &Image, row: usize, col: usize) -> Result<[f64; 3], String> {
+        match image.mat.channels() {
+            1 => image.mat.at_2d::<u8>(row as i32, col as i32)
+                .map(|&value| [value as f64; 3])
+                .map_err(|err| err.to_string()),
+            _ => image.mat.at_2d::<Vec3b>(row as i32, col as i32)
+                .map(|value| [value[0] as f64, value[1] as f64, value[2] as f64])
+                .map_err(|err| err.to_string()),
+        }
+    }
+    ///
+    /// Returns true if pixel has a rust tint: red > green > blue and saturated enough
+    fn is_rust([b, g, r]: [f64; 3]) -> bool {
+        r > g && g > b && r > 0.0 && (r - b) / r >= Self::RUST_SATURATION
+    }
+    ///
+    /// Returns true if pixel is a specular reflection of the flattened wire
+    fn is_shine([b, g, r]: [f64; 3]) -> bool {
+        b.min(g).min(r) >= Self::SHINE
+    }
+    ///
+    /// Returns median of the values, 0.0 if empty
+    fn median(values: &[f64]) -> f64 {
+        let mut values = values.to_vec();
+        values.sort_by(f64::total_cmp);
+        let len = values.len();
+        match len {
+            0 => 0.0,
+            _ if len % 2 == 1 => values[len / 2],
+            _ => (values[len / 2 - 1] + values[len / 2]) / 2.0,
+        }
+    }
+    ///
+    /// Returns segments with raw rust, shine and texture values
+    /// - `band` - [column, top row, bottom row] of the rope band
+    fn measure(&self, image: &Image, band: &[[usize; 3]]) -> Result<Vec<(usize, usize, f64, f64, f64)>, String> {
+        let mut segments = vec![];
+        for chunk in band.chunks(self.segment) {
+            let mut pixels = 0.0;
+            let mut rust = 0.0;
+            let mut shine = 0.0;
+            let mut texture = 0.0;
+            let mut diffs = 0.0;
+            for (i, &[col, top, bottom]) in chunk.iter().enumerate() {
+                let next = chunk.get(i + 1);
+                for row in top..=bottom {
+                    let pixel = Self::pixel(image, row, col)?;
+                    pixels += 1.0;
+                    if Self::is_rust(pixel) {
+                        rust += 1.0;
+                    }
+                    if Self::is_shine(pixel) {
+                        shine += 1.0;
+                    }
+                    if let Some(&[next_col, next_top, next_bottom]) = next {
+                        if (next_top..=next_bottom).contains(&row) {
+                            let next_pixel = Self::pixel(image, row, next_col)?;
+                            let brightness = pixel.iter().sum::<f64>() / 3.0;
+                            let next_brightness = next_pixel.iter().sum::<f64>() / 3.0;
+                            texture += (next_brightness - brightness).abs();
+                            diffs += 1.0;
+                        }
+                    }
+                }
+            }
+            if let (Some(first), Some(last)) = (chunk.first(), chunk.last()) {
+                segments.push((
+                    first[0],
+                    last[0],
+                    if pixels > 0.0 { rust / pixels } else { 0.0 },
+                    if pixels > 0.0 { shine / pixels } else { 0.0 },
+                    if diffs > 0.0 { texture / diffs } else { 0.0 },
+                ));
+            }
+        }
+        Ok(segments)
+    }
+    ///
+    /// Returns scored and classified segments
+    /// - texture of the segment is taken relative to the median texture of the frame
+    fn classify(&self, measured: Vec<(usize, usize, f64, f64, f64)>) -> Vec<SurfaceSegment> {
+        let textures: Vec<f64> = measured.iter().map(|(_, _, _, _, texture)| *texture).collect();
+        let median = Self::median(&textures);
+        measured.into_iter().map(|(start, end, rust, shine, texture)| {
+            let texture = if median > 0.0 { texture / median } else { 1.0 };
+            let corrosion = (rust + (texture - 1.0).clamp(0.0, 1.0)) / 2.0;
+            let wear = (shine + (1.0 - texture).clamp(0.0, 1.0)) / 2.0;
+            let kind = if corrosion >= self.threshold.corrosion && corrosion >= wear {
+                Some(SurfaceDefectType::Corrosion)
+            } else if wear >= self.threshold.wear {
+                Some(SurfaceDefectType::Wear)
+            } else {
+                None
+            };
+            SurfaceSegment { start, end, corrosion, wear, kind }
+        }).collect()
+    }
+    ///
+    /// Merges neighbouring segments of the same kind into regions
+    fn regions(segments: &[SurfaceSegment]) -> Vec<SurfaceRegion> {
+        segments.iter().fold(vec![], |mut acc: Vec<SurfaceRegion>, segment| {
+            if let Some(kind) = segment.kind {
+                let score = match kind {
+                    SurfaceDefectType::Corrosion => segment.corrosion,
+                    SurfaceDefectType::Wear => segment.wear,
+                };
+                match acc.last_mut() {
+                    Some(prev) if prev.kind == kind && prev.end + 1 >= segment.start => {
+                        prev.end = segment.end;
+                        prev.score = prev.score.max(score);
+                    }
+                    _ => acc.push(SurfaceRegion { kind, start: segment.start, end: segment.end, score }),
+                }
+            }
+            acc
+        })
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for SurfaceDefect {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let image = &ContextRead::<InitialCtx>::read(&ctx).src_frame;
+                if image.mat.channels() == 1 {
+                    log::debug!("{}.eval | Monochrome frame, rust tint not assessed", self.dbg);
+                }
+                let edges = &ContextRead::<EdgeDetectionCtx>::read(&ctx).result;
+                let band: Vec<[usize; 3]> = edges.get(Side::Upper).iter()
+                    .zip(edges.get(Side::Lower).iter())
+                    .map(|(upper, lower)| [upper.x as usize, upper.y.min(lower.y).round() as usize, upper.y.max(lower.y).round() as usize])
+                    .collect();
+                match self.measure(image, &band) {
+                    Ok(measured) => {
+                        let segments = self.classify(measured);
+                        let result = Self::regions(&segments);
+                        log::debug!("{}.eval | Surface regions: {:?}", self.dbg, result);
+                        ctx.write(SurfaceDefectCtx { segments, result })
+                    }
+                    Err(err) => Err(error.pass_with("Input image format error", err)),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::algorithm::surface_defect::SurfaceDefectType;
+///
+/// Scores of the single segment of the rope band
+/// - `start`, `end` - first and last column of the segment
+/// - `corrosion` - corrosion score, 0...1
+/// - `wear` - wear score, 0...1
+/// - `kind` - classified defect, if score exceeds threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceSegment {
+    pub start: usize,
+    pub end: usize,
+    pub corrosion: f64,
+    pub wear: f64,
+    pub kind: Option<SurfaceDefectType>,
+}
+///
+/// Region of the neighbouring segments with the same defect
+/// - `start`, `end` - first and last column of the region
+/// - `score` - maximum score of the segments in the region
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceRegion {
+    pub kind: SurfaceDefectType,
+    pub start: usize,
+    pub end: usize,
+    pub score: f64,
+}
+///
+/// Store result of `SurfaceDefect`
+/// - `segments` - scores of each segment of the rope band
+/// - `result` - classified regions
+#[derive(Debug, Clone, Default)]
+pub struct SurfaceDefectCtx {
+    pub segments: Vec<SurfaceSegment>,
+    pub result: Vec<SurfaceRegion>,
+}
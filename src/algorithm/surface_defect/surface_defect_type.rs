@@ -0,0 +1,10 @@
+///
+/// Enum of surface defect type's,
+/// [корозионные повреждения и поверхностный износ](design/algorithm/part02_overview.md)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurfaceDefectType {
+    /// Rust tint and rough texture of the wires
+    Corrosion,
+    /// Flattened, shiny outer wires
+    Wear,
+}
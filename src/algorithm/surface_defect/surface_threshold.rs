@@ -0,0 +1,25 @@
+///
+/// Store threshodls values for `SurfaceDefect`
+/// - `corrosion` - minimal corrosion score of the segment, 0...1
+/// - `wear` - minimal wear score of the segment, 0...1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceThreshold {
+    pub corrosion: f64,
+    pub wear: f64,
+}
+//
+//
+impl SurfaceThreshold {
+    ///
+    /// Returns [SurfaceThreshold] new instance
+    pub fn new(corrosion: f64, wear: f64) -> Self {
+        Self { corrosion, wear }
+    }
+}
+//
+//
+impl Default for SurfaceThreshold {
+    fn default() -> Self {
+        Self::new(0.3, 0.3)
+    }
+}
@@ -0,0 +1,171 @@
+use std::{fs, path::Path};
+use opencv::{
+    calib3d, imgcodecs, imgproc,
+    core::{Mat, MatTraitConst, Point2f, Point3f, Size, TermCriteria, TermCriteria_Type, Vector},
+};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::undistort::Intrinsics,
+    domain::{Error, Eval, Image},
+};
+///
+/// Computes camera [Intrinsics] and lens distortion from the set of checkerboard images
+/// - `pattern` - number of inner corners of the checkerboard per row and per column
+/// - `square` - size of the checkerboard square, mm
+/// - All the images must be of the same size, the images differing from the first one with the checkerboard found are skipped
+pub struct CameraCalibration {
+    dbg: Dbg,
+    pattern: Size,
+    square: f32,
+}
+//
+//
+impl CameraCalibration {
+    /// Minimal number of images with the checkerboard found
+    const MIN_IMAGES: usize = 3;
+    /// Extensions of the image files read from the directory
+    const EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "bmp", "tif"];
+    ///
+    /// New instance [CameraCalibration]
+    pub fn new(pattern: (usize, usize), square: f64) -> Self {
+        Self {
+            dbg: Dbg::own("CameraCalibration"),
+            pattern: Size::new(pattern.0 as i32, pattern.1 as i32),
+            square: square as f32,
+        }
+    }
+    ///
+    /// Returns the grayscale images of the checkerboard read from the `dir`, in the order of the file names
+    pub fn images(dir: impl AsRef<Path>) -> Result<Vec<Image>, Error> {
+        let error = Error::new("CameraCalibration", "images");
+        let mut paths: Vec<_> = match fs::read_dir(dir.as_ref()) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|ext| Self::EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+                })
+                .collect(),
+            Err(err) => return Err(error.pass_with(format!("Read {:?} error", dir.as_ref()), err.to_string())),
+        };
+        paths.sort();
+        let mut images = vec![];
+        for path in paths {
+            match imgcodecs::imread(&path.to_string_lossy(), imgcodecs::IMREAD_GRAYSCALE) {
+                Ok(mat) if !mat.empty() => images.push(Image::new(mat.cols() as usize, mat.rows() as usize, mat, 0)),
+                Ok(_) => log::warn!("CameraCalibration.images | Image {:?} not decoded", path),
+                Err(err) => return Err(error.pass_with(format!("Read {:?} error", path), err.to_string())),
+            }
+        }
+        Ok(images)
+    }
+    ///
+    /// Returns corners of the checkerboard in it's own plane
+    fn board(&self) -> Vector<Point3f> {
+        (0..self.pattern.height).flat_map(|row| {
+            (0..self.pattern.width).map(move |col| Point3f::new(col as f32 * self.square, row as f32 * self.square, 0.0))
+        }).collect()
+    }
+    ///
+    /// Returns refined corners of the checkerboard, None if it isn't found on the `image`
+    fn corners(&self, image: &Image) -> Result<Option<Vector<Point2f>>, opencv::Error> {
+        let gray = match image.mat.channels() {
+            1 => image.mat.clone(),
+            _ => {
+                let mut gray = Mat::default();
+                imgproc::cvt_color(&image.mat, &mut gray, imgproc::COLOR_BGR2GRAY, 0)?;
+                gray
+            }
+        };
+        let mut corners = Vector::<Point2f>::new();
+        let found = calib3d::find_chessboard_corners(
+            &gray,
+            self.pattern,
+            &mut corners,
+            calib3d::CALIB_CB_ADAPTIVE_THRESH + calib3d::CALIB_CB_NORMALIZE_IMAGE,
+        )?;
+        if !found {
+            return Ok(None);
+        }
+        imgproc::corner_sub_pix(
+            &gray,
+            &mut corners,
+            Size::new(11, 11),
+            Size::new(-1, -1),
+            TermCriteria::new(TermCriteria_Type::COUNT as i32 + TermCriteria_Type::EPS as i32, 30, 0.001)?,
+        )?;
+        Ok(Some(corners))
+    }
+}
+//
+//
+impl Eval<Vec<Image>, Result<Intrinsics, Error>> for CameraCalibration {
+    fn eval(&self, images: Vec<Image>) -> Result<Intrinsics, Error> {
+        let error = Error::new(&self.dbg, "eval");
+        let board = self.board();
+        let mut object_points = Vector::<Vector<Point3f>>::new();
+        let mut image_points = Vector::<Vector<Point2f>>::new();
+        let mut size = Size::default();
+        for (i, image) in images.iter().enumerate() {
+            let image_size = Size::new(image.mat.cols(), image.mat.rows());
+            match self.corners(image) {
+                Ok(Some(_)) if !image_points.is_empty() && image_size != size => log::warn!(
+                    "{}.eval | Image {} of {}x{} skipped, differs from the first checkerboard image of {}x{}",
+                    self.dbg, i, image_size.width, image_size.height, size.width, size.height,
+                ),
+                Ok(Some(corners)) => {
+                    size = image_size;
+                    object_points.push(board.clone());
+                    image_points.push(corners);
+                }
+                Ok(None) => log::warn!("{}.eval | Checkerboard not found on the image {}", self.dbg, i),
+                Err(err) => return Err(error.pass_with(format!("Image {} error", i), err.to_string())),
+            }
+        }
+        if image_points.len() < Self::MIN_IMAGES {
+            return Err(error.err(format!(
+                "Checkerboard found on {} images, at least {} required",
+                image_points.len(), Self::MIN_IMAGES,
+            )));
+        }
+        let mut camera_matrix = Mat::default();
+        let mut dist_coeffs = Mat::default();
+        let mut rvecs = Vector::<Mat>::new();
+        let mut tvecs = Vector::<Mat>::new();
+        let criteria = match TermCriteria::new(TermCriteria_Type::COUNT as i32 + TermCriteria_Type::EPS as i32, 30, f64::EPSILON) {
+            Ok(criteria) => criteria,
+            Err(err) => return Err(error.pass(err.to_string())),
+        };
+        match calib3d::calibrate_camera(
+            &object_points, &image_points, size,
+            &mut camera_matrix, &mut dist_coeffs, &mut rvecs, &mut tvecs,
+            0, criteria,
+        ) {
+            Ok(rms) => {
+                let mut matrix = [0.0; 9];
+                for (i, value) in matrix.iter_mut().enumerate() {
+                    *value = match camera_matrix.at_2d::<f64>(i as i32 / 3, i as i32 % 3) {
+                        Ok(value) => *value,
+                        Err(err) => return Err(error.pass_with("Camera matrix error", err.to_string())),
+                    };
+                }
+                let mut coeffs = vec![];
+                for i in 0..dist_coeffs.total() {
+                    match dist_coeffs.at::<f64>(i as i32) {
+                        Ok(value) => coeffs.push(*value),
+                        Err(err) => return Err(error.pass_with("Distortion coefficients error", err.to_string())),
+                    }
+                }
+                log::info!("{}.eval | Calibrated on {} images, RMS error: {:.4} px", self.dbg, image_points.len(), rms);
+                Ok(Intrinsics {
+                    camera_matrix: matrix,
+                    dist_coeffs: coeffs,
+                    width: size.width as usize,
+                    height: size.height as usize,
+                    error: rms,
+                })
+            }
+            Err(err) => Err(error.pass_with("Calibration error", err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::{fs, path::{Path, PathBuf}};
+use serde::{Deserialize, Serialize};
+use crate::domain::Error;
+///
+/// Intrinsic parameters of the camera
+/// - `camera_matrix` - [fx, 0, cx, 0, fy, cy, 0, 0, 1], row by row
+/// - `dist_coeffs` - lens distortion coefficients (k1, k2, p1, p2, k3, ...)
+/// - `width`, `height` - resolution of the calibration images
+/// - `error` - RMS reprojection error of the calibration, px
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Intrinsics {
+    pub camera_matrix: [f64; 9],
+    pub dist_coeffs: Vec<f64>,
+    pub width: usize,
+    pub height: usize,
+    pub error: f64,
+}
+//
+//
+impl Intrinsics {
+    ///
+    /// Returns path of the intrinsics file of the `camera` in the `dir`
+    pub fn path(dir: impl AsRef<Path>, camera: &str) -> PathBuf {
+        dir.as_ref().join(format!("{}.intrinsics.json", camera.trim_start_matches('/').replace('/', "-")))
+    }
+    ///
+    /// Reads [Intrinsics] from the json file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let error = Error::new("Intrinsics", "read");
+        match fs::read_to_string(path.as_ref()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|err| error.pass_with(format!("Parse {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with(format!("Read {:?} error", path.as_ref()), err.to_string())),
+        }
+    }
+    ///
+    /// Writes [Intrinsics] into the json file
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let error = Error::new("Intrinsics", "write");
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => fs::write(path.as_ref(), json)
+                .map_err(|err| error.pass_with(format!("Write {:?} error", path.as_ref()), err.to_string())),
+            Err(err) => Err(error.pass_with("Serialize error", err.to_string())),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//!
+//! Lens distortion correction
+//! - [CameraCalibration] - computes camera [Intrinsics] from the set of checkerboard images
+//! - [UndistortMap] - precomputed remap tables of the camera
+//! - [Undistort] - stage, correcting the source frame first, before any measurement
+mod camera_calibration;
+mod intrinsics;
+mod undistort_map;
+mod undistort;
+
+pub use camera_calibration::*;
+pub use intrinsics::*;
+pub use undistort_map::*;
+pub use undistort::*;
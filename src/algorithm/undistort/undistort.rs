@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use opencv::{core::{self, MatTraitConst}, imgproc};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{undistort::UndistortMap, ContextRead, ContextWrite, EvalResult, InitialCtx},
+    domain::{Error, Eval, Image},
+};
+///
+/// Corrects lens distortion of the source frame using precomputed [UndistortMap]
+/// - Replaces `src_frame` of the [InitialCtx], so must be inserted before `DetectingContoursCv`
+/// - Frame of the resolution other than the calibration one rejected, the tables don't fit it
+/// - Without the map, the camera not calibrated, the frame passed as is
+pub struct Undistort {
+    dbg: Dbg,
+    map: Option<Arc<UndistortMap>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Undistort {
+    ///
+    /// New instance [Undistort]
+    /// - `map` - remap tables of the camera, None if the camera isn't calibrated
+    pub fn new(map: Option<Arc<UndistortMap>>, ctx: impl Eval<(), EvalResult> + 'static) -> Self {
+        Self {
+            dbg: Dbg::own("Undistort"),
+            map,
+            ctx: Box::new(ctx),
+        }
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Undistort {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let map = match &self.map {
+                    Some(map) => map,
+                    None => return Ok(ctx),
+                };
+                let src = &ContextRead::<InitialCtx>::read(&ctx).src_frame;
+                if src.mat.empty() {
+                    return Ok(ctx);
+                }
+                let size = core::Size::new(src.mat.cols(), src.mat.rows());
+                if size != map.size {
+                    return Err(error.err(format!(
+                        "Frame {}x{} doesn't match the calibration {}x{}",
+                        size.width, size.height, map.size.width, map.size.height,
+                    )));
+                }
+                let mut dst = core::Mat::default();
+                match imgproc::remap(
+                    &src.mat,
+                    &mut dst,
+                    &map.map1,
+                    &map.map2,
+                    imgproc::INTER_LINEAR,
+                    core::BORDER_CONSTANT,
+                    core::Scalar::default(),
+                ) {
+                    Ok(_) => {
+                        let result = InitialCtx::new(Image {
+                            width: src.width,
+                            height: src.height,
+                            timestamp: src.timestamp,
+                            mat: dst,
+                            bytes: src.bytes,
+                        });
+                        ctx.write(result)
+                    }
+                    Err(err) => Err(error.pass(err.to_string())),
+                }
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
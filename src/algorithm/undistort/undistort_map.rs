@@ -0,0 +1,50 @@
+use opencv::{
+    calib3d,
+    core::{self, Mat, Size},
+};
+use crate::{algorithm::undistort::Intrinsics, domain::Error};
+///
+/// Precomputed remap tables of the camera, built once from [Intrinsics]
+/// and shared between frames
+/// - `size` - resolution of the frames the tables built for, of the calibration images
+#[derive(Debug)]
+pub struct UndistortMap {
+    pub map1: Mat,
+    pub map2: Mat,
+    pub size: Size,
+}
+//
+//
+impl UndistortMap {
+    ///
+    /// Returns [UndistortMap] new instance
+    /// - Corrected frame keeps the camera matrix, so the scale at the frame center stays the same
+    pub fn new(intrinsics: &Intrinsics) -> Result<Self, Error> {
+        let error = Error::new("UndistortMap", "new");
+        let m = &intrinsics.camera_matrix;
+        let camera_matrix = match Mat::from_slice_2d(&[[m[0], m[1], m[2]], [m[3], m[4], m[5]], [m[6], m[7], m[8]]]) {
+            Ok(matrix) => matrix,
+            Err(err) => return Err(error.pass_with("Camera matrix error", err.to_string())),
+        };
+        let dist_coeffs = match Mat::from_slice_2d(&[intrinsics.dist_coeffs.as_slice()]) {
+            Ok(coeffs) => coeffs,
+            Err(err) => return Err(error.pass_with("Distortion coefficients error", err.to_string())),
+        };
+        let size = Size::new(intrinsics.width as i32, intrinsics.height as i32);
+        let mut map1 = Mat::default();
+        let mut map2 = Mat::default();
+        match calib3d::init_undistort_rectify_map(
+            &camera_matrix,
+            &dist_coeffs,
+            &core::no_array(),
+            &camera_matrix,
+            size,
+            core::CV_16SC2,
+            &mut map1,
+            &mut map2,
+        ) {
+            Ok(_) => Ok(Self { map1, map2, size }),
+            Err(err) => Err(error.pass_with("Remap tables error", err.to_string())),
+        }
+    }
+}
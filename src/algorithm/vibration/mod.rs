@@ -0,0 +1,15 @@
+//!
+//! Vibration and swing of the rope
+//! - Lateral position of the rope axis on the frame tracked over the consecutive frames
+//! - Amplitude and dominant frequency of the lateral displacement, machine condition signal
+//! - Geometry measurements of the frames swinging over the limit flagged as unreliable,
+//!   the band moves within the frame and widens by the motion blur
+mod vibration_conf;
+mod vibration_history;
+mod vibration_ctx;
+mod vibration;
+
+pub use vibration_conf::*;
+pub use vibration_history::*;
+pub use vibration_ctx::*;
+pub use vibration::*;
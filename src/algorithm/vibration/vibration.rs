@@ -0,0 +1,145 @@
+use std::{collections::VecDeque, f64::consts::PI, sync::Arc};
+use sal_core::dbg::Dbg;
+use crate::{
+    algorithm::{
+        diameter::Scale,
+        roi::RoiCtx,
+        rope_axis::RopeAxisCtx,
+        vibration::{VibrationConf, VibrationCtx, VibrationHistory},
+        ContextRead, ContextWrite, EvalResult, InitialCtx,
+    },
+    domain::{Error, Eval, RwLock},
+};
+///
+/// Measures the lateral vibration and swing of the rope
+/// - Lateral position of the frame - mean of the dynamic axis of `RopeAxis`, moved onto the frame aligned by `Orientation`,
+///   across the rope, whatever the angle of the camera,
+///   the axis taken from the edges as measured, not smoothed over the frames, so the swing isn't damped
+/// - Amplitude - half of the peak to peak position over the last `window` frames
+/// - Dominant frequency - strongest harmonic of the discrete Fourier transform of the positions,
+///   the frames assumed evenly spaced by their mean interval
+/// - Frame without the rope has no measurement, it's `reliable` kept from the last measured frame
+/// - Frame swinging with the amplitude over the `limit` marked as unreliable,
+///   so `GeometryDefect` flags its records, `Baseline` doesn't learn it,
+///   `DefectTracker` skips its records and `Discard` doesn't assess its diameter and waviness
+/// - Must be inserted after `RopeAxis`
+pub struct Vibration {
+    dbg: Dbg,
+    conf: VibrationConf,
+    scale: Option<Scale>,
+    history: Arc<RwLock<VibrationHistory>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
+}
+//
+//
+impl Vibration {
+    /// Minimal number of the frames the frequency measured on
+    const SAMPLES: usize = 8;
+    ///
+    /// New instance [Vibration]
+    /// - `conf` - window and limit of the measurement
+    /// - `scale` - pixel to millimetre calibration of the camera, if known
+    /// - `history` - positions of the rope axis, shared between the frames of the same camera
+    pub fn new(
+        conf: VibrationConf,
+        scale: Option<Scale>,
+        history: Arc<RwLock<VibrationHistory>>,
+        ctx: impl Eval<(), EvalResult> + 'static,
+    ) -> Self {
+        Self {
+            dbg: Dbg::own("Vibration"),
+            conf,
+            scale,
+            history,
+            ctx: Box::new(ctx),
+        }
+    }
+    ///
+    /// Returns half of the peak to peak position of the `samples`, px
+    fn amplitude(samples: &VecDeque<(usize, f64)>) -> f64 {
+        let (min, max) = samples
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), (_, axis)| (min.min(*axis), max.max(*axis)));
+        (max - min) / 2.0
+    }
+    ///
+    /// Returns dominant frequency of the `samples`, Hz, None if not enough samples or no displacement
+    fn frequency(samples: &VecDeque<(usize, f64)>, mean: f64) -> Option<f64> {
+        let n = samples.len();
+        let (first, last) = (samples.front()?.0, samples.back()?.0);
+        if n < Self::SAMPLES || last <= first {
+            return None;
+        }
+        let interval = (last - first) as f64 / 1e9 / (n - 1) as f64;
+        let mut best: Option<(usize, f64)> = None;
+        for k in 1..=n / 2 {
+            let (mut re, mut im) = (0.0, 0.0);
+            for (i, (_, axis)) in samples.iter().enumerate() {
+                let phase = 2.0 * PI * (k * i) as f64 / n as f64;
+                re += (axis - mean) * phase.cos();
+                im -= (axis - mean) * phase.sin();
+            }
+            let power = re * re + im * im;
+            if power > f64::EPSILON && best.is_none_or(|(_, best)| power > best) {
+                best = Some((k, power));
+            }
+        }
+        best.map(|(k, _)| k as f64 / (n as f64 * interval))
+    }
+}
+//
+//
+impl Eval<(), EvalResult> for Vibration {
+    fn eval(&self, _: ()) -> EvalResult {
+        let error = Error::new(&self.dbg, "eval");
+        match self.ctx.eval(()) {
+            Ok(ctx) => {
+                let timestamp = ContextRead::<InitialCtx>::read(&ctx).src_frame.timestamp;
+                let roi = ContextRead::<RoiCtx>::read(&ctx);
+                let dynamic = &ContextRead::<RopeAxisCtx>::read(&ctx).dynamic;
+                let axis = match dynamic.is_empty() {
+                    true => None,
+                    false => Some(dynamic.iter().map(|dot| roi.aligned(*dot).y).sum::<f64>() / dynamic.len() as f64),
+                };
+                let mut history = self.history.write();
+                if let Some(axis) = axis {
+                    history.push(timestamp, axis, self.conf.window);
+                }
+                let samples = &history.samples;
+                let result = match axis {
+                    _ if samples.is_empty() => VibrationCtx::default(),
+                    // The history isn't changed since the last measured frame
+                    None => {
+                        log::debug!("{}.eval | Rope not found, no measurement", self.dbg);
+                        VibrationCtx { reliable: Self::amplitude(samples) <= self.conf.limit, ..Default::default() }
+                    }
+                    Some(axis) => {
+                        let mean = samples.iter().map(|(_, axis)| axis).sum::<f64>() / samples.len() as f64;
+                        let amplitude = Self::amplitude(samples);
+                        let frequency = Self::frequency(samples, mean);
+                        let velocity = frequency.map(|frequency| 2.0 * PI * frequency * amplitude);
+                        VibrationCtx {
+                            axis: Some(axis),
+                            displacement: axis - mean,
+                            amplitude,
+                            amplitude_mm: self.scale.map(|scale| scale.mm(amplitude)),
+                            frequency,
+                            velocity,
+                            velocity_mm: self.scale.zip(velocity).map(|(scale, velocity)| scale.mm(velocity)),
+                            reliable: amplitude <= self.conf.limit,
+                        }
+                    }
+                };
+                match result.reliable {
+                    true => log::debug!("{}.eval | Amplitude: {:.2} px, frequency: {:?} Hz", self.dbg, result.amplitude, result.frequency),
+                    false => log::warn!(
+                        "{}.eval | Swing {:.2} px exceeds {:.2} px, frequency: {:?} Hz, geometry unreliable",
+                        self.dbg, result.amplitude, self.conf.limit, result.frequency,
+                    ),
+                }
+                ctx.write(result)
+            }
+            Err(err) => Err(error.pass(err)),
+        }
+    }
+}
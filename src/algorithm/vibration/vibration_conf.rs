@@ -0,0 +1,19 @@
+///
+/// Configuration parameters of the `Vibration`
+/// - `window` - number of the last frames the vibration measured over
+/// - `limit` - amplitude of the lateral displacement, geometry of the frame unreliable above it, px
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VibrationConf {
+    pub window: usize,
+    pub limit: f64,
+}
+//
+//
+impl Default for VibrationConf {
+    fn default() -> Self {
+        Self {
+            window: 64,
+            limit: 5.0,
+        }
+    }
+}
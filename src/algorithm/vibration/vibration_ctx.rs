@@ -0,0 +1,38 @@
+///
+/// Store result of `Vibration`
+/// - `axis` - lateral position of the rope axis on the frame aligned by `Orientation`, px, None if the rope isn't found,
+///   then the frame has no measurement, only `reliable` kept from the last measured frame, the rest zero or None
+/// - `displacement` - deviation of the `axis` from its mean position over the window, px
+/// - `amplitude` - half of the peak to peak lateral displacement over the window, px
+/// - `amplitude_mm` - same, mm, if the camera [Scale](crate::algorithm::Scale) is calibrated
+/// - `frequency` - dominant frequency of the lateral displacement, Hz, None if not enough frames
+/// - `velocity` - peak lateral velocity `2π * frequency * amplitude`, px/s
+/// - `velocity_mm` - same, mm/s, if the camera [Scale](crate::algorithm::Scale) is calibrated
+/// - `reliable` - false if the `amplitude` exceeds the limit, the geometry of the frame distorted by the swing
+#[derive(Debug, Clone, PartialEq)]
+pub struct VibrationCtx {
+    pub axis: Option<f64>,
+    pub displacement: f64,
+    pub amplitude: f64,
+    pub amplitude_mm: Option<f64>,
+    pub frequency: Option<f64>,
+    pub velocity: Option<f64>,
+    pub velocity_mm: Option<f64>,
+    pub reliable: bool,
+}
+//
+//
+impl Default for VibrationCtx {
+    fn default() -> Self {
+        Self {
+            axis: None,
+            displacement: 0.0,
+            amplitude: 0.0,
+            amplitude_mm: None,
+            frequency: None,
+            velocity: None,
+            velocity_mm: None,
+            reliable: true,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::VecDeque;
+///
+/// Lateral position of the rope axis on the last frames, shared between the frames of the same camera
+/// - `samples` - timestamp of the frame, ns, and the position of the axis on the frame aligned by `Orientation`, px
+#[derive(Debug, Clone, Default)]
+pub struct VibrationHistory {
+    pub samples: VecDeque<(usize, f64)>,
+}
+//
+//
+impl VibrationHistory {
+    ///
+    /// Appends the `axis` position of the frame taken at the `timestamp`, keeps the last `window` samples
+    /// - History restarted if the timestamp goes back, the frames of another stream
+    pub fn push(&mut self, timestamp: usize, axis: f64, window: usize) {
+        if self.samples.back().is_some_and(|(last, _)| *last > timestamp) {
+            self.samples.clear();
+        }
+        self.samples.push_back((timestamp, axis));
+        while self.samples.len() > window.max(1) {
+            self.samples.pop_front();
+        }
+    }
+}
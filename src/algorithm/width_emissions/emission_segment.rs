@@ -0,0 +1,22 @@
+use crate::algorithm::geometry_defect::DefectSide;
+///
+/// Contiguous run of the columns, where the rope width deviates from it's center
+/// - `start`, `end` - first and last column of the segment, px
+/// - `first`, `last` - indexes of the first and last edge points of the segment
+/// - `upper`, `lower` - deviation of each edge from it's center, perpendicular to the fitted edge line,
+///   per edge point `first..=last`, px
+/// - `peak` - maximum deviation of the width, px
+/// - `area` - width deviations summed over the segment, multiplied by the column step, px²
+/// - `side` - edge moved, [DefectSide::Both] if both or neither edge stands out alone, the side of the Hill and Pit
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmissionSegment {
+    pub start: usize,
+    pub end: usize,
+    pub first: usize,
+    pub last: usize,
+    pub upper: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub peak: f64,
+    pub area: f64,
+    pub side: DefectSide,
+}
@@ -1,5 +1,7 @@
+mod emission_segment;
 mod width_emissions_ctx;
 mod width_emissions;
 
+pub use emission_segment::*;
 pub use width_emissions_ctx::*;
 pub use width_emissions::*;
@@ -1,10 +1,7 @@
 use sal_core::dbg::Dbg;
 use crate::{
     algorithm::{
-            geometry_defect::Threshold, mad::{
-                Bond, 
-                MadCtx
-            }, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Side
+            geometry::Line, geometry_defect::{DefectSide, Threshold}, mad::OutlierCtx, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, Side
         }, 
     domain::{
             Dot, 
@@ -12,70 +9,124 @@ use crate::{
             Eval
         }
     };
-use super::WidthEmissionsCtx;
+use super::{EmissionSegment, WidthEmissionsCtx};
 ///
 /// Finding width emissions the rope
+/// - Width of each column compared with the center and the spread of it's outlier detector
+/// - Emission columns separated by no more then `gap` frame columns are merged into the one [EmissionSegment],
+///   segments shorter then `min_length` frame columns are dropped as a noise,
+///   the columns without the edges (missing or occluded) are counted as well, so the emissions
+///   on the both sides of the occlusion aren't merged
+/// - Edge profiles measured perpendicular to the edge lines fitted by the robust estimator,
+///   so the tilted rope doesn't move the both edges
 pub struct WidthEmissions {
     dbg: Dbg,
     threshold: Threshold,
-    mad: Box<dyn Eval<Vec<usize>, MadCtx> + Send>,
-    ctx: Box<dyn Eval<(), EvalResult> + Send>,
+    min_length: usize,
+    gap: usize,
+    mad: Box<dyn Eval<Vec<f64>, OutlierCtx>>,
+    fit: Box<dyn Eval<Vec<Dot<f64>>, Line>>,
+    ctx: Box<dyn Eval<(), EvalResult>>,
 }
 //
 //
 impl WidthEmissions {
     ///
     /// New instance [WidthEmissions]
+    /// - `min_length` - minimal length of the segment, frame columns
+    /// - `gap` - maximal number of the frame columns without emission inside the segment
+    /// - `mad` - outlier detector of the edges and the widths, [OutlierDetector](crate::algorithm::OutlierDetector) for example
+    /// - `fit` - line estimator of the rope edges, [Ransac](crate::algorithm::Ransac) for example
     pub fn new(
         threshold: Threshold,
-        mad: impl Eval<Vec<usize>, MadCtx> + Send + 'static,
-        ctx: impl Eval<(), EvalResult> + Send + 'static,
+        min_length: usize,
+        gap: usize,
+        mad: impl Eval<Vec<f64>, OutlierCtx> + 'static,
+        fit: impl Eval<Vec<Dot<f64>>, Line> + 'static,
+        ctx: impl Eval<(), EvalResult> + 'static,
     ) -> Self {
         Self {
             dbg: Dbg::own("WidthEmissions"),
             threshold,
+            min_length,
+            gap,
             mad: Box::new(mad),
+            fit: Box::new(fit),
             ctx: Box::new(ctx),
         }
     }
     ///
     /// Compute width between initial dots
-    fn points_width(initial_points_upper: Vec<Dot<usize>>, initial_points_lower: Vec<Dot<usize>>) -> Vec<usize> {
-        let mut dots_width = Vec::new();
-        for i in 0..initial_points_upper.len() { // `for` only for one vector cause they must be same length
-            let width = initial_points_upper[i].y - initial_points_lower[i].y;
-            dots_width.push(width);
-        };
-        dots_width
+    /// - Edges are paired by the column, so `zip` keeps only the columns having both edges
+    fn points_width(initial_points_upper: Vec<Dot<f64>>, initial_points_lower: Vec<Dot<f64>>) -> Vec<f64> {
+        initial_points_upper.iter()
+            .zip(initial_points_lower.iter())
+            .map(|(upper, lower)| (upper.y - lower.y).abs())
+            .collect()
     }
     ///
-    /// Find emissions
-    fn emissions(
-        initial_points_upper: Vec<Dot<usize>>, 
-        initial_points_lower: Vec<Dot<usize>>, 
-        median: f64, 
-        mad: f64, 
-        threshold: f64
-    ) -> Vec<Bond<usize>> {
-        let mut emissions = Vec::new();
-        for i in 0..initial_points_upper.len() { // `for` only for one vector cause they must be same length
-            let deviation = ((initial_points_upper[i].y - initial_points_lower[i].y) as f64 - median).abs();
-            if deviation > threshold * mad {
-                emissions.push(
-                    Bond {
-                        x: initial_points_upper[i].x,
-                        y: initial_points_upper[i].y,
-                    }
-                );
-                emissions.push(
-                    Bond {
-                        x: initial_points_lower[i].x,
-                        y: initial_points_lower[i].y,
-                    }
-                );
+    /// Returns indexes of the columns, where the width deviates from it's center
+    fn emissions(widths: &[f64], outliers: &OutlierCtx, threshold: f64) -> Vec<usize> {
+        widths.iter()
+            .enumerate()
+            .filter(|(i, width)| {
+                let mad = outliers.at(*i);
+                (*width - mad.median).abs() > threshold * mad.mad
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+    ///
+    /// Returns runs `(first, last)` of the emission indexes, the `points` of which separated by no more then `gap` columns
+    fn runs(emissions: &[usize], points: &[Dot<f64>], gap: usize) -> Vec<(usize, usize)> {
+        let mut runs: Vec<(usize, usize)> = vec![];
+        for &index in emissions {
+            match runs.last_mut() {
+                Some(run) if points[index].x - points[run.1].x <= (gap + 1) as f64 => run.1 = index,
+                _ => runs.push((index, index)),
             }
+        }
+        runs
+    }
+    ///
+    /// Returns [EmissionSegment] of the edge points `first..=last`
+    /// - `distances` - distances of the upper and lower edge points from their fitted lines, px
+    fn segment(
+        &self,
+        (first, last): (usize, usize),
+        initial_points_upper: &[Dot<f64>],
+        (distances_upper, distances_lower): (&[f64], &[f64]),
+        widths: &[f64],
+        (outliers_upper, outliers_lower, outliers_widths): (&OutlierCtx, &OutlierCtx, &OutlierCtx),
+    ) -> EmissionSegment {
+        let upper: Vec<f64> = (first..=last).map(|i| distances_upper[i] - outliers_upper.at(i).median).collect();
+        let lower: Vec<f64> = (first..=last).map(|i| distances_lower[i] - outliers_lower.at(i).median).collect();
+        let deviations: Vec<f64> = (first..=last).map(|i| (widths[i] - outliers_widths.at(i).median).abs()).collect();
+        let (start, end) = (initial_points_upper[first].x, initial_points_upper[last].x);
+        let step = if last > first { (end - start) / (last - first) as f64 } else { 1.0 };
+        // Edge moved, if it's largest deviation exceeds the threshold of it's own spread
+        let moved = |deviations: &[f64], outliers: &OutlierCtx| {
+            deviations.iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                .is_some_and(|(i, deviation)| deviation.abs() > self.threshold.0 * outliers.at(first + i).mad)
         };
-        emissions
+        let side = match (moved(&upper, outliers_upper), moved(&lower, outliers_lower)) {
+            (true, false) => DefectSide::Upper,
+            (false, true) => DefectSide::Lower,
+            _ => DefectSide::Both,
+        };
+        EmissionSegment {
+            start: start as usize,
+            end: end as usize,
+            first,
+            last,
+            upper,
+            lower,
+            peak: deviations.iter().fold(0.0, |peak: f64, deviation| peak.max(*deviation)),
+            area: deviations.iter().sum::<f64>() * step,
+            side,
+        }
     }
 }
 //
@@ -88,21 +139,34 @@ impl Eval<(), EvalResult> for WidthEmissions {
                 let edge_detection_ctx = ContextRead::<EdgeDetectionCtx>::read(&ctx);
                 let initial_points_upper = edge_detection_ctx.result.get(Side::Upper);
                 let initial_points_lower = edge_detection_ctx.result.get(Side::Lower);
-                let mad_result = self.mad.eval(
-                    Self::points_width(
-                            initial_points_upper.clone(),
-                            initial_points_lower.clone(),
-                    )
+                let widths = Self::points_width(
+                    initial_points_upper.clone(),
+                    initial_points_lower.clone(),
                 );
+                let outliers_widths = self.mad.eval(widths.clone());
+                let upper_line = self.fit.eval(initial_points_upper.clone());
+                let lower_line = self.fit.eval(initial_points_lower.clone());
+                let distances_upper: Vec<f64> = initial_points_upper.iter().map(|dot| upper_line.distance(*dot)).collect();
+                let distances_lower: Vec<f64> = initial_points_lower.iter().map(|dot| lower_line.distance(*dot)).collect();
+                let outliers_upper = self.mad.eval(distances_upper.clone());
+                let outliers_lower = self.mad.eval(distances_lower.clone());
+                let emissions = Self::emissions(&widths, &outliers_widths, self.threshold.0);
                 let result = WidthEmissionsCtx {
-                    result: Self::emissions(
-                                initial_points_upper.clone(),
-                                initial_points_lower.clone(),
-                                mad_result.median,
-                                mad_result.mad,
-                                self.threshold.0
-                            ),
+                    result: Self::runs(&emissions, &initial_points_upper, self.gap)
+                        .into_iter()
+                        .filter(|(first, last)| {
+                            initial_points_upper[*last].x - initial_points_upper[*first].x + 1.0 >= self.min_length as f64
+                        })
+                        .map(|run| self.segment(
+                            run,
+                            &initial_points_upper,
+                            (&distances_upper, &distances_lower),
+                            &widths,
+                            (&outliers_upper, &outliers_lower, &outliers_widths),
+                        ))
+                        .collect(),
                 };
+                log::debug!("{}.eval | Emission segments: {}", self.dbg, result.result.len());
                 ctx.write(result)
             },
             Err(err) => Err(error.pass(err)),
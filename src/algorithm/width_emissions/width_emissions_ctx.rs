@@ -1,7 +1,8 @@
-use crate::algorithm::mad::Bond;
+use super::EmissionSegment;
 ///
 /// Store result of `WidthEmissions`
+/// - `result` - segments of the width emissions, ordered along the rope
 #[derive(Debug, Clone, Default)]
 pub struct WidthEmissionsCtx {
-    pub result: Vec<Bond<usize>>
-}
\ No newline at end of file
+    pub result: Vec<EmissionSegment>
+}
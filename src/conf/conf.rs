@@ -1,8 +1,10 @@
-use crate::conf::{FastScanConf, FineScanConf};
+use crate::{algorithm::DiscardConf, conf::{FastScanConf, FineScanConf}};
 
 ///
 /// Te application configuration
 pub struct Conf {
     pub fast_scan: FastScanConf,
     pub fine_scan: FineScanConf,
+    /// Rope construction and mechanism group, the discard criteria depend on
+    pub discard: DiscardConf,
 }
\ No newline at end of file
@@ -1,6 +1,41 @@
-use crate::algorithm::Threshold;
+use crate::algorithm::{
+    GrooveThreshold, IlluminationConf, MoundThreshold, OutlierDetector, Scale, Segmentation, Threshold, TrackerConf,
+    VibrationConf,
+};
 ///
 /// Configuration parameters for `FastScan` algorithms
 pub struct FastScanConf {
+    /// Pixel to millimetre calibration of the camera, restored from the `<camera>.scale.json`, measured in px only if None
+    pub scale: Option<Scale>,
+    /// Nominal diameter of the rope, mm
+    pub nominal_diameter: f64,
     pub geometry_defect_threshold: Threshold,
-}
\ No newline at end of file
+    /// Minimal length of the width emission segment, frame columns
+    pub emission_min_length: usize,
+    /// Maximal number of the columns without emission inside the width emission segment
+    pub emission_gap: usize,
+    /// Discard limit of the waviness `d1 / d`, `Waviness::RUNNING` or `Waviness::STATIONARY`
+    pub waviness_limit: f64,
+    pub groove_threshold: GrooveThreshold,
+    pub mound_threshold: MoundThreshold,
+    /// Rows added above and below the rope band, when the frame cropped to the rope working area, px
+    pub roi_margin: usize,
+    /// Brightness, contrast and gamma stabilisation of the frames
+    pub illumination: IlluminationConf,
+    /// Separation of the rope from the background
+    pub segmentation: Segmentation,
+    /// Outlier detector of the rope edges and widths
+    pub outlier_detector: OutlierDetector,
+    /// Allowed deviation of the frame diameter from the long-term baseline, part of the baseline diameter
+    pub baseline_limit: f64,
+    /// Weight of the external position feed, fused with the optical travel of the rope, 0.0...1.0
+    pub position_weight: f64,
+    /// Required overlap of the consecutive frames, part of the frame width, 0.0...1.0
+    pub coverage_overlap: f64,
+    /// Association of the defects of the consecutive frames into the events
+    pub defect_tracker: TrackerConf,
+    /// Window and limit of the lateral swing of the rope
+    pub vibration: VibrationConf,
+    /// Frames between the saves of the learned models and the panorama, so they survive the crash
+    pub persist_interval: usize,
+}
@@ -1 +1,8 @@
-pub struct FineScanConf {}
\ No newline at end of file
+use crate::algorithm::SurfaceThreshold;
+///
+/// Configuration parameters for `FineScan` algorithms
+pub struct FineScanConf {
+    /// Length of the rope band segment scored by `SurfaceDefect`, columns
+    pub surface_defect_segment: usize,
+    pub surface_defect_threshold: SurfaceThreshold,
+}
@@ -114,24 +114,15 @@ impl AcBuffer {
             opencv::core::Mat_AUTO_STEP,
         ) };
         match src {
-            Ok(src) => match self.pixel_format {
-                PixelFormat::BayerRG8 | PixelFormat::BayerBG8 | PixelFormat::BayerGB8 |
-                PixelFormat::BayerRG10 | PixelFormat::BayerGR10 | PixelFormat::BayerBG10 | PixelFormat::BayerGB10 |
-                PixelFormat::BayerRG12 | PixelFormat::BayerGR12 | PixelFormat::BayerBG12 | PixelFormat::BayerGB12 |
-                PixelFormat::BayerRG16 | PixelFormat::BayerGR16 | PixelFormat::BayerBG16 | PixelFormat::BayerGB16 |
-                PixelFormat::QoiBayerRG8 => {
-                    let mut dst = src.clone();
-                    match opencv::imgproc::cvt_color(
-                        &src, 
-                        &mut dst, 
-                        opencv::imgproc::COLOR_BayerRG2RGB,
-                        3,
-                    ) {
-                        Ok(_) => Ok(Image { width, height, timestamp: timestamp, mat: dst, bytes: len }),
-                        Err(err) => Err(error.pass_with("OpenCv COLOR_BayerRG2RGB conversion Error", err.to_string())),
+            Ok(src) => match self.pixel_format.demosaic() {
+                Some(code) => {
+                    let mut dst = opencv::core::Mat::default();
+                    match opencv::imgproc::cvt_color(&src, &mut dst, code, 3) {
+                        Ok(_) => Ok(Image { width, height, timestamp, mat: dst, bytes: len }),
+                        Err(err) => Err(error.pass_with(format!("OpenCv {:?} demosaicing Error", self.pixel_format), err.to_string())),
                     }
                 }
-                _ => Ok(Image { width, height, timestamp, mat: src, bytes: len })
+                None => Ok(Image { width, height, timestamp, mat: src, bytes: len })
             }
             Err(err) => Err(error.pass_with("Create OpenCv Mat Error", err.to_string())),
         }
@@ -176,4 +176,18 @@ impl PixelFormat {
             Self::QoiYCbCr8   => opencv::core::CV_8UC3,
         }
     }
+    ///
+    /// Returns the OpenCV code converting the Bayer mosaic into BGR, None if the format isn't a Bayer one
+    /// - OpenCV names the pattern by the second and the third pixels of the second row,
+    ///   so the GenICam BayerRG (RGGB) is the OpenCV BayerBG and so on
+    pub fn demosaic(&self) -> Option<i32> {
+        match self {
+            Self::BayerRG8 | Self::BayerRG10 | Self::BayerRG12 | Self::BayerRG16 |
+            Self::QoiBayerRG8 => Some(opencv::imgproc::COLOR_BayerBG2BGR),
+            Self::BayerGR8 | Self::BayerGR10 | Self::BayerGR12 | Self::BayerGR16 => Some(opencv::imgproc::COLOR_BayerGB2BGR),
+            Self::BayerBG8 | Self::BayerBG10 | Self::BayerBG12 | Self::BayerBG16 => Some(opencv::imgproc::COLOR_BayerRG2BGR),
+            Self::BayerGB8 | Self::BayerGB10 | Self::BayerGB12 | Self::BayerGB16 => Some(opencv::imgproc::COLOR_BayerGR2BGR),
+            _ => None,
+        }
+    }
 }
@@ -5,26 +5,83 @@ mod infrostructure;
 #[cfg(test)]
 mod test;
 //
+use std::sync::Arc;
 use debugging::session::debug_session::{Backtrace, DebugSession, LogLevel};
 use sal_core::dbg::Dbg;
 use crate::{
-    domain::Eval,
+    domain::{Eval, RwLock},
     infrostructure::camera::{Camera, CameraConf},
     conf::{Conf, FastScanConf, FineScanConf},
     algorithm::{
-        DetectingContoursCv, EdgeDetection, GeometryDefect, Initial, InitialCtx, Mad, Threshold,
+        Background, BackgroundModel, Baseline, BaselineModel, Brightness, CameraCalibration, DefectTracker, DetectingContoursCv,
+        Diameter, Discard, DiscardConf, EdgeDetection, EdgeTrack, EdgeTrackConf, EventTrack, GeometryDefect,
+        GrooveThreshold, Illumination, IlluminationConf, Initial, InitialCtx, Intrinsics, LayHistory, LayLength,
+        MoundThreshold, Orientation, OutlierDetector, Panorama, PanoramaStrip, Position, ProfileDefect, Ransac,
+        Roi, RoiBand, RopeAxis, RopeTrack, Scale, Segmentation, Speed, SurfaceDefect, SurfaceThreshold,
+        Threshold, TrackerConf, Undistort, UndistortMap, Vibration, VibrationConf, VibrationHistory, Waviness,
+        WidthEmissions,
     }
 };
 ///
 /// Application entry point
+/// - `calibrate <images dir> <corners per row> <corners per column> <square, mm>` - calibrates the lens distortion
+///   of the camera from `config.yaml` by the checkerboard images, see [calibrate]
 fn main() {
     DebugSession::init(LogLevel::Debug, Backtrace::Short);
     let dbg = Dbg::own("main");
     let path = "./config.yaml";
     let conf = CameraConf::read(&dbg, path);
+    let camera_name = conf.name.join();
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|arg| arg == "calibrate") {
+        calibrate(&dbg, &camera_name, &args[2..]);
+        return;
+    }
     let mut camera = Camera::new(conf);
     let recv = camera.stream();
     let handle = camera.read().unwrap();
+    let scale = Scale::read(Scale::path(".", &camera_name))
+        .inspect_err(|err| log::warn!("{} | Camera scale not restored, measured in px only, diameter and defect percent not measured: {}", dbg, err))
+        .ok();
+    let undistort = Intrinsics::read(Intrinsics::path(".", &camera_name))
+        .and_then(|intrinsics| UndistortMap::new(&intrinsics))
+        .inspect_err(|err| log::warn!("{} | Camera intrinsics not restored, lens distortion not corrected: {}", dbg, err))
+        .ok()
+        .map(Arc::new);
+    let roi_band = Arc::new(RwLock::new(RoiBand::default()));
+    let brightness = Arc::new(RwLock::new(Brightness::default()));
+    let background_path = BackgroundModel::path(".", &camera_name);
+    let background = Arc::new(RwLock::new(
+        BackgroundModel::read(&background_path).unwrap_or_else(|err| {
+            log::warn!("{} | Background model not restored: {}", dbg, err);
+            BackgroundModel::default()
+        }),
+    ));
+    let track = Arc::new(RwLock::new(RopeTrack::default()));
+    let edges = Arc::new(RwLock::new(EdgeTrack::new(EdgeTrackConf::default())));
+    let panorama = Arc::new(RwLock::new(PanoramaStrip::new(".", &camera_name, PanoramaStrip::WIDTH)));
+    let events = Arc::new(RwLock::new(EventTrack::default()));
+    let vibration = Arc::new(RwLock::new(VibrationHistory::default()));
+    let lay = Arc::new(RwLock::new(LayHistory::default()));
+    let baseline_path = BaselineModel::path(".", &camera_name);
+    let baseline = Arc::new(RwLock::new(
+        BaselineModel::read(&baseline_path).unwrap_or_else(|err| {
+            log::warn!("{} | Baseline model not restored: {}", dbg, err);
+            BaselineModel::default()
+        }),
+    ));
+    let persist = || {
+        if let Err(err) = background.read().write(&background_path) {
+            log::warn!("{} | Background model not saved: {}", dbg, err);
+        }
+        if let Err(err) = baseline.read().write(&baseline_path) {
+            log::warn!("{} | Baseline model not saved: {}", dbg, err);
+        }
+        if let Err(err) = panorama.read().flush() {
+            log::warn!("{} | Panorama not saved: {}", dbg, err);
+        }
+    };
+    let mut frames = 0usize;
     let window = "Retrived";
     if let Err(err) = opencv::highgui::named_window(window, opencv::highgui::WINDOW_NORMAL) {
         log::warn!("{}.stream | Create Window Error: {}", dbg, err);
@@ -40,23 +97,157 @@ fn main() {
         opencv::highgui::wait_key(1).unwrap();
         let conf = Conf {
             fast_scan: FastScanConf {
+                scale,
+                nominal_diameter: 20.0,
                 geometry_defect_threshold: Threshold::min(),
+                emission_min_length: 3,
+                emission_gap: 2,
+                waviness_limit: Waviness::RUNNING,
+                groove_threshold: GrooveThreshold::default(),
+                mound_threshold: MoundThreshold::default(),
+                roi_margin: 20,
+                illumination: IlluminationConf::default(),
+                segmentation: Segmentation::default(),
+                outlier_detector: OutlierDetector::default(),
+                baseline_limit: 0.05,
+                position_weight: 0.5,
+                coverage_overlap: 0.2,
+                defect_tracker: TrackerConf::default(),
+                vibration: VibrationConf::default(),
+                persist_interval: 1000,
+            },
+            fine_scan: FineScanConf {
+                surface_defect_segment: 50,
+                surface_defect_threshold: SurfaceThreshold::default(),
             },
-            fine_scan: FineScanConf {},
+            discard: DiscardConf::default(),
         };
-        let result = GeometryDefect::new(
-            conf.fast_scan.geometry_defect_threshold,
-            *Box::new(Mad::new()),
-            EdgeDetection::new(
-                DetectingContoursCv::new(
-                    Initial::new(
-                        InitialCtx::new(frame),
+        let result = Discard::new(
+            conf.discard,
+            conf.fast_scan.waviness_limit,
+            DefectTracker::new(
+                conf.fast_scan.defect_tracker,
+                conf.fast_scan.scale,
+                events.clone(),
+                GeometryDefect::new(
+                    conf.fast_scan.geometry_defect_threshold,
+                    conf.fast_scan.scale,
+                    &camera_name,
+                    conf.fast_scan.outlier_detector,
+                    Ransac::default(),
+                    WidthEmissions::new(
+                        conf.fast_scan.geometry_defect_threshold,
+                        conf.fast_scan.emission_min_length,
+                        conf.fast_scan.emission_gap,
+                        conf.fast_scan.outlier_detector,
+                        Ransac::default(),
+                        ProfileDefect::new(
+                            conf.fast_scan.groove_threshold,
+                            conf.fast_scan.mound_threshold,
+                            Baseline::new(
+                                conf.fast_scan.baseline_limit,
+                                baseline.clone(),
+                                SurfaceDefect::new(
+                                    conf.fine_scan.surface_defect_segment,
+                                    conf.fine_scan.surface_defect_threshold,
+                                    Diameter::new(
+                                        conf.fast_scan.scale,
+                                        conf.fast_scan.nominal_diameter,
+                                        Vibration::new(
+                                            conf.fast_scan.vibration,
+                                            conf.fast_scan.scale,
+                                            vibration.clone(),
+                                            LayLength::new(
+                                                conf.fast_scan.scale,
+                                                lay.clone(),
+                                                RopeAxis::new(
+                                                    conf.fast_scan.waviness_limit,
+                                                    EdgeDetection::new(
+                                                        Some(edges.clone()),
+                                                        conf.fast_scan.scale,
+                                                        Background::new(
+                                                            background.clone(),
+                                                            DetectingContoursCv::new(
+                                                                conf.fast_scan.segmentation,
+                                                                Panorama::new(
+                                                                    conf.fast_scan.scale,
+                                                                    panorama.clone(),
+                                                                    Speed::new(
+                                                                        conf.fast_scan.scale,
+                                                                        conf.fast_scan.coverage_overlap,
+                                                                        Position::new(
+                                                                            conf.fast_scan.scale,
+                                                                            None,
+                                                                            conf.fast_scan.position_weight,
+                                                                            track.clone(),
+                                                                            Roi::new(
+                                                                                conf.fast_scan.roi_margin,
+                                                                                roi_band.clone(),
+                                                                                Orientation::new(
+                                                                                    Illumination::new(
+                                                                                        conf.fast_scan.illumination,
+                                                                                        None,
+                                                                                        brightness.clone(),
+                                                                                        Undistort::new(
+                                                                                            undistort.clone(),
+                                                                                            Initial::new(
+                                                                                                InitialCtx::new(frame),
+                                                                                            ),
+                                                                                        ),
+                                                                                    ),
+                                                                                ),
+                                                                            ),
+                                                                        ),
+                                                                    ),
+                                                                ),
+                                                            ),
+                                                        ),
+                                                    ),
+                                                ),
+                                            ),
+                                        ),
+                                    ),
+                                ),
+                            ),
+                        ),
                     ),
                 ),
             ),
         )
         .eval(());
         _ = result;
+        frames += 1;
+        if frames % conf.fast_scan.persist_interval.max(1) == 0 {
+            persist();
+        }
+    }
+    persist();
+    for event in &events.read().events {
+        log::info!("{} | Defect {} still in view: {:?} {:?}, {:?}", dbg, event.id, event.kind, event.status, event.start);
     }
     handle.join().unwrap()
 }
+///
+/// Calibrates the lens distortion of the `camera` by the checkerboard images of the directory,
+/// writes the intrinsics into `<camera>.intrinsics.json` of the working directory, restored on the next start
+/// - `args` - `<images dir> <corners per row> <corners per column> <square, mm>`,
+///   number of the inner corners of the checkerboard, size of it's square
+fn calibrate(dbg: &Dbg, camera: &str, args: &[String]) {
+    let (Some(dir), Some(Ok(cols)), Some(Ok(rows)), Some(Ok(square))) = (
+        args.first(),
+        args.get(1).map(|arg| arg.parse::<usize>()),
+        args.get(2).map(|arg| arg.parse::<usize>()),
+        args.get(3).map(|arg| arg.parse::<f64>()),
+    ) else {
+        log::error!("{} | Usage: calibrate <images dir> <corners per row> <corners per column> <square, mm>", dbg);
+        return;
+    };
+    let path = Intrinsics::path(".", camera);
+    let result = CameraCalibration::images(dir)
+        .and_then(|images| CameraCalibration::new((cols, rows), square).eval(images))
+        .and_then(|intrinsics| intrinsics.write(&path).map(|_| intrinsics));
+    match result {
+        Ok(intrinsics) => log::info!("{} | Camera intrinsics written into {:?}, RMS error: {:.4} px", dbg, path, intrinsics.error),
+        Err(err) => log::error!("{} | Camera not calibrated: {}", dbg, err),
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+
+mod background {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, MatTrait, MatTraitConst, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Background, BackgroundCtx, BackgroundModel, Context, ContextRead, ContextWrite,
+            DetectingContoursCvCtx, EvalResult, InitialCtx, SpeedCtx,
+        },
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the contours image 40 x 60 of the frame `index`
+    /// - static structure - vertical line on the column 10
+    /// - rope edge - row 20 from the column 20, it's strands flickering frame by frame
+    /// - rope surface - rows 21..30, texture moving along the rope
+    fn contours(index: usize) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(40, 60, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..40 {
+            *mat.at_2d_mut::<u8>(row, 10).unwrap() = 200;
+        }
+        for col in 20..60 {
+            *mat.at_2d_mut::<u8>(20, col).unwrap() = if index % 2 == 0 { 200 } else { 60 };
+            for row in 21..30 {
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = ((col as usize + index) * 37 % 256) as u8;
+            }
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Returns the context with the contours image of the frame `index`, the rope `moving` or stopped
+    fn ctx_with(index: usize, moving: bool, mask: Image) -> MocEval {
+        let speed = match moving {
+            true => SpeedCtx { overlap: Some(0.9), ..Default::default() },
+            false => SpeedCtx { overlap: Some(1.0), stationary: true, ..Default::default() },
+        };
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(DetectingContoursCvCtx { result: contours(index), mask })
+                .unwrap()
+                .write(speed)
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns the context with the contours image of the frame `index`, the rope moving, not segmented
+    fn ctx(index: usize) -> MocEval {
+        ctx_with(index, true, Image::default())
+    }
+    ///
+    /// Testing `eval` learning the background over the frames
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("background");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BackgroundModel::default()));
+        // step, frame index, pixel, target gradient
+        let test_data = [
+            // Not learned yet, nothing suppressed
+            (1, 0, (5, 10), 200),
+            (2, 0, (20, 40), 200),
+            // Static line learned and suppressed
+            (3, 39, (5, 10), 0),
+            // Flickering rope edge kept
+            (4, 39, (20, 40), 60),
+        ];
+        let mut index = 0;
+        let mut result = Background::new(model.clone(), ctx(index)).eval(()).unwrap();
+        for (step, frame, (row, col), target) in test_data {
+            while index < frame {
+                index += 1;
+                result = Background::new(model.clone(), ctx(index)).eval(()).unwrap();
+            }
+            let contours = &ContextRead::<DetectingContoursCvCtx>::read(&result).result.mat;
+            let gradient = *contours.at_2d::<u8>(row, col).unwrap();
+            assert!(gradient == target, "step {} \nresult: {:?}\ntarget: {:?}", step, gradient, target);
+        }
+        let suppressed = ContextRead::<BackgroundCtx>::read(&result).suppressed;
+        assert!(suppressed == 40, "\nresult: {:?}\ntarget: {:?}", suppressed, 40);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the model isn't learned on the stopped rope and inside the rope mask
+    #[test]
+    fn learning() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("background_learning");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // Mask of the rope covering the static line
+        let mut mask = Mat::new_rows_cols_with_default(40, 60, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..40 {
+            *mask.at_2d_mut::<u8>(row, 10).unwrap() = 255;
+        }
+        // step, rope moving, mask of the rope, target learned
+        let test_data = [
+            (1, false, Image::default(), false),
+            (2, true, Image::with(mask), true),
+        ];
+        for (step, moving, mask, learned) in test_data {
+            let model = Arc::new(RwLock::new(BackgroundModel::default()));
+            let mut result = Background::new(model.clone(), ctx_with(0, moving, mask.clone())).eval(()).unwrap();
+            for index in 1..40 {
+                result = Background::new(model.clone(), ctx_with(index, moving, mask.clone())).eval(()).unwrap();
+            }
+            let background = *ContextRead::<BackgroundCtx>::read(&result);
+            assert!(background.learned == learned, "step {} \nresult: {:?}\ntarget: {:?}", step, background.learned, learned);
+            assert!(background.suppressed == 0, "step {} \nresult: {:?}\ntarget: {:?}", step, background.suppressed, 0);
+            let contours = &ContextRead::<DetectingContoursCvCtx>::read(&result).result.mat;
+            let gradient = *contours.at_2d::<u8>(5, 10).unwrap();
+            assert!(gradient == 200, "step {} \nresult: {:?}\ntarget: {:?}", step, gradient, 200);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the model restored from the file suppresses the background from the first frame
+    #[test]
+    fn persistence() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("background_persistence");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BackgroundModel::default()));
+        for index in 0..40 {
+            Background::new(model.clone(), ctx(index)).eval(()).unwrap();
+        }
+        let path = BackgroundModel::path(std::env::temp_dir(), "background-test");
+        model.read().write(&path).unwrap();
+        let restored = BackgroundModel::read(&path).unwrap();
+        assert!(restored == *model.read(), "\nrestored model differs from the saved one");
+        let result = Background::new(Arc::new(RwLock::new(restored)), ctx(0)).eval(()).unwrap();
+        let contours = &ContextRead::<DetectingContoursCvCtx>::read(&result).result.mat;
+        let gradient = *contours.at_2d::<u8>(5, 10).unwrap();
+        assert!(gradient == 0, "\nresult: {:?}\ntarget: {:?}", gradient, 0);
+        _ = std::fs::remove_file(&path);
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
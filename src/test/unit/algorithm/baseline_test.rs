@@ -0,0 +1,293 @@
+#[cfg(test)]
+
+mod baseline {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Baseline, BaselineCtx, BaselineModel, Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult,
+            GeometryDefectType, InitialCtx, InitialPoints, PositionCtx, VibrationCtx,
+        },
+        domain::{Dot, Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the context with the rope edges 20 columns long of the `width`
+    /// - both edges are jagged by 1 px, so the roughness is 0.5 px
+    fn ctx(width: f64) -> MocEval {
+        ctx_at(width, None, true)
+    }
+    ///
+    /// Returns the context with the rope edges of the `width` at the `position` along the rope, m,
+    /// the geometry `reliable` or distorted by the swing
+    fn ctx_at(width: f64, position: Option<f64>, reliable: bool) -> MocEval {
+        let upper = (0..20).map(|x| Dot { x: x as f64, y: 60.0 + (x % 2) as f64 }).collect();
+        let lower = (0..20).map(|x| Dot { x: x as f64, y: 60.0 - width + (x % 2) as f64 }).collect();
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(EdgeDetectionCtx { result: InitialPoints::new(upper, lower), ..Default::default() })
+                .unwrap()
+                .write(PositionCtx { position, ..Default::default() })
+                .unwrap()
+                .write(VibrationCtx { reliable, ..Default::default() })
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns the result of the frame
+    fn eval_frame(model: &Arc<RwLock<BaselineModel>>, ctx: MocEval) -> BaselineCtx {
+        let result = Baseline::new(0.05, model.clone(), ctx).eval(()).unwrap();
+        ContextRead::<BaselineCtx>::read(&result).clone()
+    }
+    ///
+    /// Testing `eval` comparing the frames with the baseline learned
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let limit = 0.05;
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        for _ in 0..BaselineModel::WARMUP {
+            let result = Baseline::new(limit, model.clone(), ctx(40.0)).eval(()).unwrap();
+            assert!(ContextRead::<BaselineCtx>::read(&result).learned, "warmup frame must be learned");
+        }
+        let state = model.read().clone();
+        assert!(state.ready(), "baseline must be ready after {} frames", BaselineModel::WARMUP);
+        assert!((state.diameter - 40.0).abs() < 1e-9, "\nresult: {:?}\ntarget: {:?}", state.diameter, 40.0);
+        assert!((state.roughness - 0.5).abs() < 1e-9, "\nresult: {:?}\ntarget: {:?}", state.roughness, 0.5);
+        // step, frame width, target defect (kind, peak, relative, confidence), target learned, target baseline
+        let test_data = [
+            // Necking of the whole frame, not visible by the median of the frame itself
+            (1, 36.0, Some((GeometryDefectType::Compressing, 4.0, 0.1, 0.5)), false, 40.0),
+            (2, 44.0, Some((GeometryDefectType::Expansion, 4.0, 0.1, 0.5)), false, 40.0),
+            // Within the limit, baseline slowly adapted
+            (3, 40.8, None, true, 40.008),
+            // No edges, nothing compared and learned
+            (4, f64::NAN, None, false, 40.008),
+        ];
+        for (step, width, target, learned, baseline) in test_data {
+            let ctx = match width.is_nan() {
+                true => MocEval {
+                    ctx: Context::new(InitialCtx::new(Image::default()))
+                        .write(EdgeDetectionCtx { result: InitialPoints::new(vec![], vec![]), ..Default::default() })
+                        .unwrap(),
+                },
+                false => ctx(width),
+            };
+            let result = Baseline::new(limit, model.clone(), ctx).eval(()).unwrap();
+            let result = ContextRead::<BaselineCtx>::read(&result).clone();
+            match (&result.result, target) {
+                (Some(defect), Some((kind, peak, relative, confidence))) => {
+                    assert!(defect.kind == kind, "step {} \nresult: {:?}\ntarget: {:?}", step, defect.kind, kind);
+                    assert!((defect.start, defect.end) == (0, 19), "step {} \nresult: {:?}\ntarget: {:?}", step, (defect.start, defect.end), (0, 19));
+                    assert!((defect.peak - peak).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, defect.peak, peak);
+                    assert!((defect.relative - relative).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, defect.relative, relative);
+                    assert!((defect.confidence - confidence).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, defect.confidence, confidence);
+                }
+                (None, None) => {}
+                (result, target) => panic!("step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+            }
+            assert!(result.learned == learned, "step {} \nresult: {:?}\ntarget: {:?}", step, result.learned, learned);
+            assert!((result.baseline - baseline).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result.baseline, baseline);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the defective frame rejected while warming up
+    #[test]
+    fn warmup() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline_warmup");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        for _ in 0..10 {
+            eval_frame(&model, ctx(40.0));
+        }
+        // Necking beyond the limit of the warm-up median
+        let result = eval_frame(&model, ctx(30.0));
+        assert!(!result.learned && result.result.is_none(), "\nresult: {:?}\ntarget: rejected, not reported", result);
+        assert!(model.read().rejected == 1, "\nresult: {:?}\ntarget: {:?}", model.read().rejected, 1);
+        for _ in 0..40 {
+            eval_frame(&model, ctx(40.0));
+        }
+        let state = model.read().clone();
+        assert!(state.ready() && state.frames == BaselineModel::WARMUP, "\nresult: {:?}\ntarget: {:?} frames", state.frames, BaselineModel::WARMUP);
+        assert!((state.diameter - 40.0).abs() < 1e-9, "\nresult: {:?}\ntarget: {:?}", state.diameter, 40.0);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the baseline learned again after the frames rejected in a row,
+    /// the unreliable frames compared but not learned and not counted
+    #[test]
+    fn relearn() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline_relearn");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        for _ in 0..BaselineModel::WARMUP {
+            eval_frame(&model, ctx(40.0));
+        }
+        // Swinging rope
+        let result = eval_frame(&model, ctx_at(40.8, None, false));
+        assert!(!result.learned && (result.baseline - 40.0).abs() < 1e-9, "\nresult: {:?}\ntarget: not learned, baseline 40", result);
+        let result = eval_frame(&model, ctx_at(36.0, None, false));
+        assert!(result.result.is_some() && model.read().rejected == 0, "\nresult: {:?}\ntarget: reported, not counted", result);
+        // Rope replaced by the thinner one
+        for _ in 1..BaselineModel::RELEARN {
+            let result = eval_frame(&model, ctx(30.0));
+            assert!(!result.learned && result.result.is_some(), "\nresult: {:?}\ntarget: rejected and reported", result);
+        }
+        let result = eval_frame(&model, ctx(30.0));
+        assert!(!result.learned && result.baseline == 0.0, "\nresult: {:?}\ntarget: baseline dropped", result);
+        assert!(!model.read().ready(), "\nresult: {:?}\ntarget: not ready", model.read().frames);
+        let result = eval_frame(&model, ctx(30.0));
+        assert!(result.learned && (result.baseline - 30.0).abs() < 1e-9, "\nresult: {:?}\ntarget: learned, baseline 30", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing the baseline never learned again on the defect of the stopped rope,
+    /// learned again on the frames rejected along several sections, the learned sections kept
+    #[test]
+    fn relearn_sections() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline_relearn_sections");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        for _ in 0..BaselineModel::WARMUP {
+            eval_frame(&model, ctx_at(40.0, Some(5.5), true));
+        }
+        // Rope stopped with the necked section in the view
+        for _ in 0..BaselineModel::RELEARN + 10 {
+            let result = eval_frame(&model, ctx_at(36.0, Some(5.5), true));
+            assert!(!result.learned && result.result.is_some(), "\nresult: {:?}\ntarget: rejected and reported", result);
+        }
+        assert!(model.read().ready(), "\nresult: {:?}\ntarget: ready", model.read().frames);
+        let result = eval_frame(&model, ctx_at(40.0, Some(5.5), true));
+        assert!(result.learned && (result.baseline - 40.0).abs() < 1e-9, "\nresult: {:?}\ntarget: learned, baseline 40", result);
+        // Zoom changed, the rope moving along the sections 20 to 24
+        for frame in 1..BaselineModel::RELEARN {
+            let result = eval_frame(&model, ctx_at(30.0, Some(20.0 + frame as f64 * 0.02), true));
+            assert!(!result.learned && result.result.is_some(), "\nresult: {:?}\ntarget: rejected and reported", result);
+        }
+        let result = eval_frame(&model, ctx_at(30.0, Some(25.0), true));
+        assert!(!result.learned && result.baseline == 0.0, "\nresult: {:?}\ntarget: baseline dropped", result);
+        assert!(!model.read().ready(), "\nresult: {:?}\ntarget: not ready", model.read().frames);
+        let section = model.read().sections.get(&5).copied();
+        assert!(
+            section.is_some_and(|section| (section.diameter - 40.0).abs() < 1e-9),
+            "\nresult: {:?}\ntarget: section 5 of 40 px kept", section,
+        );
+        test_duration.exit();
+    }
+    ///
+    /// Testing the frame compared with the baseline of the section of the rope
+    #[test]
+    fn sections() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline_sections");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        // Section 5 of 40 px, section 12 of 38.5 px, the whole rope about 39.4 px
+        for _ in 0..BaselineModel::WARMUP {
+            eval_frame(&model, ctx_at(40.0, Some(5.5), true));
+        }
+        for _ in 0..BaselineModel::WARMUP {
+            eval_frame(&model, ctx_at(38.5, Some(12.2), true));
+        }
+        // step, position, target baseline, target defect
+        let test_data = [
+            (1, Some(12.4), 38.5, Some(GeometryDefectType::Expansion)),
+            (2, Some(5.0), 40.0, None),
+            // Section not learned, compared with the whole rope
+            (3, Some(30.0), 39.4075, None),
+            (4, None, 39.4075, None),
+        ];
+        for (step, position, baseline, target) in test_data {
+            let result = eval_frame(&model, ctx_at(40.5, position, true));
+            let kind = result.result.as_ref().map(|defect| defect.kind.clone());
+            assert!(kind == target, "step {} \nresult: {:?}\ntarget: {:?}", step, kind, target);
+            // The frames within the limit slightly adapt the baseline
+            assert!((result.baseline - baseline).abs() < 0.05, "step {} \nresult: {:?}\ntarget: {:?}", step, result.baseline, baseline);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the baseline restored from the file is ready from the first frame
+    #[test]
+    fn persistence() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("baseline_persistence");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let model = Arc::new(RwLock::new(BaselineModel::default()));
+        for _ in 0..BaselineModel::WARMUP {
+            Baseline::new(0.05, model.clone(), ctx(40.0)).eval(()).unwrap();
+        }
+        let path = BaselineModel::path(std::env::temp_dir(), "baseline-test");
+        model.read().write(&path).unwrap();
+        let restored = BaselineModel::read(&path).unwrap();
+        assert!(restored == *model.read(), "\nrestored model differs from the saved one");
+        let result = Baseline::new(0.05, Arc::new(RwLock::new(restored)), ctx(36.0)).eval(()).unwrap();
+        let result = ContextRead::<BaselineCtx>::read(&result).clone();
+        assert!(result.result.is_some(), "\nresult: {:?}\ntarget: Compressing defect", result.result);
+        _ = std::fs::remove_file(&path);
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+
+mod defect_tracker {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, Defect, DefectEvent, DefectSide, DefectTracker, DefectTrackerCtx,
+            Direction, EvalResult, EventStatus, EventTrack, GeometryDefectCtx, GeometryDefectType, InitialCtx, PositionCtx,
+            PositionSource, Scale, TrackerConf, VibrationCtx,
+        },
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    /// Size of the pixel on the rope surface, mm
+    const MM_PER_PX: f64 = 0.5;
+    ///
+    /// Returns the context of the frame 100 px wide, travelled by `shift` px from the previous one,
+    /// to the `travel` px from the first, with the records (kind, start, end, relative)
+    fn ctx(timestamp: usize, shift: Option<f64>, travel: f64, defects: &[(GeometryDefectType, usize, usize, f64)]) -> MocEval {
+        let mat = Mat::new_rows_cols_with_default(10, 100, CV_8UC1, Scalar::all(0.0)).unwrap();
+        let position = Some(travel * MM_PER_PX / 1000.0);
+        let result = defects.iter().map(|(kind, start, end, relative)| {
+            let side = match kind {
+                GeometryDefectType::Pit => DefectSide::Upper,
+                _ => DefectSide::Both,
+            };
+            Defect::new(kind.clone(), side, *start, *end, relative * 40.0, *relative, 0.5)
+                .locate(timestamp, "test", None, 0.0)
+                .place(position, Direction::Forward)
+        }).collect();
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::with(mat)))
+                .write(PositionCtx { position, shift, source: PositionSource::Optical, ..Default::default() })
+                .unwrap()
+                .write(GeometryDefectCtx { result })
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns ids of the `events`
+    fn ids(events: &[DefectEvent]) -> Vec<usize> {
+        events.iter().map(|event| event.id).collect()
+    }
+    ///
+    /// Testing `eval` associating the records of the consecutive frames into the events
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("defect_tracker");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = TrackerConf { gap: 5, confirm: 3, patience: 2 };
+        let track = Arc::new(RwLock::new(EventTrack::default()));
+        let (exp, pit) = (GeometryDefectType::Expansion, GeometryDefectType::Pit);
+        // step, shift, travel, records, target (events, confirmed, closed, dropped)
+        let test_data = [
+            // Bulge entering the view, moving 10 px per frame
+            (1, None, 0.0, vec![(exp.clone(), 30, 40, 0.05)], (vec![0], vec![], vec![], vec![])),
+            (2, Some(10.0), 10.0, vec![(exp.clone(), 40, 50, 0.06)], (vec![0], vec![], vec![], vec![])),
+            // Observed on 3 frames, alarmed once
+            (3, Some(10.0), 20.0, vec![(exp.clone(), 50, 60, 0.08)], (vec![0], vec![0], vec![], vec![])),
+            // Single frame pit
+            (4, Some(10.0), 30.0, vec![(exp.clone(), 60, 70, 0.07), (pit.clone(), 10, 12, 0.02)], (vec![0, 1], vec![], vec![], vec![])),
+            // Fast travel, the bulge left the view
+            (5, Some(40.0), 70.0, vec![], (vec![1], vec![], vec![0], vec![])),
+            // Pit missed in the view
+            (6, Some(0.0), 70.0, vec![], (vec![1], vec![], vec![], vec![])),
+            (7, Some(0.0), 70.0, vec![], (vec![], vec![], vec![], vec![1])),
+        ];
+        let mut closed = vec![];
+        for (step, shift, travel, defects, (events, confirmed, closed_ids, dropped)) in test_data {
+            let ctx = ctx(step, shift, travel, &defects);
+            let result = DefectTracker::new(conf, Some(Scale::new(MM_PER_PX, None)), track.clone(), ctx).eval(()).unwrap();
+            let result = ContextRead::<DefectTrackerCtx>::read(&result).clone();
+            assert!(ids(&result.events) == events, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.events), events);
+            assert!(ids(&result.confirmed) == confirmed, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.confirmed), confirmed);
+            assert!(ids(&result.closed) == closed_ids, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.closed), closed_ids);
+            assert!(ids(&result.dropped) == dropped, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.dropped), dropped);
+            closed.extend(result.closed);
+        }
+        // The bulge reported once, over the whole length seen on the frames
+        let event = &closed[0];
+        assert!(event.status == EventStatus::Confirmed, "\nresult: {:?}\ntarget: {:?}", event.status, EventStatus::Confirmed);
+        assert!(event.observations == 4, "\nresult: {:?}\ntarget: {:?}", event.observations, 4);
+        assert!((event.first, event.last) == (30.0, 40.0), "\nresult: {:?}\ntarget: {:?}", (event.first, event.last), (30.0, 40.0));
+        let (start, end) = (event.start.unwrap(), event.end.unwrap());
+        assert!((start - 0.005).abs() < 1e-12 && (end - 0.010).abs() < 1e-12, "\nresult: {:?}\ntarget: {:?}", (start, end), (0.005, 0.010));
+        assert!((event.relative - 0.08).abs() < 1e-12, "\nresult: {:?}\ntarget: {:?}", event.relative, 0.08);
+        assert!(event.best.timestamp == 3, "\nresult: {:?}\ntarget: {:?}", event.best.timestamp, 3);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` skipping the records of the frames unreliable by the swing of the rope
+    #[test]
+    fn unreliable() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("defect_tracker_unreliable");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = TrackerConf { gap: 5, confirm: 3, patience: 2 };
+        let track = Arc::new(RwLock::new(EventTrack::default()));
+        let (exp, pit) = (GeometryDefectType::Expansion, GeometryDefectType::Pit);
+        // step, shift, travel, records, reliable, target (events, confirmed, observations of the bulge)
+        let test_data = [
+            (1, None, 0.0, vec![(exp.clone(), 30, 40, 0.05)], true, (vec![0], vec![], 1)),
+            // Swinging over more frames than the patience, neither observed nor missed
+            (2, Some(10.0), 10.0, vec![(exp.clone(), 40, 50, 0.05), (pit.clone(), 10, 12, 0.02)], false, (vec![0], vec![], 1)),
+            (3, Some(0.0), 10.0, vec![(exp.clone(), 40, 50, 0.05)], false, (vec![0], vec![], 1)),
+            (4, Some(0.0), 10.0, vec![], false, (vec![0], vec![], 1)),
+            // Swing damped
+            (5, Some(0.0), 10.0, vec![(exp.clone(), 40, 50, 0.05)], true, (vec![0], vec![], 2)),
+            (6, Some(0.0), 10.0, vec![(exp.clone(), 40, 50, 0.05)], true, (vec![0], vec![0], 3)),
+        ];
+        for (step, shift, travel, defects, reliable, (events, confirmed, observations)) in test_data {
+            let ctx = MocEval {
+                ctx: ctx(step, shift, travel, &defects).ctx.write(VibrationCtx { reliable, ..Default::default() }).unwrap(),
+            };
+            let result = DefectTracker::new(conf, Some(Scale::new(MM_PER_PX, None)), track.clone(), ctx).eval(()).unwrap();
+            let result = ContextRead::<DefectTrackerCtx>::read(&result).clone();
+            assert!(ids(&result.events) == events, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.events), events);
+            assert!(ids(&result.confirmed) == confirmed, "step {} \nresult: {:?}\ntarget: {:?}", step, ids(&result.confirmed), confirmed);
+            let result = result.events[0].observations;
+            assert!(result == observations, "step {} \nresult: {:?}\ntarget: {:?}", step, result, observations);
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
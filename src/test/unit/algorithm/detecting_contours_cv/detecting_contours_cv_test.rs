@@ -2,12 +2,12 @@
 
 mod detecting_contours_cv {
     use std::{sync::Once, time::Duration};
-    use opencv::{highgui, imgcodecs};
+    use opencv::{core::{Mat, MatTraitConst, Point, Scalar, CV_8UC3}, highgui, imgcodecs, imgproc};
     use sal_core::{dbg::Dbg, error::Error};
     use testing::stuff::max_test_duration::TestDuration;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
     use crate::{
-        algorithm::{Context, ContextRead, DetectingContoursCv, DetectingContoursCvCtx, InitialCtx},
+        algorithm::{Binarisation, Context, ContextRead, DetectingContoursCv, DetectingContoursCvCtx, InitialCtx, Segmentation},
         domain::{Eval, Image},
     };
     ///
@@ -47,7 +47,7 @@ mod detecting_contours_cv {
                 path,
                 imgcodecs::IMREAD_COLOR,
             ).unwrap();
-            let ctx = DetectingContoursCv::new(FakePassImg::new(Image::with(img))).eval(()).unwrap();
+            let ctx = DetectingContoursCv::new(Segmentation::default(), FakePassImg::new(Image::with(img))).eval(()).unwrap();
             let result: &DetectingContoursCvCtx = ctx.read();
             highgui::named_window("detected_contours_cv", highgui::WINDOW_NORMAL).unwrap();
             highgui::imshow("contours", &result.result.mat).unwrap();
@@ -57,6 +57,44 @@ mod detecting_contours_cv {
         test_duration.exit();
     }
     ///
+    /// Returns the frame 40 x 60 of the `background` brightness with the horizontal rope on the rows 15..25
+    fn rope(rope: f64, background: f64) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(40, 60, CV_8UC3, Scalar::all(background)).unwrap();
+        imgproc::rectangle_points(&mut mat, Point::new(0, 15), Point::new(59, 24), Scalar::all(rope), imgproc::FILLED, imgproc::LINE_8, 0).unwrap();
+        Image::with(mat)
+    }
+    ///
+    /// Testing the rope mask
+    #[test]
+    fn segment() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("detecting_contours_cv_segment");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let otsu = Segmentation { binarisation: Binarisation::Otsu, cleanup: 5 };
+        let hysteresis = Segmentation { binarisation: Binarisation::Hysteresis { low: 50.0, high: 150.0 }, cleanup: 5 };
+        let test_data = [
+            (1, otsu, rope(200.0, 30.0)),
+            // Dark rope on the bright background
+            (2, otsu, rope(30.0, 200.0)),
+            (3, hysteresis, rope(200.0, 30.0)),
+        ];
+        for (step, segmentation, image) in test_data {
+            let ctx = DetectingContoursCv::new(segmentation, FakePassImg::new(image)).eval(()).unwrap();
+            let result: &DetectingContoursCvCtx = ctx.read();
+            for (row, target) in [(2, 0), (20, 255), (37, 0)] {
+                for col in [5, 30, 55] {
+                    let value = *result.mask.mat.at_2d::<u8>(row, col).unwrap();
+                    assert!(value == target, "step {} row {} col {} \nresult: {:?}\ntarget: {:?}", step, row, col, value, target);
+                }
+            }
+        }
+        test_duration.exit();
+    }
+    ///
     /// Fake implements `Eval` for testing [EdgeDetection]
     struct FakePassImg {
         img: Image,
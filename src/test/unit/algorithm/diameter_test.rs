@@ -0,0 +1,157 @@
+#[cfg(test)]
+
+mod diameter {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, Diameter, DiameterCtx, EdgeDetectionCtx, EvalResult,
+            InitialCtx, InitialPoints, Scale,
+        },
+        domain::{Dot, Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("diameter");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (
+                1,
+                Some(Scale::new(0.5, None)),
+                5.0,
+                InitialPoints::new(
+                    vec![
+                        Dot { x: 10.0, y: 20.0 },
+                        Dot { x: 20.0, y: 21.0 },
+                        Dot { x: 30.0, y: 19.0 },
+                        Dot { x: 40.0, y: 20.0 },
+                    ],
+                    vec![
+                        Dot { x: 10.0, y: 30.0 },
+                        Dot { x: 20.0, y: 30.0 },
+                        Dot { x: 30.0, y: 30.0 },
+                        Dot { x: 40.0, y: 30.0 },
+                    ],
+                ),
+                DiameterCtx { mean: 5.0, min: 4.5, max: 5.5, nominal: 5.0, reduction: 10.0, expansion: 10.0, ..Default::default() },
+            ),
+            (
+                2,
+                Some(Scale::new(0.5, None)),
+                5.0,
+                InitialPoints::new(vec![], vec![]),
+                DiameterCtx { mean: 0.0, min: 0.0, max: 0.0, nominal: 5.0, reduction: 0.0, expansion: 0.0, ..Default::default() },
+            ),
+            (
+                3,
+                None,
+                5.0,
+                InitialPoints::new(vec![Dot { x: 10.0, y: 20.0 }], vec![Dot { x: 10.0, y: 30.0 }]),
+                DiameterCtx { mean: 0.0, min: 0.0, max: 0.0, nominal: 5.0, reduction: 0.0, expansion: 0.0, ..Default::default() },
+            ),
+        ];
+        for (step, scale, nominal, initial_points, target) in test_data {
+            let mut ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(
+                        Image::default()
+                    )
+                ),
+            };
+            ctx.ctx = ctx.ctx
+                .clone()
+                .write(EdgeDetectionCtx { result: initial_points, ..Default::default() })
+                .unwrap();
+            let result = Diameter::new(scale, nominal, ctx).eval(());
+            match result {
+                Ok(result) => {
+                    let result = ContextRead::<DiameterCtx>::read(&result).clone();
+                    assert!(
+                        result == target,
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        result,
+                        target
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing [Scale] calibration
+    #[test]
+    fn scale() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("diameter_scale");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, Scale::from_reference(10.0, 80.0, Some(200.0)).unwrap(), 0.125),
+            (2, Scale::from_reference(10.0, 80.0, Some(200.0)).unwrap().with_distance(250.0), 0.15625),
+            (3, Scale::from_reference(10.0, 80.0, None).unwrap().with_distance(250.0), 0.125),
+            (4, Scale::from_lens(12.0, 0.00345, 200.0), 0.00345 * 188.0 / 12.0),
+        ];
+        for (step, scale, target) in test_data {
+            let result = scale.mm_per_px;
+            assert!(
+                (result - target).abs() < 1e-9,
+                "step {} \nresult: {:?}\ntarget: {:?}",
+                step,
+                result,
+                target
+            );
+        }
+        // The reference not measured
+        for width in [0.0, -80.0, f64::NAN] {
+            let result = Scale::from_reference(10.0, width, None);
+            assert!(result.is_err(), "width {} \nresult: {:?}\ntarget: error", width, result);
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
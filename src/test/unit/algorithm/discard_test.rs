@@ -0,0 +1,245 @@
+#[cfg(test)]
+
+mod discard {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, Defect, DefectEvent, DefectSide, DefectTrackerCtx, DiameterCtx, Discard,
+            DiscardConf, DiscardCriteria, DiscardCtx, DiscardRule, EvalResult, EventStatus, GeometryDefectType,
+            InitialCtx, MechanismGroup, RopeAxisCtx, RopeCondition, RopeConstruction, RopeLay, Severity, SurfaceDefectCtx,
+            SurfaceSegment, VibrationCtx, Waviness,
+        },
+        domain::{Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `eval` of the criteria, steel core ordinary lay rope, 100 outer wires, M4
+    #[test]
+    fn criteria() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("discard_criteria");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        // step, condition, target severity, target rule, target number of findings
+        let test_data = [
+            (1, RopeCondition::default(), Severity::Serviceable, None, 0),
+            (2, RopeCondition { diameter_reduction: Some(4.0), ..Default::default() }, Severity::Monitor, Some(DiscardRule::DiameterReduction), 1),
+            (3, RopeCondition { diameter_reduction: Some(8.0), ..Default::default() }, Severity::Discard, Some(DiscardRule::DiameterReduction), 1),
+            (4, RopeCondition { broken_wires_6d: Some(4), ..Default::default() }, Severity::Discard, Some(DiscardRule::BrokenWires6d), 1),
+            (5, RopeCondition { broken_wires_30d: Some(4), ..Default::default() }, Severity::Monitor, Some(DiscardRule::BrokenWires30d), 1),
+            (6, RopeCondition { waviness: Some(1.06), ..Default::default() }, Severity::Monitor, Some(DiscardRule::Waviness), 1),
+            (7, RopeCondition { waviness: Some(1.12), ..Default::default() }, Severity::Discard, Some(DiscardRule::Waviness), 1),
+            (8, RopeCondition { deformations: vec![GeometryDefectType::Pit], ..Default::default() },
+                Severity::Monitor, Some(DiscardRule::Deformation(GeometryDefectType::Pit)), 1),
+            (9, RopeCondition { deformations: vec![GeometryDefectType::Pit, GeometryDefectType::Mound], ..Default::default() },
+                Severity::Monitor, Some(DiscardRule::Deformation(GeometryDefectType::Pit)), 2),
+            // The worst criterion cited
+            (10, RopeCondition { diameter_reduction: Some(4.0), corrosion: Some(0.9), ..Default::default() }, Severity::Discard, Some(DiscardRule::Corrosion), 2),
+            (11, RopeCondition { local_expansion: Some(3.0), ..Default::default() }, Severity::Monitor, Some(DiscardRule::LocalExpansion), 1),
+            (12, RopeCondition { local_compression: Some(6.0), ..Default::default() }, Severity::Discard, Some(DiscardRule::LocalCompression), 1),
+        ];
+        let criteria = DiscardCriteria::new(DiscardConf::default(), Waviness::RUNNING);
+        for (step, condition, severity, rule, findings) in test_data {
+            let result = criteria.eval(condition);
+            assert!(result.severity == severity, "step {} \nresult: {:?}\ntarget: {:?}", step, result.severity, severity);
+            let result_rule = result.rule.as_ref().map(|finding| finding.rule.clone());
+            assert!(result_rule == rule, "step {} \nresult: {:?}\ntarget: {:?}", step, result_rule, rule);
+            assert!(result.findings.len() == findings, "step {} \nresult: {:?}\ntarget: {:?}", step, result.findings, findings);
+        }
+        // Criteria without the measure not assessed
+        let result = criteria.eval(RopeCondition { diameter_reduction: Some(8.0), waviness: Some(1.0), ..Default::default() });
+        let target = vec![DiscardRule::BrokenWires6d, DiscardRule::BrokenWires30d, DiscardRule::Corrosion];
+        assert!(result.unassessed == target, "\nresult: {:?}\ntarget: {:?}", result.unassessed, target);
+        assert!(result.severity == Severity::Discard, "\nresult: {:?}\ntarget: {:?}", result.severity, Severity::Discard);
+        test_duration.exit();
+    }
+    ///
+    /// Testing discard numbers of the broken wires by the rope construction and the mechanism group
+    #[test]
+    fn broken_wires() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("discard_broken_wires");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        // step, construction, lay, group, outer wires, target on 6d and 30d
+        let test_data = [
+            (1, RopeConstruction::FibreCore, RopeLay::Ordinary, MechanismGroup::M4, 50, (2, 4)),
+            (2, RopeConstruction::SteelCore, RopeLay::Lang, MechanismGroup::M3, 120, (3, 5)),
+            (3, RopeConstruction::SteelCore, RopeLay::Ordinary, MechanismGroup::M6, 75, (6, 12)),
+            (4, RopeConstruction::FibreCore, RopeLay::Ordinary, MechanismGroup::M1, 400, (16, 32)),
+            (5, RopeConstruction::RotationResistant, RopeLay::Lang, MechanismGroup::M5, 100, (4, 8)),
+        ];
+        for (step, construction, lay, group, outer_wires, target) in test_data {
+            let conf = DiscardConf { construction, lay, group, outer_wires, ..Default::default() };
+            let result = conf.broken_wires();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Returns the event of the single record with the `status`
+    fn event(id: usize, defect: Defect, status: EventStatus) -> DefectEvent {
+        let (first, last) = (defect.start as f64, defect.end as f64);
+        DefectEvent { status, ..DefectEvent::new(id, defect, first, last, None, None) }
+    }
+    ///
+    /// Testing `eval` of the verdict by the confirmed defect events
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("discard");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let hill = Defect::new(GeometryDefectType::Hill, DefectSide::Upper, 10, 20, 3.0, 0.03, 0.5);
+        // 6 % and 3 % of the nominal 20 mm
+        let expansion = Defect { percent: Some(6.0), ..Defect::new(GeometryDefectType::Expansion, DefectSide::Both, 40, 60, 6.0, 0.06, 0.5) };
+        let compressing = Defect { percent: Some(3.0), ..Defect::new(GeometryDefectType::Compressing, DefectSide::Both, 40, 60, 3.0, 0.03, 0.5) };
+        // step, events in the view, events closed on the frame, target severity, target rule
+        let test_data = [
+            (1, vec![], vec![], Severity::Serviceable, None),
+            (
+                2,
+                vec![event(0, hill.clone(), EventStatus::Confirmed), event(1, expansion.clone(), EventStatus::Confirmed)],
+                vec![],
+                Severity::Discard,
+                Some(DiscardRule::LocalExpansion),
+            ),
+            // Closed on the frame, graded last time
+            (3, vec![], vec![event(2, compressing, EventStatus::Confirmed)], Severity::Monitor, Some(DiscardRule::LocalCompression)),
+            // Not confirmed yet, may be the false positive
+            (4, vec![event(3, expansion, EventStatus::Tentative)], vec![], Severity::Serviceable, None),
+            (5, vec![event(4, hill, EventStatus::Tentative)], vec![], Severity::Serviceable, None),
+        ];
+        for (step, events, closed, severity, rule) in test_data {
+            let ctx = MocEval {
+                ctx: Context::new(InitialCtx::new(Image::default()))
+                    .write(DiameterCtx { mean: 20.0, min: 20.0, max: 20.0, nominal: 20.0, ..Default::default() })
+                    .unwrap()
+                    .write(DefectTrackerCtx { events, closed, ..Default::default() })
+                    .unwrap(),
+            };
+            let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+            let result = ContextRead::<DiscardCtx>::read(&result).clone();
+            assert!(result.severity == severity, "step {} \nresult: {:?}\ntarget: {:?}", step, result.severity, severity);
+            let result_rule = result.rule.map(|finding| finding.rule);
+            assert!(result_rule == rule, "step {} \nresult: {:?}\ntarget: {:?}", step, result_rule, rule);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` reporting the criteria of the missing stages as not assessed
+    #[test]
+    fn unassessed() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("discard_unassessed");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        // Neither `Diameter`, nor `RopeAxis`, nor `SurfaceDefect` evaluated
+        let ctx = MocEval { ctx: Context::new(InitialCtx::new(Image::default())) };
+        let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+        let result = ContextRead::<DiscardCtx>::read(&result).clone();
+        let target = vec![
+            DiscardRule::DiameterReduction, DiscardRule::BrokenWires6d, DiscardRule::BrokenWires30d,
+            DiscardRule::LocalExpansion, DiscardRule::LocalCompression, DiscardRule::Corrosion, DiscardRule::Waviness,
+        ];
+        assert!(result.unassessed == target, "\nresult: {:?}\ntarget: {:?}", result.unassessed, target);
+        // Diameter 18 mm of the nominal 20 mm, reduced by 10 %, the surface scored
+        let ctx = MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(DiameterCtx { mean: 18.0, min: 18.0, max: 18.0, nominal: 20.0, ..Default::default() })
+                .unwrap()
+                .write(SurfaceDefectCtx {
+                    segments: vec![SurfaceSegment { start: 0, end: 49, corrosion: 0.2, wear: 0.0, kind: None }],
+                    result: vec![],
+                })
+                .unwrap(),
+        };
+        let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+        let result = ContextRead::<DiscardCtx>::read(&result).clone();
+        let target = vec![DiscardRule::BrokenWires6d, DiscardRule::BrokenWires30d, DiscardRule::Waviness];
+        assert!(result.unassessed == target, "\nresult: {:?}\ntarget: {:?}", result.unassessed, target);
+        let rule = result.rule.map(|finding| finding.rule);
+        assert!(rule == Some(DiscardRule::DiameterReduction), "\nresult: {:?}\ntarget: {:?}", rule, DiscardRule::DiameterReduction);
+        assert!(result.severity == Severity::Discard, "\nresult: {:?}\ntarget: {:?}", result.severity, Severity::Discard);
+        // The same frame distorted by the swing of the rope
+        let ctx = MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(DiameterCtx { mean: 18.0, min: 18.0, max: 18.0, nominal: 20.0, ..Default::default() })
+                .unwrap()
+                .write(VibrationCtx { reliable: false, ..Default::default() })
+                .unwrap(),
+        };
+        let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+        let result = ContextRead::<DiscardCtx>::read(&result).clone();
+        assert!(result.unassessed.contains(&DiscardRule::DiameterReduction), "\nresult: {:?}\ntarget: DiameterReduction not assessed", result.unassessed);
+        assert!(result.severity == Severity::Serviceable, "\nresult: {:?}\ntarget: {:?}", result.severity, Severity::Serviceable);
+        // The expansion of 6 % confirmed, but the camera scale unknown, the rope measured in px only
+        let expansion = Defect::new(GeometryDefectType::Expansion, DefectSide::Both, 40, 60, 6.0, 0.06, 0.5);
+        let ctx = MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(DefectTrackerCtx { events: vec![event(0, expansion, EventStatus::Confirmed)], ..Default::default() })
+                .unwrap(),
+        };
+        let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+        let result = ContextRead::<DiscardCtx>::read(&result).clone();
+        assert!(result.unassessed.contains(&DiscardRule::LocalExpansion), "\nresult: {:?}\ntarget: LocalExpansion not assessed", result.unassessed);
+        assert!(result.severity == Severity::Serviceable, "\nresult: {:?}\ntarget: {:?}", result.severity, Severity::Serviceable);
+        // The rope wavy beyond the limit, but no full wave in the view yet
+        let ctx = MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(RopeAxisCtx { waviness: Waviness { amplitude: 2.0, wavelength: 0.0, ratio: 1.2 }, ..Default::default() })
+                .unwrap(),
+        };
+        let result = Discard::new(DiscardConf::default(), Waviness::RUNNING, ctx).eval(()).unwrap();
+        let result = ContextRead::<DiscardCtx>::read(&result).clone();
+        assert!(result.unassessed.contains(&DiscardRule::Waviness), "\nresult: {:?}\ntarget: Waviness not assessed", result.unassessed);
+        assert!(result.severity == Severity::Serviceable, "\nresult: {:?}\ntarget: {:?}", result.severity, Severity::Serviceable);
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
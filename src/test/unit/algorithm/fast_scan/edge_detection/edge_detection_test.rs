@@ -1,12 +1,12 @@
 #[cfg(test)]
 
 mod edge_detection_test {
-    use std::{sync::Once, time::Duration};
-    use opencv::{core::{Mat, MatTrait, Vec3b}, highgui, imgcodecs, imgproc};
+    use std::{sync::{Arc, Once}, time::Duration};
+    use opencv::{core::{Mat, MatTrait, Scalar, Vec3b, CV_8UC1}, highgui, imgcodecs, imgproc};
     use sal_core::{dbg::Dbg, error::Error};
     use testing::stuff::max_test_duration::TestDuration;
     use debugging::session::debug_session::{DebugSession, LogLevel, Backtrace};
-    use crate::{algorithm::{Context, ContextRead, EdgeDetection, EdgeDetectionCtx, InitialCtx, InitialPoints, Side}, domain::{Dot, Eval, Image}};
+    use crate::{algorithm::{Context, ContextRead, ContextWrite, DetectingContoursCvCtx, EdgeDetection, EdgeDetectionCtx, EdgeState, EdgeStrength, EdgeTrack, EdgeTrackConf, InitialCtx, InitialPoints, PositionCtx, Side}, domain::{Dot, Eval, Image, RwLock}};
     ///
     ///
     static INIT: Once = Once::new();
@@ -30,23 +30,23 @@ mod edge_detection_test {
             path,
             imgcodecs::IMREAD_GRAYSCALE,
         ).unwrap();
-        let ctx = EdgeDetection::new(FakePassImg::new(Image::with(img.clone()))).eval(()).unwrap();
+        let ctx = EdgeDetection::new(None, None, FakePassImg::new(Image::with(img.clone()))).eval(()).unwrap();
         let edges: &EdgeDetectionCtx = ctx.read();
         let mut img_of_edges = imgcodecs::imread(
             path,
             imgcodecs::IMREAD_COLOR,
         ).unwrap();
         for dot in edges.result.get(Side::Upper) {
-            if dot.x >= 0 && dot.y >= 0 {
-                let x = dot.x as i32;
-                let y = dot.y as i32;
+            if dot.x >= 0.0 && dot.y >= 0.0 {
+                let x = dot.x.round() as i32;
+                let y = dot.y.round() as i32;
                 *img_of_edges.at_2d_mut::<Vec3b>(y, x).unwrap() = Vec3b::from_array([0, 0, 255]);
             }
         }
         for dot in edges.result.get(Side::Lower) {
-            if dot.x >= 0 && dot.y >= 0 {
-                let x = dot.x as i32;
-                let y = dot.y as i32;
+            if dot.x >= 0.0 && dot.y >= 0.0 {
+                let x = dot.x.round() as i32;
+                let y = dot.y.round() as i32;
                 *img_of_edges.at_2d_mut::<Vec3b>(y, x).unwrap() = Vec3b::from_array([0, 255, 0]);
             }
         }
@@ -64,19 +64,19 @@ mod edge_detection_test {
         let img = Mat::from_slice_2d(&matrix).unwrap();
         let mut img_of_edges = Mat::default();
         imgproc::cvt_color(&img, &mut img_of_edges, imgproc::COLOR_GRAY2BGR, 0).unwrap();
-        let ctx = EdgeDetection::new(FakePassImg::new(Image::with(img))).eval(()).unwrap();
+        let ctx = EdgeDetection::new(None, None, FakePassImg::new(Image::with(img))).eval(()).unwrap();
         let edges: &EdgeDetectionCtx = ctx.read();
         for dot in edges.result.get(Side::Upper) {
-            if dot.x >= 0 && dot.y >= 0 {
-                let x = dot.x as i32;
-                let y = dot.y as i32;
+            if dot.x >= 0.0 && dot.y >= 0.0 {
+                let x = dot.x.round() as i32;
+                let y = dot.y.round() as i32;
                 *img_of_edges.at_2d_mut::<Vec3b>(y, x).unwrap() = Vec3b::from_array([0, 0, 255]);
             }
         }
         for dot in edges.result.get(Side::Lower) {
-            if dot.x >= 0 && dot.y >= 0 {
-                let x = dot.x as i32;
-                let y = dot.y as i32;
+            if dot.x >= 0.0 && dot.y >= 0.0 {
+                let x = dot.x.round() as i32;
+                let y = dot.y.round() as i32;
                 *img_of_edges.at_2d_mut::<Vec3b>(y, x).unwrap() = Vec3b::from_array([0, 255, 0]);
             }
         }
@@ -99,34 +99,94 @@ mod edge_detection_test {
         log::debug!("\n{}", dbg);
         let test_duration = TestDuration::new(dbg, Duration::from_secs(100));
         test_duration.run().unwrap();
-        fn into_dots(dots: &[usize]) -> Vec<Dot<usize>> {
-            dots.chunks(2).map(|d| d.into()).collect()
+        fn into_dots(dots: &[f64]) -> Vec<Dot<f64>> {
+            dots.chunks(2).map(|d| Dot { x: d[0], y: d[1] }).collect()
         }
-        let test_data: [(i32, Image, Result<EdgeDetectionCtx, Error>); 2] = [
+        let test_data: [(i32, Image, Image, Result<EdgeDetectionCtx, Error>); 5] = [
             (
                 1,
                 Image::with( Mat::from_slice_2d(&MATRIX1).unwrap()),
+                Image::default(),
                 Ok(EdgeDetectionCtx {
                     result: InitialPoints::new(
-                        into_dots(&[0,1, 1,0, 2,0, 3,1, 4,0, 5,0]),
-                        into_dots(&[0,5, 1,4, 2,5, 3,5, 4,5, 5,4]),
-                    )
+                        into_dots(&[0.,1., 1.,0., 2.,0., 3.,1., 4.,0., 5.,0.]),
+                        into_dots(&[0.,5., 1.,4., 2.,5., 3.,5., 4.,5., 5.,4.]),
+                    ),
+                    strength: vec![EdgeStrength::new(1.0, 1.0); 6],
+                    states: vec![EdgeState::Found; 6],
+                    ..Default::default()
                 }),
             ),
             (
                 2,
                 Image::with( Mat::from_slice_2d(&MATRIX2).unwrap()),
+                Image::default(),
                 Ok(EdgeDetectionCtx {
                     result: InitialPoints::new(
-                        into_dots(&[0,2, 1,1, 2,0, 3,1, 4,0, 5,1]),
-                        into_dots(&[0,3, 1,4, 2,4, 3,5, 4,4, 5,3]),
-                    )
+                        into_dots(&[0.,2., 1.,1., 2.,0., 3.,1., 4.,0., 5.,1.]),
+                        into_dots(&[0.,3., 1.,4., 2.,4., 3.,5., 4.,4., 5.,3.]),
+                    ),
+                    strength: vec![EdgeStrength::new(1.0, 1.0); 6],
+                    states: vec![EdgeState::Found; 6],
+                    ..Default::default()
                 }),
-            )
+            ),
+            (
+                3,
+                Image::with( Mat::from_slice_2d(&MATRIX3).unwrap()),
+                Image::default(),
+                Ok(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        into_dots(&[1.,1., 2.,1., 3.,1., 4.,1.]),
+                        into_dots(&[1.,4., 2.,4., 3.,4., 4.,4.]),
+                    ),
+                    strength: vec![EdgeStrength::new(1.0, 1.0); 4],
+                    states: vec![
+                        EdgeState::Missing,
+                        EdgeState::Found,
+                        EdgeState::Found,
+                        EdgeState::Found,
+                        EdgeState::Found,
+                        EdgeState::Occluded,
+                    ],
+                    ..Default::default()
+                }),
+            ),
+            (
+                4,
+                Image::with( Mat::from_slice_2d(&MATRIX4).unwrap()),
+                Image::default(),
+                Ok(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        into_dots(&[0.,2.25, 1.,2.25, 2.,2.25, 3.,2.25, 4.,2.25, 5.,2.25]),
+                        into_dots(&[0.,3.75, 1.,3.75, 2.,3.75, 3.,3.75, 4.,3.75, 5.,3.75]),
+                    ),
+                    strength: vec![EdgeStrength::new(40.0, 40.0); 6],
+                    states: vec![EdgeState::Found; 6],
+                    ..Default::default()
+                }),
+            ),
+            // Rope boundaries from the mask, the noise above the rope ignored
+            (
+                5,
+                Image::with( Mat::from_slice_2d(&MATRIX5).unwrap()),
+                Image::with( Mat::from_slice_2d(&MASK5).unwrap()),
+                Ok(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        into_dots(&[0.,2.75, 1.,2.75, 2.,2.75, 3.,2.75, 4.,2.75, 5.,2.75]),
+                        into_dots(&[0.,5.75, 1.,5.75, 2.,5.75, 3.,5.75, 4.,5.75, 5.,5.75]),
+                    ),
+                    strength: vec![EdgeStrength::new(60.0, 60.0); 6],
+                    states: vec![EdgeState::Found; 6],
+                    ..Default::default()
+                }),
+            ),
         ];
-        for (step, img, target) in test_data {
+        for (step, img, mask, target) in test_data {
             let result = EdgeDetection::new(
-                FakePassImg::new(img)
+                None,
+                None,
+                FakePassImg::masked(img, mask),
             )
             .eval(())
             .map(|ctx| {
@@ -135,6 +195,8 @@ mod edge_detection_test {
             });
             match (result, target) {
                 (Ok(result), Ok(target)) => {
+                    // Not tracked, the measured edges passed as the filtered ones
+                    let target = EdgeDetectionCtx { filtered: target.result.clone(), ..target };
                     assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
                 }
                 (Ok(result), Err(target)) => panic!("step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
@@ -162,28 +224,218 @@ mod edge_detection_test {
             [0, 1, 1, 1, 1, 0],
             [0, 0, 0, 1, 0, 0],
         ];
+        static MATRIX3: [[u8; 6]; 6] = [
+            [0, 0, 0, 0, 0, 0],
+            [0, 1, 1, 1, 1, 1],
+            [0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0],
+            [0, 1, 1, 1, 1, 0],
+            [0, 0, 0, 0, 0, 0],
+        ];
+        static MATRIX4: [[u8; 6]; 6] = [
+            [0, 0, 0, 0, 0, 0],
+            [10, 10, 10, 10, 10, 10],
+            [40, 40, 40, 40, 40, 40],
+            [30, 30, 30, 30, 30, 30],
+            [40, 40, 40, 40, 40, 40],
+            [10, 10, 10, 10, 10, 10],
+        ];
+        static MATRIX5: [[u8; 6]; 8] = [
+            [20, 20, 20, 20, 20, 20],
+            [0, 0, 0, 0, 0, 0],
+            [40, 40, 40, 40, 40, 40],
+            [60, 60, 60, 60, 60, 60],
+            [0, 0, 0, 0, 0, 0],
+            [40, 40, 40, 40, 40, 40],
+            [60, 60, 60, 60, 60, 60],
+            [0, 0, 0, 0, 0, 0],
+        ];
+        static MASK5: [[u8; 6]; 8] = [
+            [255, 255, 255, 255, 255, 255],
+            [0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0],
+            [255, 255, 255, 255, 255, 255],
+            [255, 255, 255, 255, 255, 255],
+            [255, 255, 255, 255, 255, 255],
+            [0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0],
+        ];
+    }
+    ///
+    /// Returns the contour image 20 x 4 with the rope edges at the `upper` and `lower` rows and the `noise` rows
+    fn contours(upper: i32, lower: i32, noise: &[i32]) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(20, 4, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in [upper, lower].iter().chain(noise) {
+            for col in 0..4 {
+                *mat.at_2d_mut::<u8>(*row, col).unwrap() = 255;
+            }
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Testing EdgeDetection.eval with the edges tracked over the frames
+    #[test]
+    fn edge_tracking() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("edge_tracking");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let track = Arc::new(RwLock::new(EdgeTrack::new(EdgeTrackConf { alpha: 0.5, beta: 0.1, window: 2.0, patience: 3 })));
+        // step, image, target upper and lower edges, target filtered upper and lower edges, target untracked upper and lower edges
+        let test_data = [
+            // First frame scanned whole, the track started
+            (1, contours(5, 14, &[]), (5.0, 14.0), (5.0, 14.0), (5.0, 14.0)),
+            // Noise above the rope outside of the window, the edges measured passed as is, the filtered ones half way
+            (2, contours(6, 15, &[1]), (6.0, 15.0), (5.5, 14.5), (1.0, 15.0)),
+            // Rope jumped outside of the window, scanned whole, the track started again
+            (3, contours(12, 18, &[]), (12.0, 18.0), (12.0, 18.0), (12.0, 18.0)),
+        ];
+        for (step, image, (upper, lower), (filtered_upper, filtered_lower), (untracked_upper, untracked_lower)) in test_data {
+            let result = EdgeDetection::new(Some(track.clone()), None, FakePassImg::new(image.clone())).eval(()).unwrap();
+            let result: &EdgeDetectionCtx = result.read();
+            let target = EdgeDetectionCtx {
+                result: InitialPoints::new(
+                    (0..4).map(|x| Dot { x: x as f64, y: upper }).collect(),
+                    (0..4).map(|x| Dot { x: x as f64, y: lower }).collect(),
+                ),
+                filtered: InitialPoints::new(
+                    (0..4).map(|x| Dot { x: x as f64, y: filtered_upper }).collect(),
+                    (0..4).map(|x| Dot { x: x as f64, y: filtered_lower }).collect(),
+                ),
+                strength: vec![EdgeStrength::new(255.0, 255.0); 4],
+                states: vec![EdgeState::Found; 4],
+            };
+            assert!(*result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+            let result = EdgeDetection::new(None, None, FakePassImg::new(image)).eval(()).unwrap();
+            let result: &EdgeDetectionCtx = result.read();
+            let target = (untracked_upper, untracked_lower);
+            let result = (result.result.get(Side::Upper)[0].y, result.result.get(Side::Lower)[0].y);
+            assert!(result == target, "step {} untracked \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing EdgeDetection.eval with the bulge travelling along the tracked columns, the peak of it must be kept
+    #[test]
+    fn edge_tracking_defect() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("edge_tracking_defect");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let track = Arc::new(RwLock::new(EdgeTrack::new(EdgeTrackConf::default())));
+        // Rope travelling 2 columns per frame, the bulge 3 columns wide raises the upper edge by 3 px
+        let (shift, width) = (2, 3);
+        for step in 0..6 {
+            let first = 2 + step * shift;
+            let image = bulge(first, width);
+            let position = PositionCtx { shift: (step > 0).then_some(shift as f64), ..Default::default() };
+            let result = EdgeDetection::new(Some(track.clone()), None, FakePassImg::new(image).with(position)).eval(()).unwrap();
+            let result: &EdgeDetectionCtx = result.read();
+            let count = result.result.get(Side::Upper).len();
+            assert!(count == 16, "step {} \nresult: {:?}\ntarget: {:?}", step, count, 16);
+            for dot in result.result.get(Side::Upper) {
+                let target = match (first..first + width).contains(&(dot.x as usize)) {
+                    true => 2.0,
+                    false => 5.0,
+                };
+                assert!(dot.y == target, "step {}, column {} \nresult: {:?}\ntarget: {:?}", step, dot.x, dot.y, target);
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing EdgeDetection.eval with the edges jittering by the noise over the frames,
+    /// the filtered edges must change from frame to frame less then the measured ones
+    #[test]
+    fn edge_tracking_jitter() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("edge_tracking_jitter");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let track = Arc::new(RwLock::new(EdgeTrack::new(EdgeTrackConf::default())));
+        // Rope edges at the 5 and 14 rows, jittering by up to 2 px
+        let upper_noise = [0, 2, -1, 1, -2, 0, 2, -2, 1, -1, 0, 2, -1, -2, 1, 0, 2, -1, 1, -2];
+        let lower_noise = [1, -1, 0, -2, 2, -1, 1, 0, -2, 2, 1, -1, 2, 0, -2, 1, -1, 0, 2, -1];
+        let (mut measured, mut filtered) = (vec![], vec![]);
+        for (upper, lower) in upper_noise.iter().zip(lower_noise) {
+            let image = contours(5 + upper, 14 + lower, &[]);
+            let result = EdgeDetection::new(Some(track.clone()), None, FakePassImg::new(image)).eval(()).unwrap();
+            let result: &EdgeDetectionCtx = result.read();
+            measured.push([result.result.get(Side::Upper)[0].y, result.result.get(Side::Lower)[0].y]);
+            filtered.push([result.filtered.get(Side::Upper)[0].y, result.filtered.get(Side::Lower)[0].y]);
+        }
+        // Variance of the frame to frame change of the edge
+        let variance = |edges: &[[f64; 2]], side: usize| {
+            let changes: Vec<f64> = edges.windows(2).map(|pair| pair[1][side] - pair[0][side]).collect();
+            let mean = changes.iter().sum::<f64>() / changes.len() as f64;
+            changes.iter().map(|change| (change - mean).powi(2)).sum::<f64>() / changes.len() as f64
+        };
+        for (side, name) in [(0, "upper"), (1, "lower")] {
+            let (measured, filtered) = (variance(&measured, side), variance(&filtered, side));
+            // About 6.0 px² measured vs 0.8 px² filtered
+            assert!(filtered < 0.5 * measured, "{} edge \nresult: {:?}\ntarget: less then half of {:?}", name, filtered, measured);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Returns the contour image 20 x 16 with the rope edges at the 5 and 14 rows,
+    /// the upper edge raised to the row 2 at `width` columns from the `first` one
+    fn bulge(first: usize, width: usize) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(20, 16, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for col in 0..16 {
+            let upper = match (first..first + width).contains(&col) {
+                true => 2,
+                false => 5,
+            };
+            *mat.at_2d_mut::<u8>(upper, col as i32).unwrap() = 255;
+            *mat.at_2d_mut::<u8>(14, col as i32).unwrap() = 255;
+        }
+        Image::with(mat)
     }
     ///
     /// Fake implements `Eval` for testing [EdgeDetection]
     struct FakePassImg {
         img: Image,
+        mask: Image,
+        position: PositionCtx,
     }
     impl FakePassImg{
         pub fn new(img: Image) -> Self {
             Self { 
                 img,
+                mask: Image::default(),
+                position: PositionCtx::default(),
+            }
+        }
+        pub fn masked(img: Image, mask: Image) -> Self {
+            Self { 
+                img,
+                mask,
+                position: PositionCtx::default(),
             }
         }
+        pub fn with(self, position: PositionCtx) -> Self {
+            Self { position, ..self }
+        }
     }
     //
     //
     impl Eval<(), Result<Context, Error>> for FakePassImg {
         fn eval(&self, _: ()) -> Result<Context, Error> {
-            Ok(
-                Context::new(
-                    InitialCtx::new(self.img.clone()),
-                ),
+            Context::new(
+                InitialCtx::new(self.img.clone()),
             )
+            .write(DetectingContoursCvCtx { result: self.img.clone(), mask: self.mask.clone() })?
+            .write(self.position.clone())
         }
     }
 }
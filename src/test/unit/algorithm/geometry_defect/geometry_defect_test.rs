@@ -14,8 +14,9 @@ mod geometry_defect {
     };
     use crate::{
         algorithm::{
-            GeometryDefect, GeometryDefectCtx, GeometryDefectType, Threshold,
-            WidthEmissions, Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, InitialPoints, Mad,
+            Defect, DefectSide, GeometryDefect, GeometryDefectCtx, GeometryDefectType, Threshold,
+            WidthEmissions, Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, InitialPoints, Mad, Ransac,
+            ProfileDefectCtx, RoiCtx, Side,
         }, 
         domain::{Dot, Eval, Image},
     };
@@ -50,56 +51,57 @@ mod geometry_defect {
                 Threshold(1.1),
                 InitialPoints::new(
                     vec![
-                        Dot { x: 10  , y: 100 },
-                        Dot { x: 20  , y: 105 },
-                        Dot { x: 30  , y: 110 },
-                        Dot { x: 40  , y: 120 },
-                        Dot { x: 50  , y: 130 },
-                        Dot { x: 60  , y: 135 },
-                        Dot { x: 70  , y: 130 },
-                        Dot { x: 80  , y: 120 },
-                        Dot { x: 90  , y: 110 },
-                        Dot { x: 100 , y: 105 },
-                        Dot { x: 110 , y: 100 },
-                        Dot { x: 120 , y: 100 },
-                        Dot { x: 130 , y: 105 },
-                        Dot { x: 140 , y: 110 },
-                        Dot { x: 150 , y: 120 },
-                        Dot { x: 160 , y: 130 },
-                        Dot { x: 170 , y: 135 },
-                        Dot { x: 180 , y: 130 },
-                        Dot { x: 190 , y: 120 },
-                        Dot { x: 200 , y: 110 },
-                        Dot { x: 210 , y: 105 },
-                        Dot { x: 220 , y: 100 },
+                        Dot { x: 10.0  , y: 100.0 },
+                        Dot { x: 20.0  , y: 105.0 },
+                        Dot { x: 30.0  , y: 110.0 },
+                        Dot { x: 40.0  , y: 120.0 },
+                        Dot { x: 50.0  , y: 130.0 },
+                        Dot { x: 60.0  , y: 135.0 },
+                        Dot { x: 70.0  , y: 130.0 },
+                        Dot { x: 80.0  , y: 120.0 },
+                        Dot { x: 90.0  , y: 110.0 },
+                        Dot { x: 100.0 , y: 105.0 },
+                        Dot { x: 110.0 , y: 100.0 },
+                        Dot { x: 120.0 , y: 100.0 },
+                        Dot { x: 130.0 , y: 105.0 },
+                        Dot { x: 140.0 , y: 110.0 },
+                        Dot { x: 150.0 , y: 120.0 },
+                        Dot { x: 160.0 , y: 130.0 },
+                        Dot { x: 170.0 , y: 135.0 },
+                        Dot { x: 180.0 , y: 130.0 },
+                        Dot { x: 190.0 , y: 120.0 },
+                        Dot { x: 200.0 , y: 110.0 },
+                        Dot { x: 210.0 , y: 105.0 },
+                        Dot { x: 220.0 , y: 100.0 },
                     ],
                     vec![
-                        Dot { x: 10  , y: 50 },
-                        Dot { x: 20  , y: 45 },
-                        Dot { x: 30  , y: 40 },
-                        Dot { x: 40  , y: 30 },
-                        Dot { x: 50  , y: 20 },
-                        Dot { x: 60  , y: 15 },
-                        Dot { x: 70  , y: 20 },
-                        Dot { x: 80  , y: 30 },
-                        Dot { x: 90  , y: 40 },
-                        Dot { x: 100 , y: 45 },
-                        Dot { x: 110 , y: 50 },
-                        Dot { x: 120 , y: 50 },
-                        Dot { x: 130 , y: 45 },
-                        Dot { x: 140 , y: 40 },
-                        Dot { x: 150 , y: 30 },
-                        Dot { x: 160 , y: 20 },
-                        Dot { x: 170 , y: 15 },
-                        Dot { x: 180 , y: 20 },
-                        Dot { x: 190 , y: 30 },
-                        Dot { x: 200 , y: 40 },
-                        Dot { x: 210 , y: 45 },
-                        Dot { x: 220 , y: 50 },
+                        Dot { x: 10.0  , y: 50.0 },
+                        Dot { x: 20.0  , y: 45.0 },
+                        Dot { x: 30.0  , y: 40.0 },
+                        Dot { x: 40.0  , y: 30.0 },
+                        Dot { x: 50.0  , y: 20.0 },
+                        Dot { x: 60.0  , y: 15.0 },
+                        Dot { x: 70.0  , y: 20.0 },
+                        Dot { x: 80.0  , y: 30.0 },
+                        Dot { x: 90.0  , y: 40.0 },
+                        Dot { x: 100.0 , y: 45.0 },
+                        Dot { x: 110.0 , y: 50.0 },
+                        Dot { x: 120.0 , y: 50.0 },
+                        Dot { x: 130.0 , y: 45.0 },
+                        Dot { x: 140.0 , y: 40.0 },
+                        Dot { x: 150.0 , y: 30.0 },
+                        Dot { x: 160.0 , y: 20.0 },
+                        Dot { x: 170.0 , y: 15.0 },
+                        Dot { x: 180.0 , y: 20.0 },
+                        Dot { x: 190.0 , y: 30.0 },
+                        Dot { x: 200.0 , y: 40.0 },
+                        Dot { x: 210.0 , y: 45.0 },
+                        Dot { x: 220.0 , y: 50.0 },
                     ],
                 ),
                 vec![
-                    GeometryDefectType::Expansion
+                    (GeometryDefectType::Expansion, DefectSide::Both, 50, 70),
+                    (GeometryDefectType::Expansion, DefectSide::Both, 160, 180),
                 ]
             ),
             (
@@ -107,34 +109,34 @@ mod geometry_defect {
                 Threshold(1.1),
                 InitialPoints::new(
                     vec![
-                        Dot { x: 10  , y: 100 },
-                        Dot { x: 20  , y: 105 },
-                        Dot { x: 30  , y: 110 },
-                        Dot { x: 40  , y: 85 },
-                        Dot { x: 50  , y: 80 },
-                        Dot { x: 60  , y: 75 },
-                        Dot { x: 70  , y: 70 },
-                        Dot { x: 80  , y: 85 },
-                        Dot { x: 90  , y: 110 },
-                        Dot { x: 100 , y: 105 },
-                        Dot { x: 110 , y: 100 },
+                        Dot { x: 10.0  , y: 100.0 },
+                        Dot { x: 20.0  , y: 105.0 },
+                        Dot { x: 30.0  , y: 110.0 },
+                        Dot { x: 40.0  , y: 85.0 },
+                        Dot { x: 50.0  , y: 80.0 },
+                        Dot { x: 60.0  , y: 75.0 },
+                        Dot { x: 70.0  , y: 70.0 },
+                        Dot { x: 80.0  , y: 85.0 },
+                        Dot { x: 90.0  , y: 110.0 },
+                        Dot { x: 100.0 , y: 105.0 },
+                        Dot { x: 110.0 , y: 100.0 },
                     ],
                     vec![
-                        Dot { x: 10  , y: 50 },
-                        Dot { x: 20  , y: 45 },
-                        Dot { x: 30  , y: 40 },
-                        Dot { x: 40  , y: 70 },
-                        Dot { x: 50  , y: 80 },
-                        Dot { x: 60  , y: 73 },
-                        Dot { x: 70  , y: 68 },
-                        Dot { x: 80  , y: 40 },
-                        Dot { x: 90  , y: 40 },
-                        Dot { x: 100 , y: 45 },
-                        Dot { x: 110 , y: 50 },
+                        Dot { x: 10.0  , y: 50.0 },
+                        Dot { x: 20.0  , y: 45.0 },
+                        Dot { x: 30.0  , y: 40.0 },
+                        Dot { x: 40.0  , y: 70.0 },
+                        Dot { x: 50.0  , y: 80.0 },
+                        Dot { x: 60.0  , y: 73.0 },
+                        Dot { x: 70.0  , y: 68.0 },
+                        Dot { x: 80.0  , y: 40.0 },
+                        Dot { x: 90.0  , y: 40.0 },
+                        Dot { x: 100.0 , y: 45.0 },
+                        Dot { x: 110.0 , y: 50.0 },
                     ],
                 ),
                 vec![
-                    GeometryDefectType::Compressing
+                    (GeometryDefectType::Compressing, DefectSide::Both, 40, 70),
                 ]
             ),
             (
@@ -142,35 +144,47 @@ mod geometry_defect {
                 Threshold(1.1),
                 InitialPoints::new(
                     vec![
-                        Dot { x: 10  , y: 100 },
-                        Dot { x: 20  , y: 105 },
-                        Dot { x: 30  , y: 110 },
-                        Dot { x: 40  , y: 85 },
-                        Dot { x: 50  , y: 80 },
-                        Dot { x: 60  , y: 75 },
-                        Dot { x: 70  , y: 70 },
-                        Dot { x: 80  , y: 85 },
-                        Dot { x: 90  , y: 110 },
-                        Dot { x: 100 , y: 105 },
-                        Dot { x: 110 , y: 100 },
+                        Dot { x: 10.0  , y: 100.0 },
+                        Dot { x: 20.0  , y: 105.0 },
+                        Dot { x: 30.0  , y: 110.0 },
+                        Dot { x: 40.0  , y: 85.0 },
+                        Dot { x: 50.0  , y: 80.0 },
+                        Dot { x: 60.0  , y: 75.0 },
+                        Dot { x: 70.0  , y: 70.0 },
+                        Dot { x: 80.0  , y: 85.0 },
+                        Dot { x: 90.0  , y: 110.0 },
+                        Dot { x: 100.0 , y: 105.0 },
+                        Dot { x: 110.0 , y: 100.0 },
                     ],
                     vec![
-                        Dot { x: 10  , y: 50 },
-                        Dot { x: 20  , y: 45 },
-                        Dot { x: 30  , y: 46 },
-                        Dot { x: 40  , y: 50 },
-                        Dot { x: 50  , y: 50 },
-                        Dot { x: 60  , y: 53 },
-                        Dot { x: 70  , y: 58 },
-                        Dot { x: 80  , y: 50 },
-                        Dot { x: 90  , y: 46 },
-                        Dot { x: 100 , y: 45 },
-                        Dot { x: 110 , y: 50 },
+                        Dot { x: 10.0  , y: 50.0 },
+                        Dot { x: 20.0  , y: 45.0 },
+                        Dot { x: 30.0  , y: 46.0 },
+                        Dot { x: 40.0  , y: 50.0 },
+                        Dot { x: 50.0  , y: 50.0 },
+                        Dot { x: 60.0  , y: 53.0 },
+                        Dot { x: 70.0  , y: 58.0 },
+                        Dot { x: 80.0  , y: 50.0 },
+                        Dot { x: 90.0  , y: 46.0 },
+                        Dot { x: 100.0 , y: 45.0 },
+                        Dot { x: 110.0 , y: 50.0 },
                     ],
                 ),
+                // One segment classified as a whole, no Hill / Compressing alternation
                 vec![
-                    GeometryDefectType::Hill, 
-                    GeometryDefectType::Compressing
+                    (GeometryDefectType::Compressing, DefectSide::Both, 50, 70),
+                ]
+            ),
+            (
+                4,
+                Threshold(1.1),
+                // Tilted rope with the expansion near the end of the frame
+                InitialPoints::new(
+                    (1..=22).map(|i| (i * 10) as f64).map(|x| Dot { x, y: 100.0 + x / 2.0 + if x == 180.0 || x == 190.0 { 15.0 } else { 0.0 } }).collect(),
+                    (1..=22).map(|i| (i * 10) as f64).map(|x| Dot { x, y: 50.0 + x / 2.0 - if x == 180.0 || x == 190.0 { 15.0 } else { 0.0 } }).collect(),
+                ),
+                vec![
+                    (GeometryDefectType::Expansion, DefectSide::Both, 180, 190),
                 ]
             ),
         ];
@@ -184,20 +198,45 @@ mod geometry_defect {
             };
             ctx.ctx = ctx.ctx
                 .clone()
-                .write(EdgeDetectionCtx{ result: initial_points.clone() })
+                .write(EdgeDetectionCtx { result: initial_points.clone(), ..Default::default() })
+                .unwrap()
+                .write(RoiCtx { y: 20, width: 240, height: 200, tracked: true })
                 .unwrap();
             let result = GeometryDefect::new(
                 threshold,
+                None,
+                "camera",
                 *Box::new(Mad::new()),
-                WidthEmissions::new(threshold, 
+                Ransac::new(100, 5.0),
+                WidthEmissions::new(threshold, 2, 19,
                     *Box::new(Mad::new()), 
+                    Ransac::new(100, 5.0),
                     ctx
                 ),
             ).eval(());
             match result {
                 Ok(result) => {
-                    let result = ContextRead::<GeometryDefectCtx>::read(&result)
-                        .result.clone();
+                    let result = &ContextRead::<GeometryDefectCtx>::read(&result).result;
+                    assert!(
+                        result.iter().all(|defect| defect.camera == "camera" && (0.0..=1.0).contains(&defect.confidence)),
+                        "step {} \nresult: {:?}",
+                        step,
+                        result
+                    );
+                    // Rows of the edges over the defect on the aligned frame, below the 20 rows cropped
+                    for defect in result {
+                        let columns = defect.start as f64..=defect.end as f64;
+                        let rows: Vec<f64> = initial_points.get(Side::Upper).iter()
+                            .chain(initial_points.get(Side::Lower).iter())
+                            .filter(|dot| columns.contains(&dot.x))
+                            .map(|dot| dot.y + 20.0)
+                            .collect();
+                        let target = Some((rows.iter().cloned().fold(f64::MAX, f64::min), rows.iter().cloned().fold(f64::MIN, f64::max)));
+                        assert!(defect.rows == target, "step {} \nresult: {:?}\ntarget: {:?}", step, defect.rows, target);
+                    }
+                    let result: Vec<(GeometryDefectType, DefectSide, usize, usize)> = result.iter()
+                        .map(|defect| (defect.kind.clone(), defect.side, defect.start, defect.end))
+                        .collect();
                     assert!(
                         result == target, 
                         "step {} \nresult: {:?}\ntarget: {:?}", 
@@ -212,6 +251,79 @@ mod geometry_defect {
         test_duration.exit();
     }
     ///
+    /// Testing `eval` keeps the one record of the bulge found as the outward edge deviation and as the Mound
+    #[test]
+    fn mound() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("geometry_defect");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let threshold = Threshold(3.0);
+        // Edges of the one pixel noise as found by `EdgeDetection`, the upper one on the smaller rows,
+        // the lower one moved by the `bulge` at 180 and 190, outward if positive
+        let edges = |bulge: f64| InitialPoints::new(
+            (1..=22).map(|i| Dot { x: (i * 10) as f64, y: 50.0 + ((i - 1) % 2) as f64 }).collect(),
+            (1..=22).map(|i| (i, (i * 10) as f64)).map(|(i, x)| Dot { x, y: 100.0 + ((i - 1) % 2) as f64 + if x == 180.0 || x == 190.0 { bulge } else { 0.0 } }).collect(),
+        );
+        let mound = |side, start, end| Defect::new(GeometryDefectType::Mound, side, start, end, 15.0, 0.3, 0.5);
+        let test_data = [
+            (1, edges(15.0), vec![], vec![(GeometryDefectType::Pit, DefectSide::Lower, 180, 190)]),
+            (2, edges(15.0), vec![mound(DefectSide::Lower, 175, 195)], vec![(GeometryDefectType::Mound, DefectSide::Lower, 175, 195)]),
+            // The Mound on the other side or off the bulge is the other defect
+            (
+                3,
+                edges(15.0),
+                vec![mound(DefectSide::Upper, 175, 195)],
+                vec![(GeometryDefectType::Pit, DefectSide::Lower, 180, 190), (GeometryDefectType::Mound, DefectSide::Upper, 175, 195)],
+            ),
+            (
+                4,
+                edges(15.0),
+                vec![mound(DefectSide::Lower, 20, 40)],
+                vec![(GeometryDefectType::Pit, DefectSide::Lower, 180, 190), (GeometryDefectType::Mound, DefectSide::Lower, 20, 40)],
+            ),
+            // The inward deviation isn't the Mound
+            (
+                5,
+                edges(-15.0),
+                vec![mound(DefectSide::Lower, 175, 195)],
+                vec![(GeometryDefectType::Hill, DefectSide::Lower, 180, 190), (GeometryDefectType::Mound, DefectSide::Lower, 175, 195)],
+            ),
+        ];
+        for (step, initial_points, profile_defects, target) in test_data {
+            let ctx = MocEval {
+                ctx: Context::new(InitialCtx::new(Image::default()))
+                    .write(EdgeDetectionCtx { result: initial_points, ..Default::default() })
+                    .unwrap()
+                    .write(RoiCtx { y: 20, width: 240, height: 200, tracked: true })
+                    .unwrap()
+                    .write(ProfileDefectCtx { result: profile_defects })
+                    .unwrap(),
+            };
+            let result = GeometryDefect::new(
+                threshold,
+                None,
+                "camera",
+                *Box::new(Mad::new()),
+                Ransac::new(100, 5.0),
+                WidthEmissions::new(threshold, 2, 19, *Box::new(Mad::new()), Ransac::new(100, 5.0), ctx),
+            ).eval(());
+            match result {
+                Ok(result) => {
+                    let result: Vec<(GeometryDefectType, DefectSide, usize, usize)> = ContextRead::<GeometryDefectCtx>::read(&result).result.iter()
+                        .map(|defect| (defect.kind.clone(), defect.side, defect.start, defect.end))
+                        .collect();
+                    assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
     ///
     #[derive(Debug, Clone)]
     struct MocEval {
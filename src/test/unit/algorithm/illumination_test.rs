@@ -0,0 +1,154 @@
+#[cfg(test)]
+
+mod illumination {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::{
+        core::{Mat, MatTrait, MatTraitConst, Scalar, Vec3b, CV_8UC3},
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{Brightness, Context, ContextRead, EvalResult, FlatField, Illumination, IlluminationConf, IlluminationCtx, InitialCtx},
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the gray frame 100 x 200 with the `left` and the `right` halves brightness
+    fn frame(left: f64, right: f64) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(100, 200, CV_8UC3, Scalar::all(left)).unwrap();
+        for row in 0..100 {
+            for col in 100..200 {
+                *mat.at_2d_mut::<Vec3b>(row, col).unwrap() = Vec3b::all(right as u8);
+            }
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Returns brightness of the pixel in the middle row of the frame
+    fn brightness(image: &Image, col: i32) -> f64 {
+        let pixel = image.mat.at_2d::<Vec3b>(50, col).unwrap();
+        (pixel[0] as f64 + pixel[1] as f64 + pixel[2] as f64) / 3.0
+    }
+    ///
+    /// Returns configuration with all corrections disabled
+    fn plain() -> IlluminationConf {
+        IlluminationConf { clahe_clip: 0.0, clahe_tiles: 8, gamma: 1.0, smoothing: 1.0 }
+    }
+    ///
+    /// Testing brightness, gamma and flat-field correction
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("illumination");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let flat_field = Arc::new(FlatField::new(&frame(100.0, 200.0)).unwrap());
+        let test_data = [
+            // Nothing to correct
+            (1, plain(), None, vec![frame(100.0, 100.0)], 100.0),
+            // Brightness jump halved by the smoothing
+            (2, IlluminationConf { smoothing: 0.5, ..plain() }, None, vec![frame(100.0, 100.0), frame(140.0, 140.0)], 120.0),
+            (3, IlluminationConf { gamma: 2.0, ..plain() }, None, vec![frame(64.0, 64.0)], 128.0),
+            // Uneven lighting of the reference frame evened
+            (4, plain(), Some(flat_field.clone()), vec![frame(100.0, 200.0)], 150.0),
+            (5, plain(), Some(flat_field.clone()), vec![frame(50.0, 100.0)], 75.0),
+        ];
+        for (step, conf, flat_field, frames, target) in test_data {
+            let state = Arc::new(RwLock::new(Brightness::default()));
+            let mut result = None;
+            let source = frames.last().cloned().unwrap();
+            for image in frames {
+                let ctx = MocEval {
+                    ctx: Context::new(
+                        InitialCtx::new(image)
+                    ),
+                };
+                result = match Illumination::new(conf, flat_field.clone(), state.clone(), ctx).eval(()) {
+                    Ok(result) => Some(result),
+                    Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+                };
+            }
+            let result = result.unwrap();
+            let frame = &ContextRead::<IlluminationCtx>::read(&result).frame;
+            let src_frame = &ContextRead::<InitialCtx>::read(&result).src_frame;
+            for col in [10, 190] {
+                let result = brightness(frame, col);
+                assert!((result - target).abs() <= 2.0, "step {} col {} \nresult: {:?}\ntarget: {:?}", step, col, result, target);
+                // The source frame left untouched
+                let (result, target) = (brightness(src_frame, col), brightness(&source, col));
+                assert!(result == target, "step {} col {} \nresult: {:?}\ntarget: {:?}", step, col, result, target);
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing the contrast of the dim frame is stretched by CLAHE
+    #[test]
+    fn clahe() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("illumination_clahe");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // Smooth ramp 100..150 along the rows
+        let mut mat = Mat::new_rows_cols_with_default(100, 200, CV_8UC3, Scalar::all(0.0)).unwrap();
+        for row in 0..100 {
+            for col in 0..200 {
+                *mat.at_2d_mut::<Vec3b>(row, col).unwrap() = Vec3b::all(100 + (col / 4) as u8);
+            }
+        }
+        let image = Image::with(mat);
+        let span = |image: &Image| {
+            let values: Vec<f64> = (0..200).map(|col| brightness(image, col)).collect();
+            values.iter().cloned().fold(f64::MIN, f64::max) - values.iter().cloned().fold(f64::MAX, f64::min)
+        };
+        let source = span(&image);
+        let ctx = MocEval {
+            ctx: Context::new(
+                InitialCtx::new(image)
+            ),
+        };
+        let conf = IlluminationConf { clahe_clip: 40.0, ..plain() };
+        let result = Illumination::new(conf, None, Arc::new(RwLock::new(Brightness::default())), ctx).eval(()).unwrap();
+        let result = span(&ContextRead::<IlluminationCtx>::read(&result).frame);
+        assert!(result > 2.0 * source, "\nresult: {:?}\nsource: {:?}", result, source);
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
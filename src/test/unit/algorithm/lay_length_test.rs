@@ -0,0 +1,187 @@
+#[cfg(test)]
+
+mod lay_length {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, MatTrait, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, DetectingContoursCvCtx, EdgeDetectionCtx, EvalResult,
+            InitialCtx, InitialPoints, LayDirection, LayHistory, LayLength, LayLengthCtx, PositionCtx, Scale,
+        },
+        domain::{Dot, Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns 40x200 gradient image with the strands inside the rope band (rows 10...30)
+    /// - `pitch` - distance between strands along the rope
+    /// - `slope` - 1 for strands going right-down (`\`), -1 for strands going left-down (`/`)
+    fn image(pitch: i32, slope: i32) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(40, 200, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 10..=30 {
+            for col in 0..200 {
+                if (col - slope * row).rem_euclid(pitch) == 0 {
+                    *mat.at_2d_mut::<u8>(row, col).unwrap() = 255;
+                }
+            }
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Returns the context of the `image` at the `position` along the rope, m, the rope band between the rows 10 and 30
+    fn ctx(image: Image, position: Option<f64>) -> MocEval {
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::default()))
+                .write(DetectingContoursCvCtx { result: image, mask: Image::default() })
+                .unwrap()
+                .write(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        (0..200).map(|x| Dot { x: x as f64, y: 10.0 }).collect(),
+                        (0..200).map(|x| Dot { x: x as f64, y: 30.0 }).collect(),
+                    ),
+                    ..Default::default()
+                })
+                .unwrap()
+                .write(PositionCtx { position, ..Default::default() })
+                .unwrap(),
+        }
+    }
+    ///
+    /// Testing `eval` aggregating the lay along the rope
+    #[test]
+    fn along() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("lay_length_along");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let history = Arc::new(RwLock::new(LayHistory::default()));
+        let scale = Scale::new(0.5, None);
+        // step, strand slope, position, target (frames of the section, elongation range)
+        let test_data = [
+            // The only section, it's the nominal one
+            (1, 1, Some(0.2), Some((1, 0.0..0.001))),
+            (2, 1, Some(0.7), Some((2, 0.0..0.001))),
+            // Same lay in the next section
+            (3, 1, Some(1.5), Some((1, 0.0..0.001))),
+            // Lay stretched twice in the third section
+            (4, 2, Some(2.5), Some((1, 0.5..1.5))),
+            // Position unknown, the frame not aggregated
+            (5, 1, None, None),
+        ];
+        for (step, slope, position, target) in test_data {
+            let result = LayLength::new(Some(scale), history.clone(), ctx(image(10, slope), position)).eval(()).unwrap();
+            let result = ContextRead::<LayLengthCtx>::read(&result).clone();
+            let lay = result.result.clone().unwrap();
+            let length_mm = lay.length_mm.unwrap();
+            assert!((length_mm - lay.length * 0.5).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, length_mm, lay.length * 0.5);
+            match (result.section, result.elongation, target) {
+                (Some(section), Some(elongation), Some((frames, range))) => {
+                    assert!(section.frames == frames, "step {} \nresult: {:?}\ntarget: {:?}", step, section.frames, frames);
+                    assert!(range.contains(&elongation.abs()), "step {} \nresult: {:?}\ntarget: {:?}", step, elongation, range);
+                    let section_mm = result.section_mm.unwrap();
+                    assert!((section_mm - section.length * 0.5).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, section_mm, section.length * 0.5);
+                }
+                (None, None, None) => {}
+                (section, elongation, target) => panic!("step {} \nresult: {:?}\ntarget: {:?}", step, (section, elongation), target),
+            }
+        }
+        let result = history.read().sections.len();
+        assert!(result == 3, "\nresult: {:?}\ntarget: {:?}", result, 3);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("lay_length");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, image(10, -1), (10.0, 6, LayDirection::Left)),
+            (2, image(10, 1), (10.0, 6, LayDirection::Right)),
+            (3, image(12, 1), (12.0, 5, LayDirection::Right)),
+        ];
+        for (step, image, (target_pitch, target_strands, target_direction)) in test_data {
+            let mut ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(Image::default())
+                ),
+            };
+            ctx.ctx = ctx.ctx
+                .clone()
+                .write(DetectingContoursCvCtx { result: image, mask: Image::default() })
+                .unwrap()
+                .write(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        (0..200).map(|x| Dot { x: x as f64, y: 10.0 }).collect(),
+                        (0..200).map(|x| Dot { x: x as f64, y: 30.0 }).collect(),
+                    ),
+                    ..Default::default()
+                })
+                .unwrap();
+            let result = LayLength::new(None, Arc::new(RwLock::new(LayHistory::default())), ctx).eval(());
+            match result {
+                Ok(result) => {
+                    let result = ContextRead::<LayLengthCtx>::read(&result)
+                        .result.clone();
+                    match result {
+                        Some(result) => {
+                            assert!(
+                                (result.pitch - target_pitch).abs() < 0.5 &&
+                                result.strands == target_strands &&
+                                result.direction == target_direction,
+                                "step {} \nresult: {:?}\ntarget: {:?}",
+                                step,
+                                result,
+                                (target_pitch, target_strands, target_direction),
+                            );
+                        }
+                        None => panic!("step {} \nresult: None", step),
+                    }
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
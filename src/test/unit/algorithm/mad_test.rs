@@ -41,7 +41,7 @@ mod mad {
         log::debug!("\n{}", dbg);
         let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
         test_duration.run().unwrap();
-        let test_data = [
+        let test_data: [(usize, Vec<usize>, f64); 2] = [
             (
                 1,
                 vec![
@@ -58,7 +58,12 @@ mod mad {
                     100,
                 ],
                 10.0,
-            )
+            ),
+            (
+                2,
+                vec![],
+                0.0,
+            ),
         ];
         for (step, sample, target) in test_data {
             let result = Mad::new()
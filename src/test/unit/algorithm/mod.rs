@@ -1,6 +1,25 @@
+mod background_test;
+mod baseline_test;
+mod defect_tracker_test;
 mod detecting_contours;
 mod detecting_contours_cv;
+mod diameter_test;
+mod discard_test;
 mod fast_scan;
 mod geometry_defect;
+mod illumination_test;
+mod lay_length_test;
 mod mad_test;
-mod width_emissions_test;
\ No newline at end of file
+mod orientation_test;
+mod outlier_detector_test;
+mod panorama_test;
+mod position_test;
+mod profile_defect_test;
+mod ransac_test;
+mod roi_test;
+mod rope_axis_test;
+mod speed_test;
+mod surface_defect_test;
+mod undistort_test;
+mod vibration_test;
+mod width_emissions_test;
@@ -0,0 +1,181 @@
+#[cfg(test)]
+
+mod orientation {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::{
+        core::{Mat, MatTraitConst, Point, Scalar, CV_8UC3},
+        imgproc,
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, DetectingContoursCv, EdgeDetection, EdgeDetectionCtx, EvalResult, InitialCtx, Orientation,
+            OrientationCtx, Roi, RoiBand, RoiCtx, Segmentation, Side,
+        },
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the frame 200 x 200 with the rope 40 px width through the center,
+    /// at the `angle` counter-clockwise from the rows, degrees
+    fn rope(angle: f64) -> Image {
+        rope_on(200, 200, angle, 0.0)
+    }
+    ///
+    /// Returns the frame `cols` x `rows` of the `background` brightness with the rope 40 px width through the center,
+    /// at the `angle` counter-clockwise from the rows, degrees
+    fn rope_on(cols: i32, rows: i32, angle: f64, background: f64) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(rows, cols, CV_8UC3, Scalar::all(background)).unwrap();
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (x, y, length) = (cols as f64 / 2.0, rows as f64 / 2.0, (cols + rows) as f64);
+        imgproc::line(
+            &mut mat,
+            Point::new((x - length * cos).round() as i32, (y + length * sin).round() as i32),
+            Point::new((x + length * cos).round() as i32, (y - length * sin).round() as i32),
+            Scalar::all(200.0),
+            40,
+            imgproc::LINE_8,
+            0,
+        ).unwrap();
+        Image::with(mat)
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("orientation");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, rope(0.0), 0.0, false),
+            (2, rope(30.0), 30.0, true),
+            (3, rope(-45.0), -45.0, true),
+            (4, rope(90.0), 90.0, true),
+        ];
+        for (step, image, target, rotated) in test_data {
+            let ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(image)
+                ),
+            };
+            let result = Orientation::new(ctx).eval(());
+            match result {
+                Ok(result) => {
+                    // The size of the strip doesn't depend on the angle
+                    let frame = &ContextRead::<InitialCtx>::read(&result).src_frame;
+                    assert!(
+                        (frame.mat.cols(), frame.mat.rows()) == (200, 200),
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        (frame.mat.cols(), frame.mat.rows()),
+                        (200, 200)
+                    );
+                    let orientation = ContextRead::<OrientationCtx>::read(&result);
+                    // The axis has no direction, so the angles are compared modulo 180 degrees
+                    let error = (orientation.angle - target).rem_euclid(180.0);
+                    assert!(
+                        (error < 2.0 || error > 178.0) && orientation.rotated == rotated,
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        orientation,
+                        (target, rotated)
+                    );
+                    // The normalised frame must have the rope running along the rows
+                    let result = Orientation::new(MocEval { ctx: result.clone() }).eval(()).unwrap();
+                    let orientation = ContextRead::<OrientationCtx>::read(&result);
+                    assert!(
+                        orientation.angle.abs() < 2.0,
+                        "step {} \nnormalised: {:?}",
+                        step,
+                        orientation
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `Orientation` -> `Roi` -> `DetectingContoursCv` -> `EdgeDetection` on the tilted rope over the grey background,
+    /// the corners of the strip must not give the edges off the rope
+    #[test]
+    fn pipeline() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("orientation_pipeline");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // Portrait frame, the strip of the steep rope transposed, of the gentle one not
+        for (angle, target) in [(75.0, (200, 120)), (20.0, (120, 200))] {
+            let result = Orientation::new(MocEval { ctx: Context::new(InitialCtx::new(rope_on(120, 200, angle, 60.0))) }).eval(()).unwrap();
+            let frame = &ContextRead::<InitialCtx>::read(&result).src_frame;
+            let result = (frame.mat.cols(), frame.mat.rows());
+            assert!(result == target, "angle {} \nresult: {:?}\ntarget: {:?}", angle, result, target);
+        }
+        for (step, angle) in [(1, 20.0), (2, 30.0), (3, -35.0)] {
+            let result = EdgeDetection::new(
+                None,
+                None,
+                DetectingContoursCv::new(
+                    Segmentation::default(),
+                    Roi::new(
+                        10,
+                        Arc::new(RwLock::new(RoiBand::default())),
+                        Orientation::new(MocEval { ctx: Context::new(InitialCtx::new(rope_on(200, 200, angle, 60.0))) }),
+                    ),
+                ),
+            ).eval(()).unwrap();
+            let roi = *ContextRead::<RoiCtx>::read(&result);
+            let edges = &ContextRead::<EdgeDetectionCtx>::read(&result).result;
+            // The rope band of the strip at the rows 80...120
+            for (side, target) in [(Side::Upper, 80.0), (Side::Lower, 120.0)] {
+                let dots = edges.get(side);
+                assert!(dots.len() >= 180, "step {} {:?} \nresult: {:?} columns\ntarget: >= 180", step, side, dots.len());
+                for dot in dots {
+                    let y = roi.aligned(dot).y;
+                    assert!((y - target).abs() <= 2.0, "step {} {:?} column {} \nresult: {:?}\ntarget: {:?}", step, side, dot.x, y, target);
+                }
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+
+mod outlier_detector {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession,
+        LogLevel,
+        Backtrace
+    };
+    use crate::{
+        algorithm::{OutlierCtx, OutlierDetector},
+        domain::Eval
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns 40 widths tapering from 100 to 61 with the short bulge of 10 in the middle
+    fn taper() -> Vec<f64> {
+        let mut sample: Vec<f64> = (0..40).map(|i| 100.0 - i as f64).collect();
+        sample[20] += 10.0;
+        sample
+    }
+    ///
+    /// Returns 20 widths 100 ± 2 with the single outlier 130 in the middle
+    fn noisy() -> Vec<f64> {
+        let mut sample: Vec<f64> = (0..20).map(|i| 100.0 + ((i * 7) % 5) as f64 - 2.0).collect();
+        sample[10] = 130.0;
+        sample
+    }
+    ///
+    /// Returns 20 widths 100 with the single pixel step in the middle
+    fn flat() -> Vec<f64> {
+        let mut sample = vec![100.0; 20];
+        sample[10] = 101.0;
+        sample
+    }
+    ///
+    /// Returns [noisy] widths with the cluster of the three outliers 115 in the middle
+    fn cluster() -> Vec<f64> {
+        let mut sample = noisy();
+        sample[8..=10].fill(115.0);
+        sample
+    }
+    ///
+    /// Returns [noisy] widths with the lost point
+    fn lost() -> Vec<f64> {
+        let mut sample = noisy();
+        sample[5] = f64::NAN;
+        sample
+    }
+    ///
+    /// Testing `eval` of each detector, indexes of the points scored over the threshold
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("outlier_detector");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let threshold = 2.5;
+        let test_data: [(usize, OutlierDetector, Vec<f64>, Vec<usize>); 19] = [
+            // The bulge hidden by the taper for the whole sample detectors
+            (1, OutlierDetector::Mad, taper(), vec![]),
+            (2, OutlierDetector::ZScore, taper(), vec![]),
+            (3, OutlierDetector::Iqr, taper(), vec![]),
+            (4, OutlierDetector::RollingMad { window: 7 }, taper(), vec![20]),
+            (5, OutlierDetector::Hampel { window: 7 }, taper(), vec![20]),
+            (6, OutlierDetector::Mad, noisy(), vec![10]),
+            (7, OutlierDetector::ZScore, noisy(), vec![10]),
+            (8, OutlierDetector::Iqr, noisy(), vec![10]),
+            (9, OutlierDetector::RollingMad { window: 7 }, noisy(), vec![10]),
+            (10, OutlierDetector::Hampel { window: 7 }, noisy(), vec![10]),
+            (11, OutlierDetector::Hampel { window: 7 }, vec![], vec![]),
+            // The single pixel step on the flat edge, the spread of the windows limited below
+            (12, OutlierDetector::RollingMad { window: 7 }, flat(), vec![]),
+            (13, OutlierDetector::Hampel { window: 7 }, flat(), vec![]),
+            (14, OutlierDetector::ZScore, flat(), vec![10]),
+            // The cluster inflates the spread of the rolling windows, the Hampel filter cleans it
+            (15, OutlierDetector::RollingMad { window: 7 }, cluster(), vec![9, 10]),
+            (16, OutlierDetector::Hampel { window: 7 }, cluster(), vec![8, 9, 10]),
+            // The lost point neither panics nor scored
+            (17, OutlierDetector::Mad, lost(), vec![10]),
+            (18, OutlierDetector::Iqr, lost(), vec![10]),
+            (19, OutlierDetector::Hampel { window: 7 }, lost(), vec![10]),
+        ];
+        for (step, detector, sample, target) in test_data {
+            let len = sample.len();
+            let result: OutlierCtx = detector.eval(sample);
+            assert!(
+                result.center.len() == len && result.spread.len() == len && result.score.len() == len,
+                "step {} \nresult: {:?}\ntarget len: {:?}",
+                step,
+                result,
+                len
+            );
+            let result: Vec<usize> = result.score.iter()
+                .enumerate()
+                .filter(|(_, score)| **score > threshold)
+                .map(|(i, _)| i)
+                .collect();
+            assert!(
+                result == target,
+                "step {} \nresult: {:?}\ntarget: {:?}",
+                step,
+                result,
+                target
+            );
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,274 @@
+#[cfg(test)]
+
+mod panorama {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, MatTrait, MatTraitConst, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, EvalResult, InitialCtx, Panorama, PanoramaCtx, PanoramaStrip,
+            PositionCtx, PositionSource, Scale, SurfaceMap,
+        },
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    /// Column of the texture seen at the column 0 of the first frame
+    const ORIGIN: i64 = 200;
+    /// Size of the pixel on the rope surface, mm
+    const MM_PER_PX: f64 = 0.5;
+    ///
+    /// Returns the strand texture value at the `column` of the rope and the `row`
+    fn texel(column: i64, row: i64) -> u8 {
+        let hash = (column as u64 * 2_654_435_761) ^ (row as u64 * 40_503);
+        ((hash >> 7) % 256) as u8
+    }
+    ///
+    /// Returns the frame `rows` x 40 of the rope travelled forward by `travel` columns from the first frame
+    fn frame(rows: i32, travel: i64) -> Mat {
+        let mut mat = Mat::new_rows_cols_with_default(rows, 40, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..rows {
+            for col in 0..40 {
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = texel(ORIGIN + col as i64 - travel, row as i64);
+            }
+        }
+        mat
+    }
+    ///
+    /// Returns the context with the `frame` travelled by `shift` px from the previous one, to the `travel` px from the first
+    fn ctx(frame: Mat, shift: Option<f64>, travel: i64) -> MocEval {
+        let position = PositionCtx {
+            position: Some(travel as f64 * MM_PER_PX / 1000.0),
+            shift,
+            source: PositionSource::Optical,
+            ..Default::default()
+        };
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::with(frame)))
+                .write(position)
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns the strip stitched from the frames `rows` high, travelled by `shifts` px one after another
+    fn stitch(camera: &str, width: usize, rows: i32, shifts: &[i64]) -> Arc<RwLock<PanoramaStrip>> {
+        let strip = Arc::new(RwLock::new(PanoramaStrip::new(std::env::temp_dir(), camera, width)));
+        let mut travel = 0;
+        for (i, shift) in shifts.iter().enumerate() {
+            travel += shift;
+            let shift = (i > 0).then_some(*shift as f64);
+            Panorama::new(Some(Scale::new(MM_PER_PX, None)), strip.clone(), ctx(frame(rows, travel), shift, travel))
+                .eval(())
+                .unwrap();
+        }
+        strip
+    }
+    ///
+    /// Removes the files of the `strip`
+    fn remove(strip: &PanoramaStrip, camera: &str) {
+        for index in strip.indexes() {
+            _ = std::fs::remove_file(strip.tile_path(index));
+        }
+        _ = std::fs::remove_file(PanoramaStrip::path(std::env::temp_dir(), camera));
+    }
+    ///
+    /// Testing `eval` stitching the frames of the travelling rope into the strip
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("panorama");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let camera = "panorama-test";
+        let strip = Arc::new(RwLock::new(PanoramaStrip::new(std::env::temp_dir(), camera, 32)));
+        // step, shift from the previous frame, travel from the first frame, target (tile, column, stitched)
+        let test_data = [
+            // First frame stitched whole, center at the column 20 of the strip
+            (1, None, 0, (0, 20, 40)),
+            // Forward, the slit between the centers stitched, the strip grows to the left
+            (2, Some(6), 6, (0, 14, 6)),
+            (3, Some(6), 12, (0, 8, 6)),
+            (4, Some(6), 18, (0, 2, 6)),
+            // Slit across the border of the tiles
+            (5, Some(6), 24, (-1, 28, 6)),
+            // Faster travel, wider slit
+            (6, Some(10), 34, (-1, 18, 10)),
+            // Stationary
+            (7, Some(0), 34, (-1, 18, 0)),
+            // Backward, the strip already stitched revisited
+            (8, Some(-8), 26, (-1, 26, 8)),
+        ];
+        for (step, shift, travel, (tile, column, stitched)) in test_data {
+            let result = Panorama::new(
+                Some(Scale::new(MM_PER_PX, None)),
+                strip.clone(),
+                ctx(frame(16, travel), shift.map(|shift| shift as f64), travel),
+            ).eval(()).unwrap();
+            let result = ContextRead::<PanoramaCtx>::read(&result).clone();
+            let target = PanoramaCtx { tile: Some(tile), column: Some(column), stitched };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // Strip column `u` shows the texture column `ORIGIN + u`, indexed by the position of the rope at the frame center
+        let mut strip = strip.write();
+        for u in -14..40i64 {
+            let (index, column) = (u.div_euclid(32), u.rem_euclid(32) as usize);
+            let (tile, mat) = strip.tile(index).unwrap().unwrap();
+            let position = tile.positions[column].unwrap();
+            let target = (20 - u) as f64 * MM_PER_PX / 1000.0;
+            assert!((position - target).abs() < 1e-12, "column {} \nresult: {:?}\ntarget: {:?}", u, position, target);
+            for row in 0..16 {
+                let result = *mat.at_2d::<u8>(row, column as i32).unwrap();
+                let target = texel(ORIGIN + u, row as i64);
+                assert!(result == target, "column {}, row {} \nresult: {:?}\ntarget: {:?}", u, row, result, target);
+            }
+        }
+        // Defect found at the position located on the strip
+        let result = strip.locate(0.012, 1e-6);
+        assert!(result == Some((-1, 28)), "\nresult: {:?}\ntarget: {:?}", result, Some((-1, 28)));
+        let result = strip.locate(1.0, 1e-3);
+        assert!(result.is_none(), "\nresult: {:?}\ntarget: None", result);
+        remove(&strip, camera);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` starting the new segment of the strip on the unknown travel
+    /// and placing the frames of the different height without resizing
+    #[test]
+    fn segments() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("panorama_segments");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let camera = "panorama-test-segments";
+        let strip = Arc::new(RwLock::new(PanoramaStrip::new(std::env::temp_dir(), camera, 32)));
+        // step, rows, shift from the previous frame, travel from the first frame, target (tile, column, stitched)
+        let test_data = [
+            (1, 16, None, 0, (0, 20, 40)),
+            // Lower frame centered on the strip
+            (2, 10, Some(6), 6, (0, 14, 6)),
+            // Travel unknown, the frame stitched whole 16 columns apart from the center of the previous one
+            (3, 16, None, 20, (-1, 10, 40)),
+            // Higher frame cropped
+            (4, 20, Some(6), 26, (-1, 4, 6)),
+        ];
+        for (step, rows, shift, travel, (tile, column, stitched)) in test_data {
+            let result = Panorama::new(
+                Some(Scale::new(MM_PER_PX, None)),
+                strip.clone(),
+                ctx(frame(rows, travel), shift.map(|shift| shift as f64), travel),
+            ).eval(()).unwrap();
+            let result = ContextRead::<PanoramaCtx>::read(&result).clone();
+            let target = PanoramaCtx { tile: Some(tile), column: Some(column), stitched };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        let mut strip = strip.write();
+        let result = strip.breaks().to_vec();
+        assert!(result == vec![-42], "\nresult: {:?}\ntarget: {:?}", result, vec![-42]);
+        // Columns of the gap not indexed
+        let (tile, _) = strip.tile(-1).unwrap().unwrap();
+        let result = tile.positions[30..32].to_vec();
+        assert!(result == vec![None, None], "\nresult: {:?}\ntarget: {:?}", result, vec![None::<f64>, None]);
+        // Column `u` of the lower frame: rows 3..13 of the strip, black above and below
+        let (_, mat) = strip.tile(0).unwrap().unwrap();
+        for row in 0..16 {
+            let result = *mat.at_2d::<u8>(row, 16).unwrap();
+            let target = match row {
+                3..13 => texel(ORIGIN + 16, row as i64 - 3),
+                _ => 0,
+            };
+            assert!(result == target, "row {} \nresult: {:?}\ntarget: {:?}", row, result, target);
+        }
+        // Column `u` -26 of the higher frame: rows 2..18 of the frame
+        let (_, mat) = strip.tile(-1).unwrap().unwrap();
+        for row in 0..16 {
+            let result = *mat.at_2d::<u8>(row, 6).unwrap();
+            let target = texel(ORIGIN - 4, row as i64 + 2);
+            assert!(result == target, "row {} \nresult: {:?}\ntarget: {:?}", row, result, target);
+        }
+        remove(&strip, camera);
+        test_duration.exit();
+    }
+    ///
+    /// Testing [SurfaceMap] placing the strips of the cameras side by side, aligned by the position
+    #[test]
+    fn surface_map() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("panorama_surface_map");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // Narrow tiles, so the most of them flushed into the files and restored back
+        let upper = stitch("panorama-test-upper", 4, 16, &[0, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5]);
+        // The second camera started later, so it's strip columns differ for the same position
+        let lower = stitch("panorama-test-lower", 4, 10, &[35, 5, 5, 5]);
+        assert!(upper.read().indexes().len() > PanoramaStrip::CAPACITY, "tiles must be flushed");
+        // Column `j` at the position `j * 0.5 mm`, the texture column `ORIGIN + 20 - j`
+        let step = MM_PER_PX / 1000.0;
+        let map = SurfaceMap::new(vec![upper.clone(), lower.clone()]);
+        let result = map.render(-step / 2.0, step * 29.5, 30, 1e-6).unwrap();
+        assert!((result.rows(), result.cols()) == (26, 30), "\nresult: {:?}\ntarget: {:?}", (result.rows(), result.cols()), (26, 30));
+        for j in 0..30i64 {
+            for row in 0..16 {
+                let value = *result.at_2d::<u8>(row, j as i32).unwrap();
+                let target = texel(ORIGIN + 20 - j, row as i64);
+                assert!(value == target, "upper column {}, row {} \nresult: {:?}\ntarget: {:?}", j, row, value, target);
+            }
+        }
+        // The lower camera stitched from the position 16 px only, nothing before it
+        for j in 0..30i64 {
+            for row in 0..10 {
+                let value = *result.at_2d::<u8>(16 + row, j as i32).unwrap();
+                let target = match j >= 16 {
+                    true => texel(ORIGIN + 20 - j, row as i64),
+                    false => 0,
+                };
+                assert!(value == target, "lower column {}, row {} \nresult: {:?}\ntarget: {:?}", j, row, value, target);
+            }
+        }
+        upper.read().flush().unwrap();
+        assert!(PanoramaStrip::path(std::env::temp_dir(), "panorama-test-upper").exists(), "index must be written");
+        remove(&upper.read(), "panorama-test-upper");
+        remove(&lower.read(), "panorama-test-lower");
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(test)]
+
+mod position {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, MatTrait, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, Direction, Encoder, EvalResult, InitialCtx, Position, PositionCtx, PositionFeed,
+            PositionSource, RopeTrack, Scale,
+        },
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the strand texture 64 x 128, circularly shifted along the rope by `shift` columns
+    fn texture(shift: i32) -> Mat {
+        let mut mat = Mat::new_rows_cols_with_default(64, 128, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..64 {
+            for col in 0..128 {
+                let x = (col - shift).rem_euclid(128) as u64;
+                let hash = (x * 2_654_435_761) ^ (row as u64 * 40_503);
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = ((hash >> 7) % 256) as u8;
+            }
+        }
+        mat
+    }
+    ///
+    /// Returns the context with the frame `mat` taken at the `timestamp`, ns
+    fn ctx(mat: Mat, timestamp: usize) -> MocEval {
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image { timestamp, ..Image::with(mat) })),
+        }
+    }
+    ///
+    /// Testing [Encoder] interpolating the counts at the frame timestamp
+    #[test]
+    fn encoder() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("position_encoder");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let encoder = Encoder::new(0.001, 3);
+        assert!(encoder.read(0).is_none(), "\nresult: {:?}\ntarget: None", encoder.read(0));
+        encoder.push(1_000, 0);
+        encoder.push(2_000, 100);
+        encoder.push(3_000, 300);
+        encoder.push(4_000, 400);
+        // step, timestamp, target position
+        let test_data = [
+            // Oldest count dropped, held at the first kept one
+            (1, 1_000, 0.1),
+            (2, 2_500, 0.2),
+            (3, 3_000, 0.3),
+            (4, 3_250, 0.325),
+            // Held at the last count
+            (5, 9_000, 0.4),
+        ];
+        for (step, timestamp, target) in test_data {
+            let result = encoder.read(timestamp).unwrap();
+            assert!((result - target).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` by the external feed only
+    #[test]
+    fn feed() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("position_feed");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let track = Arc::new(RwLock::new(RopeTrack::default()));
+        let feed: Arc<dyn PositionFeed> = Arc::new(MocFeed { speed: 2.0, until: None });
+        // step, timestamp, target position, target travel, target interval, target direction
+        let test_data = [
+            (1, 0, 0.0, 0.0, 0, Direction::Stationary),
+            (2, 1_000_000_000, 2.0, 2.0, 1_000_000_000, Direction::Forward),
+            (3, 1_000_000_000, 2.0, 0.0, 0, Direction::Stationary),
+            (4, 500_000_000, 1.0, -1.0, 0, Direction::Backward),
+        ];
+        for (step, timestamp, position, travel, interval, direction) in test_data {
+            let result = Position::new(None, Some(feed.clone()), 0.5, track.clone(), ctx(Mat::default(), timestamp)).eval(()).unwrap();
+            let result = ContextRead::<PositionCtx>::read(&result).clone();
+            let target = PositionCtx { position: Some(position), shift: None, travel, interval, direction, source: PositionSource::Feed, gap: false };
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` by the optical shift of the strand texture
+    #[test]
+    fn optical() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("position_optical");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let track = Arc::new(RwLock::new(RopeTrack::default()));
+        let scale = Some(Scale::new(0.5, None));
+        // step, texture shift, target source, target position, target direction
+        let test_data = [
+            // Nothing to register with
+            (1, 0, PositionSource::None, None, Direction::Stationary),
+            (2, 3, PositionSource::Optical, Some(0.0015), Direction::Forward),
+            (3, 3, PositionSource::Optical, Some(0.0015), Direction::Stationary),
+            (4, 1, PositionSource::Optical, Some(0.0005), Direction::Backward),
+        ];
+        for (step, shift, source, position, direction) in test_data {
+            let result = Position::new(scale, None, 0.5, track.clone(), ctx(texture(shift), 0)).eval(()).unwrap();
+            let result = ContextRead::<PositionCtx>::read(&result).clone();
+            assert!(result.source == source, "step {} \nresult: {:?}\ntarget: {:?}", step, result.source, source);
+            assert!(result.direction == direction, "step {} \nresult: {:?}\ntarget: {:?}", step, result.direction, direction);
+            match (result.position, position) {
+                (Some(result), Some(target)) => assert!(
+                    (result - target).abs() < 1e-4,
+                    "step {} \nresult: {:?}\ntarget: {:?}", step, result, target,
+                ),
+                (None, None) => {}
+                (result, target) => panic!("step {} \nresult: {:?}\ntarget: {:?}", step, result, target),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` over the gap of the position, the feed lost and nothing to register with
+    #[test]
+    fn gap() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("position_gap");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        // Feed lost after 1 s
+        let feed: Arc<dyn PositionFeed> = Arc::new(MocFeed { speed: 2.0, until: Some(1_000_000_000) });
+        let track = Arc::new(RwLock::new(RopeTrack::default()));
+        // step, timestamp, target source, target position, target gap
+        let test_data = [
+            (1, 0, PositionSource::Feed, Some(0.0), false),
+            (2, 500_000_000, PositionSource::Feed, Some(1.0), false),
+            // Extrapolated by the last speed of 2 m/s
+            (3, 1_500_000_000, PositionSource::Extrapolated, Some(3.0), true),
+            (4, 2_000_000_000, PositionSource::Extrapolated, Some(4.0), true),
+        ];
+        for (step, timestamp, source, position, gap) in test_data {
+            let result = Position::new(None, Some(feed.clone()), 0.5, track.clone(), ctx(Mat::default(), timestamp)).eval(()).unwrap();
+            let result = ContextRead::<PositionCtx>::read(&result).clone();
+            assert!(result.source == source, "step {} \nresult: {:?}\ntarget: {:?}", step, result.source, source);
+            assert!(result.gap == gap, "step {} \nresult: {:?}\ntarget: {:?}", step, result.gap, gap);
+            assert!(
+                result.position.zip(position).is_some_and(|(result, target)| (result - target).abs() < 1e-9),
+                "step {} \nresult: {:?}\ntarget: {:?}", step, result.position, position,
+            );
+        }
+        // Speed never measured, the position held at the previous one
+        let track = Arc::new(RwLock::new(RopeTrack::default()));
+        let feed: Arc<dyn PositionFeed> = Arc::new(MocFeed { speed: 2.0, until: Some(0) });
+        for (step, timestamp, source, position, gap) in [
+            (5, 0, PositionSource::Feed, Some(0.0), false),
+            (6, 500_000_000, PositionSource::None, Some(0.0), true),
+        ] {
+            let result = Position::new(None, Some(feed.clone()), 0.5, track.clone(), ctx(Mat::default(), timestamp)).eval(()).unwrap();
+            let result = ContextRead::<PositionCtx>::read(&result).clone();
+            let target = (source, position, gap, 0.0);
+            let result = (result.source, result.position, result.gap, result.travel);
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Feed of the rope moving with the constant `speed`, m/s, lost after the `until` timestamp, ns
+    struct MocFeed {
+        speed: f64,
+        until: Option<usize>,
+    }
+    //
+    //
+    impl PositionFeed for MocFeed {
+        fn read(&self, timestamp: usize) -> Option<f64> {
+            match self.until {
+                Some(until) if timestamp > until => None,
+                _ => Some(self.speed * timestamp as f64 / 1e9),
+            }
+        }
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+
+mod profile_defect {
+    use std::{
+        ops::Range,
+        sync::Once,
+        time::Duration
+    };
+    use opencv::core::{Mat, MatTrait, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, GeometryDefectType,
+            GrooveThreshold, InitialCtx, InitialPoints, MoundThreshold, ProfileDefect, ProfileDefectCtx,
+        },
+        domain::{Dot, Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the rope 20 px width with brightness 100,
+    /// where in the `cols` upper edge moved to the `edge` and `rows` filled with `value`
+    fn rope(cols: Range<usize>, edge: usize, rows: Range<usize>, value: f64) -> (Image, InitialPoints<f64>) {
+        let mut mat = Mat::new_rows_cols_with_default(60, 200, CV_8UC1, Scalar::all(100.0)).unwrap();
+        for col in cols.clone() {
+            for row in rows.clone() {
+                *mat.at_2d_mut::<u8>(row as i32, col as i32).unwrap() = value as u8;
+            }
+        }
+        let (upper, lower) = (0..200)
+            .map(|x| {
+                let y = if cols.contains(&x) { edge } else { 20 };
+                (Dot { x: x as f64, y: y as f64 }, Dot { x: x as f64, y: 40.0 })
+            })
+            .unzip();
+        (Image::with(mat), InitialPoints::new(upper, lower))
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("profile_defect");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, rope(90..100, 17, 17..25, 200.0), vec![GeometryDefectType::Mound]),
+            (2, rope(60..120, 21, 21..27, 60.0), vec![GeometryDefectType::Groove]),
+            (3, rope(0..0, 20, 0..0, 100.0), vec![]),
+        ];
+        for (step, (image, initial_points), target) in test_data {
+            let mut ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(image)
+                ),
+            };
+            ctx.ctx = ctx.ctx
+                .clone()
+                .write(EdgeDetectionCtx { result: initial_points, ..Default::default() })
+                .unwrap();
+            let result = ProfileDefect::new(GrooveThreshold::default(), MoundThreshold::default(), ctx).eval(());
+            match result {
+                Ok(result) => {
+                    let result: Vec<GeometryDefectType> = ContextRead::<ProfileDefectCtx>::read(&result).result.iter()
+                        .map(|defect| defect.kind.clone())
+                        .collect();
+                    assert!(
+                        result == target,
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        result,
+                        target
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
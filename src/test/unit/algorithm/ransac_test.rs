@@ -0,0 +1,70 @@
+#[cfg(test)]
+
+mod ransac {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{Line, Ransac},
+        domain::{Dot, Eval},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns dots of the `line`, shifted by `outlier` in the `outliers` range of x
+    fn dots(line: Line, outliers: std::ops::Range<usize>, outlier: f64) -> Vec<Dot<f64>> {
+        (0..100)
+            .map(|x| {
+                let shift = if outliers.contains(&x) { outlier } else { 0.0 };
+                Dot { x: x as f64, y: line.y(x as f64) + shift }
+            })
+            .collect()
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("ransac");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, dots(Line::new(0.2, 10.0), 0..0, 0.0), Line::new(0.2, 10.0)),
+            (2, dots(Line::new(0.2, 10.0), 40..55, 12.0), Line::new(0.2, 10.0)),
+            (3, dots(Line::new(-0.1, 50.0), 80..100, -8.0), Line::new(-0.1, 50.0)),
+            (4, vec![], Line::default()),
+        ];
+        for (step, dots, target) in test_data {
+            let result = Ransac::new(100, 2.0).eval(dots);
+            assert!(
+                (result.slope - target.slope).abs() < 1e-9 && (result.intercept - target.intercept).abs() < 1e-9,
+                "step {} \nresult: {:?}\ntarget: {:?}",
+                step,
+                result,
+                target
+            );
+        }
+        test_duration.exit();
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+
+mod roi {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::{
+        core::{Mat, MatTraitConst, Point, Scalar, CV_8UC3},
+        imgproc,
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{Context, ContextRead, ContextWrite, EvalResult, IlluminationCtx, InitialCtx, Roi, RoiBand, RoiCtx},
+        domain::{Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the frame 200 x 200 with the horizontal rope on the rows `top..bottom`,
+    /// without the rope if `top == bottom`
+    fn rope(top: i32, bottom: i32) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(200, 200, CV_8UC3, Scalar::all(0.0)).unwrap();
+        if bottom > top {
+            imgproc::rectangle_points(
+                &mut mat,
+                Point::new(0, top),
+                Point::new(199, bottom - 1),
+                Scalar::all(200.0),
+                imgproc::FILLED,
+                imgproc::LINE_8,
+                0,
+            ).unwrap();
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Testing `eval` over the sequence of frames sharing the rope band
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("roi");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let band = Arc::new(RwLock::new(RoiBand::default()));
+        let test_data = [
+            // Located over the whole frame
+            (1, band.clone(), rope(80, 120), RoiCtx { y: 69, width: 200, height: 62, tracked: false }),
+            // Found near the band of the previous frame
+            (2, band.clone(), rope(80, 120), RoiCtx { y: 69, width: 200, height: 62, tracked: true }),
+            (3, band.clone(), rope(90, 130), RoiCtx { y: 79, width: 200, height: 62, tracked: true }),
+            // Lost near the previous band, relocated
+            (4, band.clone(), rope(20, 60), RoiCtx { y: 9, width: 200, height: 62, tracked: false }),
+            // No rope, the full frame passed
+            (5, Arc::new(RwLock::new(RoiBand::default())), rope(0, 0), RoiCtx { y: 0, width: 200, height: 200, tracked: false }),
+        ];
+        for (step, band, image, target) in test_data {
+            let ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(image)
+                ),
+            };
+            let result = Roi::new(10, band, ctx).eval(());
+            match result {
+                Ok(result) => {
+                    let roi = ContextRead::<RoiCtx>::read(&result);
+                    assert!(*roi == target, "step {} \nresult: {:?}\ntarget: {:?}", step, roi, target);
+                    let frame = &ContextRead::<InitialCtx>::read(&result).src_frame.mat;
+                    let size = (frame.cols() as usize, frame.rows() as usize);
+                    assert!(
+                        size == (target.width, target.height),
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        size,
+                        (target.width, target.height)
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` locates the band on the normalised frame and crops the both frames
+    #[test]
+    fn normalised() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("roi_normalised");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        // The rope too dark to be seen on the source frame
+        let ctx = MocEval {
+            ctx: Context::new(InitialCtx::new(rope(0, 0)))
+                .write(IlluminationCtx { frame: rope(80, 120), ..Default::default() })
+                .unwrap(),
+        };
+        let result = Roi::new(10, Arc::new(RwLock::new(RoiBand::default())), ctx).eval(()).unwrap();
+        let target = RoiCtx { y: 69, width: 200, height: 62, tracked: false };
+        let roi = ContextRead::<RoiCtx>::read(&result);
+        assert!(*roi == target, "\nresult: {:?}\ntarget: {:?}", roi, target);
+        for frame in [&ContextRead::<InitialCtx>::read(&result).src_frame, &ContextRead::<IlluminationCtx>::read(&result).frame] {
+            let size = (frame.mat.cols() as usize, frame.mat.rows() as usize);
+            assert!(size == (target.width, target.height), "\nresult: {:?}\ntarget: {:?}", size, (target.width, target.height));
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+
+mod rope_axis {
+    use std::{
+        f64::consts::PI,
+        sync::Once,
+        time::Duration
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, GeometryDefectType,
+            InitialCtx, InitialPoints, RoiCtx, RopeAxis, RopeAxisCtx, Waviness,
+        },
+        domain::{Dot, Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns edges of the rope 40 px width, with the dynamic axis `center(x)`
+    fn edges(center: impl Fn(f64) -> f64) -> InitialPoints<f64> {
+        let (upper, lower) = (0..200)
+            .map(|x| {
+                let (x, y) = (x as f64, center(x as f64).round());
+                (Dot { x, y: y - 20.0 }, Dot { x, y: y + 20.0 })
+            })
+            .unzip();
+        InitialPoints::new(upper, lower)
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("rope_axis");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (1, edges(|x| 50.0 + 4.0 * (2.0 * PI * x / 50.0).sin()), Some(GeometryDefectType::Waviness)),
+            (2, edges(|x| 50.0 + 0.1 * x), None),
+            (3, edges(|_| 50.0), None),
+        ];
+        for (step, initial_points, target) in test_data {
+            let mut ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(
+                        Image::default()
+                    )
+                ),
+            };
+            ctx.ctx = ctx.ctx
+                .clone()
+                .write(EdgeDetectionCtx { result: initial_points, ..Default::default() })
+                .unwrap()
+                .write(RoiCtx { y: 30, width: 200, height: 100, tracked: true })
+                .unwrap();
+            let result = RopeAxis::new(Waviness::RUNNING, ctx).eval(());
+            match result {
+                Ok(result) => {
+                    let result = ContextRead::<RopeAxisCtx>::read(&result);
+                    log::debug!("step {} | waviness: {:?}", step, result.waviness);
+                    assert!(
+                        result.result.as_ref().map(|defect| &defect.kind) == target.as_ref(),
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        result.result,
+                        target
+                    );
+                    // Static axis moved onto the aligned frame by the rows cropped
+                    let (axis, frame) = (result.static_axis, result.frame_axis);
+                    assert!(
+                        frame.slope == axis.slope && (frame.intercept - axis.intercept - 30.0).abs() < 1e-9,
+                        "step {} \nresult: {:?}\ntarget: {:?} + 30 rows", step, frame, axis,
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
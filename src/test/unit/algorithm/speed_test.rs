@@ -0,0 +1,129 @@
+#[cfg(test)]
+
+mod speed {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use opencv::core::{Mat, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, Direction, EvalResult, InitialCtx, PositionCtx, PositionSource,
+            Scale, Speed, SpeedCtx,
+        },
+        domain::{Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns the context of the frame 100 px wide with the `position` of the frame
+    fn ctx(position: PositionCtx) -> MocEval {
+        let mat = Mat::new_rows_cols_with_default(20, 100, CV_8UC1, Scalar::all(0.0)).unwrap();
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::with(mat)))
+                .write(position)
+                .unwrap(),
+        }
+    }
+    ///
+    /// Testing `eval` measuring speed, direction and coverage of the rope
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("speed");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let scale = Scale::new(0.5, None);
+        let overlap = 0.2;
+        // step, source, shift, travel, interval, target (speed, rate, direction, stationary, overlap, limit, covered)
+        let test_data = [
+            // First frame, nothing to compare with
+            (1, PositionSource::None, None, 0.0, 0, SpeedCtx::default()),
+            // Optical shift forward and backward, the speed limited by the half of the frame the shift measured within
+            (2, PositionSource::Optical, Some(10.0), 0.0, 100_000_000,
+                SpeedCtx { speed: Some(50.0), rate: 100.0, direction: Direction::Forward, stationary: false, overlap: Some(0.9), limit: Some(250.0), covered: true }),
+            (3, PositionSource::Optical, Some(-10.0), 0.0, 100_000_000,
+                SpeedCtx { speed: Some(-50.0), rate: -100.0, direction: Direction::Backward, stationary: false, overlap: Some(0.9), limit: Some(250.0), covered: true }),
+            // Shift below the half of the pixel, the rope is stopped
+            (4, PositionSource::Optical, Some(0.2), 0.0, 100_000_000,
+                SpeedCtx { speed: Some(1.0), rate: 2.0, direction: Direction::Stationary, stationary: true, overlap: Some(0.998), limit: Some(250.0), covered: true }),
+            // Feed travel 45 mm = 90 px, beyond the optical registration, frames overlap by 10 % only
+            (5, PositionSource::Feed, None, 0.045, 100_000_000,
+                SpeedCtx { speed: Some(450.0), rate: 900.0, direction: Direction::Forward, stationary: false, overlap: Some(0.1), limit: Some(400.0), covered: false }),
+            // Feed travel 60 mm = 120 px, frames don't overlap
+            (6, PositionSource::Feed, None, 0.06, 100_000_000,
+                SpeedCtx { speed: Some(600.0), rate: 1200.0, direction: Direction::Forward, stationary: false, overlap: Some(-0.2), limit: Some(400.0), covered: false }),
+            // Registration failed, the travel unknown, the gap can't be excluded
+            (7, PositionSource::Optical, None, 0.0, 100_000_000,
+                SpeedCtx { speed: None, rate: 0.0, direction: Direction::Stationary, stationary: false, overlap: None, limit: Some(250.0), covered: false }),
+            // Position gap, the travel 5 mm = 10 px extrapolated only, the gap can't be excluded
+            (8, PositionSource::Extrapolated, None, 0.005, 100_000_000,
+                SpeedCtx { speed: Some(50.0), rate: 100.0, direction: Direction::Forward, stationary: false, overlap: Some(0.9), limit: Some(400.0), covered: false }),
+            // Feed travel 45 mm = 90 px fused with the optical shift aliased to -10 px, the overlap by the feed
+            (9, PositionSource::Fused, Some(-10.0), 0.045, 100_000_000,
+                SpeedCtx { speed: Some(450.0), rate: 900.0, direction: Direction::Forward, stationary: false, overlap: Some(0.1), limit: Some(400.0), covered: false }),
+        ];
+        for (step, source, shift, travel, interval, target) in test_data {
+            let gap = source == PositionSource::Extrapolated;
+            let position = PositionCtx { shift, travel, interval, source, gap, ..Default::default() };
+            let result = Speed::new(Some(scale), overlap, ctx(position)).eval(()).unwrap();
+            let result = ContextRead::<SpeedCtx>::read(&result).clone();
+            assert!(eq(result.speed, target.speed), "step {} \nresult: {:?}\ntarget: {:?}", step, result.speed, target.speed);
+            assert!((result.rate - target.rate).abs() < 1e-9, "step {} \nresult: {:?}\ntarget: {:?}", step, result.rate, target.rate);
+            assert!(result.direction == target.direction, "step {} \nresult: {:?}\ntarget: {:?}", step, result.direction, target.direction);
+            assert!(result.stationary == target.stationary, "step {} \nresult: {:?}\ntarget: {:?}", step, result.stationary, target.stationary);
+            assert!(eq(result.overlap, target.overlap), "step {} \nresult: {:?}\ntarget: {:?}", step, result.overlap, target.overlap);
+            assert!(eq(result.limit, target.limit), "step {} \nresult: {:?}\ntarget: {:?}", step, result.limit, target.limit);
+            assert!(result.covered == target.covered, "step {} \nresult: {:?}\ntarget: {:?}", step, result.covered, target.covered);
+        }
+        // Without the camera scale the speed measured on the frame only
+        let position = PositionCtx { shift: Some(10.0), interval: 100_000_000, source: PositionSource::Optical, ..Default::default() };
+        let result = Speed::new(None, overlap, ctx(position)).eval(()).unwrap();
+        let result = ContextRead::<SpeedCtx>::read(&result).clone();
+        assert!(result.speed.is_none() && result.limit.is_none(), "\nresult: {:?}\ntarget: speed and limit unknown", result);
+        assert!((result.rate - 100.0).abs() < 1e-9, "\nresult: {:?}\ntarget: {:?}", result.rate, 100.0);
+        test_duration.exit();
+    }
+    ///
+    /// Returns true if both values are unknown or equal
+    fn eq(result: Option<f64>, target: Option<f64>) -> bool {
+        match (result, target) {
+            (Some(result), Some(target)) => (result - target).abs() < 1e-9,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
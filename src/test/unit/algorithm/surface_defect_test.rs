@@ -0,0 +1,193 @@
+#[cfg(test)]
+
+mod surface_defect {
+    use std::{
+        sync::Once,
+        time::Duration
+    };
+    use opencv::{
+        core::{Mat, MatTrait, MatTraitConst, Scalar, Vec3b, CV_8UC1, CV_8UC3},
+        imgproc,
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult, InitialCtx, InitialPoints,
+            SurfaceDefect, SurfaceDefectCtx, SurfaceDefectType, SurfaceRegion, SurfaceThreshold,
+        },
+        domain::{Dot, Eval, Image},
+        infrostructure::arena::PixelFormat,
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Returns gray 10x20 image, where rope band (rows 2...7) in the columns `rust` has a rust tint
+    fn image(rust: std::ops::Range<i32>) -> Image {
+        let mut mat = Mat::new_rows_cols_with_default(10, 20, CV_8UC3, Scalar::all(128.0)).unwrap();
+        for col in rust {
+            for row in 2..=7 {
+                *mat.at_2d_mut::<Vec3b>(row, col).unwrap() = Vec3b::from_array([40, 70, 160]);
+            }
+        }
+        Image::with(mat)
+    }
+    ///
+    /// Returns the context of the `image` with the rope band between the rows `upper` and `lower`
+    fn ctx(image: Image, upper: f64, lower: f64) -> MocEval {
+        MocEval {
+            ctx: Context::new(InitialCtx::new(image))
+                .write(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        (0..20).map(|x| Dot { x: x as f64, y: upper }).collect(),
+                        (0..20).map(|x| Dot { x: x as f64, y: lower }).collect(),
+                    ),
+                    ..Default::default()
+                })
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns the raw RGGB mosaic of the BGR `image`, as captured by the BayerRG camera
+    fn mosaic(image: &Image) -> Mat {
+        let mut mat = Mat::new_rows_cols_with_default(image.mat.rows(), image.mat.cols(), CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..image.mat.rows() {
+            for col in 0..image.mat.cols() {
+                let [b, g, r] = image.mat.at_2d::<Vec3b>(row, col).unwrap().0;
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = match (row % 2, col % 2) {
+                    (0, 0) => r,
+                    (1, 1) => b,
+                    _ => g,
+                };
+            }
+        }
+        mat
+    }
+    ///
+    /// Testing `eval` on the frame of the Bayer camera, the rust tint seen on the demosaiced frame only
+    #[test]
+    fn bayer() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("surface_defect_bayer");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let raw = mosaic(&image(0..20));
+        let mut demosaiced = Mat::default();
+        imgproc::cvt_color(&raw, &mut demosaiced, PixelFormat::BayerRG8.demosaic().unwrap(), 3).unwrap();
+        // Band inside the rust tinted rows, so the demosaicing interpolates the tint only
+        // step, frame, target kinds of the regions
+        let test_data = [
+            // Demosaiced, the rust tint restored
+            (1, demosaiced, vec![(SurfaceDefectType::Corrosion, 0, 19)]),
+            // Raw mosaic taken as monochrome, the rust tint lost
+            (2, raw, vec![]),
+        ];
+        for (step, mat, target) in test_data {
+            let result = SurfaceDefect::new(5, SurfaceThreshold::default(), ctx(Image::with(mat), 3.0, 6.0)).eval(()).unwrap();
+            let result: Vec<_> = ContextRead::<SurfaceDefectCtx>::read(&result)
+                .result
+                .iter()
+                .map(|region| (region.kind, region.start, region.end))
+                .collect();
+            assert!(result == target, "step {} \nresult: {:?}\ntarget: {:?}", step, result, target);
+        }
+        // Not a Bayer format
+        let result = PixelFormat::BGR8.demosaic();
+        assert!(result.is_none(), "\nresult: {:?}\ntarget: None", result);
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval`
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("surface_defect");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let test_data = [
+            (
+                1,
+                image(0..10),
+                vec![
+                    SurfaceRegion { kind: SurfaceDefectType::Corrosion, start: 0, end: 9, score: 0.5 },
+                ],
+            ),
+            (
+                2,
+                image(0..0),
+                vec![],
+            ),
+        ];
+        for (step, image, target) in test_data {
+            let mut ctx = MocEval {
+                ctx: Context::new(
+                    InitialCtx::new(image)
+                ),
+            };
+            ctx.ctx = ctx.ctx
+                .clone()
+                .write(EdgeDetectionCtx {
+                    result: InitialPoints::new(
+                        (0..20).map(|x| Dot { x: x as f64, y: 2.0 }).collect(),
+                        (0..20).map(|x| Dot { x: x as f64, y: 7.0 }).collect(),
+                    ),
+                    ..Default::default()
+                })
+                .unwrap();
+            let result = SurfaceDefect::new(
+                5,
+                SurfaceThreshold::default(),
+                ctx,
+            ).eval(());
+            match result {
+                Ok(result) => {
+                    let result = ContextRead::<SurfaceDefectCtx>::read(&result)
+                        .result.clone();
+                    assert!(
+                        result == target,
+                        "step {} \nresult: {:?}\ntarget: {:?}",
+                        step,
+                        result,
+                        target
+                    );
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
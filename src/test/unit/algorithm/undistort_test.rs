@@ -0,0 +1,256 @@
+#[cfg(test)]
+
+mod undistort {
+    use std::{
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::{
+        core::{copy_make_border, Mat, MatTrait, MatTraitConst, Scalar, Size, BORDER_CONSTANT, CV_8UC1},
+        imgproc,
+    };
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{CameraCalibration, ContextRead, EvalResult, Context, InitialCtx, Intrinsics, Undistort, UndistortMap},
+        domain::{Eval, Image},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    ///
+    /// Testing `eval` without lens distortion, frame must stay the same
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("undistort");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let mut mat = Mat::new_rows_cols_with_default(20, 30, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..20 {
+            for col in 0..30 {
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = (row * 10 + col) as u8;
+            }
+        }
+        let test_data = [
+            (
+                1,
+                Intrinsics {
+                    camera_matrix: [100.0, 0.0, 15.0, 0.0, 100.0, 10.0, 0.0, 0.0, 1.0],
+                    dist_coeffs: vec![0.0, 0.0, 0.0, 0.0, 0.0],
+                    width: 30,
+                    height: 20,
+                    error: 0.0,
+                },
+                mat,
+            ),
+        ];
+        for (step, intrinsics, target) in test_data {
+            let map = Arc::new(UndistortMap::new(&intrinsics).unwrap());
+            let result = Undistort::new(
+                Some(map),
+                MocEval { ctx: Context::new(InitialCtx::new(Image::with(target.clone()))) },
+            ).eval(());
+            match result {
+                Ok(result) => {
+                    let result = &ContextRead::<InitialCtx>::read(&result).src_frame.mat;
+                    for row in 0..20 {
+                        for col in 0..30 {
+                            let value = *result.at_2d::<u8>(row, col).unwrap();
+                            let target = *target.at_2d::<u8>(row, col).unwrap();
+                            assert!(
+                                value == target,
+                                "step {} row {} col {} \nresult: {:?}\ntarget: {:?}",
+                                step, row, col,
+                                value,
+                                target
+                            );
+                        }
+                    }
+                },
+                Err(err) => panic!("step {} \nerror: {:#?}", step, err),
+            }
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Returns the frame 30 x 20 of the horizontal gradient, 8 per column
+    fn gradient() -> Mat {
+        let mut mat = Mat::new_rows_cols_with_default(20, 30, CV_8UC1, Scalar::all(0.0)).unwrap();
+        for row in 0..20 {
+            for col in 0..30 {
+                *mat.at_2d_mut::<u8>(row, col).unwrap() = (col * 8) as u8;
+            }
+        }
+        mat
+    }
+    ///
+    /// Testing `eval` with the radial distortion, the frame size checked against the calibration
+    #[test]
+    fn distortion() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("undistort_distortion");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(1));
+        test_duration.run().unwrap();
+        let (f, cx, cy, k1) = (20.0, 15.0, 10.0, 0.1);
+        let intrinsics = Intrinsics {
+            camera_matrix: [f, 0.0, cx, 0.0, f, cy, 0.0, 0.0, 1.0],
+            dist_coeffs: vec![k1, 0.0, 0.0, 0.0, 0.0],
+            width: 30,
+            height: 20,
+            error: 0.0,
+        };
+        let map = Arc::new(UndistortMap::new(&intrinsics).unwrap());
+        let result = Undistort::new(Some(map.clone()), MocEval { ctx: Context::new(InitialCtx::new(Image::with(gradient()))) })
+            .eval(())
+            .unwrap();
+        let result = &ContextRead::<InitialCtx>::read(&result).src_frame.mat;
+        // step, column, row of the corrected frame
+        let test_data = [
+            // Center isn't moved
+            (1, 15, 10),
+            // Barrel distortion, the pixel taken further from the center
+            (2, 25, 10),
+            (3, 5, 10),
+            (4, 25, 5),
+            (5, 20, 15),
+        ];
+        for (step, col, row) in test_data {
+            let (x, y) = ((col as f64 - cx) / f, (row as f64 - cy) / f);
+            let source = cx + f * x * (1.0 + k1 * (x * x + y * y));
+            let target = 8.0 * source;
+            let value = *result.at_2d::<u8>(row, col).unwrap() as f64;
+            assert!((value - target).abs() <= 1.0, "step {} \nresult: {:?}\ntarget: {:?}", step, value, target);
+        }
+        // Frame of the other resolution rejected
+        let frame = Mat::new_rows_cols_with_default(20, 40, CV_8UC1, Scalar::all(0.0)).unwrap();
+        let result = Undistort::new(Some(map), MocEval { ctx: Context::new(InitialCtx::new(Image::with(frame))) }).eval(());
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result.map(|_| ()));
+        // Camera not calibrated, frame passed as is
+        let result = Undistort::new(None, MocEval { ctx: Context::new(InitialCtx::new(Image::with(gradient()))) })
+            .eval(())
+            .unwrap();
+        let result = *ContextRead::<InitialCtx>::read(&result).src_frame.mat.at_2d::<u8>(10, 25).unwrap();
+        assert!(result == 200, "\nresult: {:?}\ntarget: {:?}", result, 200);
+        test_duration.exit();
+    }
+    ///
+    /// Returns the checkerboard of 9 x 7 squares of 40 px, 40 px white margin
+    fn board() -> Mat {
+        let mut mat = Mat::new_rows_cols_with_default(360, 440, CV_8UC1, Scalar::all(255.0)).unwrap();
+        for row in 0..280 {
+            for col in 0..360 {
+                if (row / 40 + col / 40) % 2 == 0 {
+                    *mat.at_2d_mut::<u8>(row + 40, col + 40).unwrap() = 0;
+                }
+            }
+        }
+        mat
+    }
+    ///
+    /// Returns the image 640 x 480 of the `board` seen by the pinhole camera with the focal length `f`, px,
+    /// the board, 1 mm per px, tilted by `a` about the x axis and by `b` about the y axis, rad, its center 625 mm ahead
+    fn view(board: &Mat, f: f64, a: f64, b: f64) -> Image {
+        let k = [[f, 0.0, 320.0], [0.0, f, 240.0], [0.0, 0.0, 1.0]];
+        let (sa, ca, sb, cb) = (a.sin(), a.cos(), b.sin(), b.cos());
+        // R = Ry(b) * Rx(a)
+        let r = [[cb, sb * sa, sb * ca], [0.0, ca, -sa], [-sb, cb * sa, cb * ca]];
+        let center = [220.0, 180.0];
+        let t: Vec<f64> = (0..3).map(|i| [0.0, 0.0, 625.0][i] - r[i][0] * center[0] - r[i][1] * center[1]).collect();
+        let columns = [[r[0][0], r[1][0], r[2][0]], [r[0][1], r[1][1], r[2][1]], [t[0], t[1], t[2]]];
+        let mut h = [[0.0; 3]; 3];
+        for (j, column) in columns.iter().enumerate() {
+            for i in 0..3 {
+                h[i][j] = (0..3).map(|m| k[i][m] * column[m]).sum();
+            }
+        }
+        let h = Mat::from_slice_2d(&h).unwrap();
+        let mut dst = Mat::default();
+        imgproc::warp_perspective(
+            board, &mut dst, &h, Size::new(640, 480), imgproc::INTER_LINEAR, BORDER_CONSTANT, Scalar::all(255.0),
+        ).unwrap();
+        Image::with(dst)
+    }
+    ///
+    /// Testing [CameraCalibration] on the synthetic views of the checkerboard
+    #[test]
+    fn calibration() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("undistort_calibration");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(30));
+        test_duration.run().unwrap();
+        let board = board();
+        let f = 500.0;
+        let deg = std::f64::consts::PI / 180.0;
+        let images: Vec<Image> = [(20.0, 0.0), (-20.0, 0.0), (0.0, 20.0), (0.0, -20.0), (15.0, 15.0)]
+            .into_iter()
+            .map(|(a, b)| view(&board, f, a * deg, b * deg))
+            .collect();
+        let calibration = CameraCalibration::new((8, 6), 40.0);
+        let result = calibration.eval(images).unwrap();
+        let size = (result.width, result.height);
+        assert!(size == (640, 480), "\nresult: {:?}\ntarget: {:?}", size, (640, 480));
+        assert!(result.error < 0.5, "\nresult: {:?}\ntarget: < 0.5 px", result.error);
+        let focal = (result.camera_matrix[0], result.camera_matrix[4]);
+        assert!(
+            (focal.0 - f).abs() < 0.05 * f && (focal.1 - f).abs() < 0.05 * f,
+            "\nresult: {:?}\ntarget: {:?} ± 5 %", focal, f,
+        );
+        // Images of the other size skipped, the checkerboard left on too few images
+        let padded = |image: Image| {
+            let mut mat = Mat::default();
+            copy_make_border(&image.mat, &mut mat, 0, 120, 0, 160, BORDER_CONSTANT, Scalar::all(255.0)).unwrap();
+            Image::with(mat)
+        };
+        let images = vec![
+            view(&board, f, 20.0 * deg, 0.0),
+            view(&board, f, -20.0 * deg, 0.0),
+            padded(view(&board, f, 0.0, 20.0 * deg)),
+            padded(view(&board, f, 0.0, -20.0 * deg)),
+        ];
+        let result = calibration.eval(images);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        // Checkerboard found on too few images
+        let blank = Mat::new_rows_cols_with_default(480, 640, CV_8UC1, Scalar::all(255.0)).unwrap();
+        let images = vec![view(&board, f, 0.0, 0.0), Image::with(blank)];
+        let result = calibration.eval(images);
+        assert!(result.is_err(), "\nresult: {:?}\ntarget: Err", result);
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+
+mod vibration {
+    use std::{
+        f64::consts::PI,
+        sync::{Arc, Once},
+        time::Duration
+    };
+    use opencv::core::{Mat, Scalar, CV_8UC1};
+    use sal_core::dbg::Dbg;
+    use testing::stuff::max_test_duration::TestDuration;
+    use debugging::session::debug_session::{
+        DebugSession, LogLevel, Backtrace,
+    };
+    use crate::{
+        algorithm::{
+            Context, ContextRead, ContextWrite, EvalResult, InitialCtx, RoiCtx, RopeAxisCtx, Scale, Vibration,
+            VibrationConf, VibrationCtx, VibrationHistory,
+        },
+        domain::{Dot, Eval, Image, RwLock},
+    };
+    ///
+    ///
+    static INIT: Once = Once::new();
+    ///
+    /// once called initialisation
+    fn init_once() {
+        INIT.call_once(|| {
+            // implement your initialisation code to be called only once for current test file
+        })
+    }
+    ///
+    /// returns:
+    ///  - ...
+    fn init_each() -> () {}
+    /// Interval between the frames, ns, 25 fps
+    const INTERVAL: usize = 40_000_000;
+    /// Size of the pixel on the rope surface, mm
+    const MM_PER_PX: f64 = 0.5;
+    ///
+    /// Returns the context of the frame `index`, the rope axis at the `axis` row of the cropped strip, None if the rope isn't found
+    fn ctx(index: usize, axis: Option<f64>) -> MocEval {
+        let mat = Mat::new_rows_cols_with_default(10, 100, CV_8UC1, Scalar::all(0.0)).unwrap();
+        let dynamic = axis.map_or(vec![], |y| (0..100).map(|x| Dot { x: x as f64, y: y + (x % 2) as f64 - 0.5 }).collect());
+        MocEval {
+            ctx: Context::new(InitialCtx::new(Image::new(100, 10, mat, 1_000_000_000 + index * INTERVAL)))
+                .write(RoiCtx { y: 20, height: 100, width: 100, ..Default::default() })
+                .unwrap()
+                .write(RopeAxisCtx { dynamic, ..Default::default() })
+                .unwrap(),
+        }
+    }
+    ///
+    /// Returns the result of the last of the `frames`, the rope swinging by the `amplitude`, px,
+    /// with the `frequency`, Hz, the rope not found on the `missed` frames
+    fn swing(conf: VibrationConf, frames: usize, amplitude: f64, frequency: f64, missed: &[usize]) -> VibrationCtx {
+        let history = Arc::new(RwLock::new(VibrationHistory::default()));
+        let mut result = VibrationCtx::default();
+        for index in 0..frames {
+            let t = (index * INTERVAL) as f64 / 1e9;
+            let axis = (!missed.contains(&index)).then(|| 50.0 + amplitude * (2.0 * PI * frequency * t).sin());
+            let ctx = Vibration::new(conf, Some(Scale::new(MM_PER_PX, None)), history.clone(), ctx(index, axis))
+                .eval(())
+                .unwrap();
+            result = ContextRead::<VibrationCtx>::read(&ctx).clone();
+        }
+        result
+    }
+    ///
+    /// Testing `eval` measuring the amplitude and the frequency of the lateral swing
+    #[test]
+    fn eval() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("vibration");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = VibrationConf { window: 32, limit: 5.0 };
+        // 4 cycles over the window of 32 frames at 25 fps
+        let frequency = 3.125;
+        // step, frames, amplitude, target (amplitude, frequency, reliable)
+        let test_data = [
+            // Rope still
+            (1, 32, 0.0, (0.0, None, true)),
+            // Too few frames for the frequency, a half of the cycle seen
+            (2, 5, 3.0, (1.5, None, true)),
+            // Swing within the limit
+            (3, 32, 3.0, (3.0, Some(frequency), true)),
+            // Window slides over the older frames
+            (4, 96, 3.0, (3.0, Some(frequency), true)),
+            // Swing over the limit, geometry unreliable
+            (5, 32, 8.0, (8.0, Some(frequency), false)),
+        ];
+        for (step, frames, amplitude, (target_amplitude, target_frequency, reliable)) in test_data {
+            let result = swing(conf, frames, amplitude, frequency, &[]);
+            assert!(
+                (result.amplitude - target_amplitude).abs() < 1e-9,
+                "step {} \nresult: {:?}\ntarget: {:?}", step, result.amplitude, target_amplitude,
+            );
+            let amplitude_mm = Some(target_amplitude * MM_PER_PX);
+            assert!(
+                result.amplitude_mm.zip(amplitude_mm).is_some_and(|(a, b)| (a - b).abs() < 1e-9),
+                "step {} \nresult: {:?}\ntarget: {:?}", step, result.amplitude_mm, amplitude_mm,
+            );
+            let matched = match (result.frequency, target_frequency) {
+                (Some(result), Some(target)) => (result - target).abs() < 1e-6,
+                (result, target) => result == target,
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result.frequency, target_frequency);
+            let velocity_mm = result.velocity.map(|velocity| velocity * MM_PER_PX);
+            let matched = match (result.velocity_mm, velocity_mm) {
+                (Some(result), Some(target)) => (result - target).abs() < 1e-9,
+                (result, target) => result == target,
+            };
+            assert!(matched, "step {} \nresult: {:?}\ntarget: {:?}", step, result.velocity_mm, velocity_mm);
+            assert!(result.reliable == reliable, "step {} \nresult: {:?}\ntarget: {:?}", step, result.reliable, reliable);
+            // Axis on the aligned frame, shifted by the Roi
+            let axis = result.axis.unwrap();
+            assert!((axis - 70.0).abs() <= target_amplitude + 1e-9, "step {} \nresult: {:?}\ntarget: 70 ± {:?}", step, axis, target_amplitude);
+        }
+        test_duration.exit();
+    }
+    ///
+    /// Testing `eval` on the frames without the rope, no measurement, the history and the reliability kept
+    #[test]
+    fn missed() {
+        DebugSession::init(LogLevel::Debug, Backtrace::Short);
+        init_once();
+        init_each();
+        let dbg = Dbg::own("vibration_missed");
+        log::debug!("\n{}", dbg);
+        let test_duration = TestDuration::new(&dbg, Duration::from_secs(10));
+        test_duration.run().unwrap();
+        let conf = VibrationConf { window: 32, limit: 5.0 };
+        let result = swing(conf, 33, 8.0, 3.125, &[32]);
+        assert!(result.axis.is_none(), "\nresult: {:?}\ntarget: None", result.axis);
+        let target = VibrationCtx { reliable: false, ..Default::default() };
+        assert!(result == target, "\nresult: {:?}\ntarget: {:?}", result, target);
+        // The rope found again, measured over the history kept
+        let result = swing(conf, 34, 8.0, 3.125, &[32]);
+        assert!(result.axis.is_some(), "\nresult: {:?}\ntarget: Some", result.axis);
+        assert!((result.amplitude - 8.0).abs() < 0.1, "\nresult: {:?}\ntarget: {:?}", result.amplitude, 8.0);
+        assert!(!result.reliable, "\nresult: {:?}\ntarget: {:?}", result.reliable, false);
+        let result = swing(conf, 1, 0.0, 3.125, &[0]);
+        assert!(result == VibrationCtx::default(), "\nresult: {:?}\ntarget: {:?}", result, VibrationCtx::default());
+        test_duration.exit();
+    }
+    ///
+    ///
+    #[derive(Debug, Clone)]
+    struct MocEval {
+        pub ctx: Context,
+    }
+    //
+    //
+    impl Eval<(), EvalResult> for MocEval {
+        fn eval(&self, _: ()) -> EvalResult {
+            Result::Ok(self.ctx.clone())
+        }
+    }
+}
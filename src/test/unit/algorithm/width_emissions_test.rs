@@ -11,8 +11,8 @@ mod width_emissions {
     };
     use crate::{
         algorithm::{
-            Bond, Context, ContextRead, ContextWrite, EdgeDetectionCtx, EvalResult,
-            InitialCtx, InitialPoints, Mad, Threshold, WidthEmissions, WidthEmissionsCtx,
+            Context, ContextRead, ContextWrite, DefectSide, EdgeDetectionCtx, EmissionSegment, EvalResult,
+            InitialCtx, InitialPoints, Mad, Ransac, Threshold, WidthEmissions, WidthEmissionsCtx,
         },
         domain::{Dot, Eval, Image},
     };
@@ -46,42 +46,109 @@ mod width_emissions {
                 1,
                 Threshold(1.1),
                 InitialPoints::new(
-                    vec![
-                        Dot { x: 10  , y: 100 },
-                        Dot { x: 20  , y: 105 },
-                        Dot { x: 30  , y: 110 },
-                        Dot { x: 40  , y: 120 },
-                        Dot { x: 50  , y: 130 },
-                        Dot { x: 60  , y: 135 },
-                        Dot { x: 70  , y: 130 },
-                        Dot { x: 80  , y: 120 },
-                        Dot { x: 90  , y: 110 },
-                        Dot { x: 100 , y: 105 },
-                        Dot { x: 110 , y: 100 },
-                    ],
-                    vec![
-                        Dot { x: 10  , y: 50 },
-                        Dot { x: 20  , y: 45 },
-                        Dot { x: 30  , y: 40 },
-                        Dot { x: 40  , y: 30 },
-                        Dot { x: 50  , y: 20 },
-                        Dot { x: 60  , y: 15 },
-                        Dot { x: 70  , y: 20 },
-                        Dot { x: 80  , y: 30 },
-                        Dot { x: 90  , y: 40 },
-                        Dot { x: 100 , y: 45 },
-                        Dot { x: 110 , y: 50 },
-                    ],
+                    [100.0, 100.0, 100.0, 100.0, 105.0, 110.0, 120.0, 130.0, 135.0, 130.0, 120.0, 110.0, 105.0, 100.0, 100.0, 100.0, 100.0].iter()
+                        .enumerate()
+                        .map(|(i, y)| Dot { x: (i * 10) as f64, y: *y })
+                        .collect(),
+                    [100.0, 100.0, 100.0, 100.0, 105.0, 110.0, 120.0, 130.0, 135.0, 130.0, 120.0, 110.0, 105.0, 100.0, 100.0, 100.0, 100.0].iter()
+                        .enumerate()
+                        .map(|(i, y)| Dot { x: (i * 10) as f64, y: 150.0 - y })
+                        .collect(),
                 ),
                 vec![
-                    Bond { x: 50, y: 130 },
-                    Bond { x: 50, y: 20  },
-                    Bond { x: 60, y: 135 },
-                    Bond { x: 60, y: 15  },
-                    Bond { x: 70, y: 130 },
-                    Bond { x: 70, y: 20  }
+                    EmissionSegment {
+                        start: 60,
+                        end: 100,
+                        first: 6,
+                        last: 10,
+                        upper: vec![15.0, 25.0, 30.0, 25.0, 15.0],
+                        lower: vec![-15.0, -25.0, -30.0, -25.0, -15.0],
+                        peak: 60.0,
+                        area: 2200.0,
+                        side: DefectSide::Both,
+                    },
                 ]
-            )
+            ),
+            (
+                2,
+                Threshold(1.1),
+                // Upper edge raised on the columns 30 and 50 merged over the gap, single column 100 dropped
+                InitialPoints::new(
+                    [40.0, 40.0, 40.0, 50.0, 40.0, 50.0, 40.0, 40.0, 40.0, 40.0, 52.0, 40.0, 40.0, 40.0, 40.0].iter()
+                        .enumerate()
+                        .map(|(i, width)| Dot { x: (i * 10) as f64, y: 60.0 + width })
+                        .collect(),
+                    (0..15).map(|i| Dot { x: (i * 10) as f64, y: 60.0 }).collect(),
+                ),
+                vec![
+                    EmissionSegment {
+                        start: 30,
+                        end: 50,
+                        first: 3,
+                        last: 5,
+                        upper: vec![10.0, 0.0, 10.0],
+                        lower: vec![0.0, 0.0, 0.0],
+                        peak: 10.0,
+                        area: 200.0,
+                        side: DefectSide::Upper,
+                    },
+                ]
+            ),
+            (
+                3,
+                Threshold(1.1),
+                // Tilted rope, upper edge raised on the columns 60 and 70, the lower edge not moved
+                InitialPoints::new(
+                    (0..15).map(|i| (i * 10) as f64).map(|x| Dot { x, y: 60.0 + 0.2 * x + if x == 60.0 || x == 70.0 { 8.0 } else { 0.0 } }).collect(),
+                    (0..15).map(|i| (i * 10) as f64).map(|x| Dot { x, y: 20.0 + 0.2 * x }).collect(),
+                ),
+                vec![
+                    EmissionSegment {
+                        start: 60,
+                        end: 70,
+                        first: 6,
+                        last: 7,
+                        upper: vec![8.0 / 1.04f64.sqrt(); 2],
+                        lower: vec![0.0, 0.0],
+                        peak: 8.0,
+                        area: 160.0,
+                        side: DefectSide::Upper,
+                    },
+                ]
+            ),
+            (
+                4,
+                Threshold(1.1),
+                // Emissions on the both sides of the 100 columns occlusion aren't merged
+                InitialPoints::new(
+                    (0..20).chain(121..141).map(|x| Dot { x: x as f64, y: 100.0 + if [18, 19, 121, 122].contains(&x) { 10.0 } else { 0.0 } }).collect(),
+                    (0..20).chain(121..141).map(|x| Dot { x: x as f64, y: 60.0 }).collect(),
+                ),
+                vec![
+                    EmissionSegment {
+                        start: 18,
+                        end: 19,
+                        first: 18,
+                        last: 19,
+                        upper: vec![10.0, 10.0],
+                        lower: vec![0.0, 0.0],
+                        peak: 10.0,
+                        area: 20.0,
+                        side: DefectSide::Upper,
+                    },
+                    EmissionSegment {
+                        start: 121,
+                        end: 122,
+                        first: 20,
+                        last: 21,
+                        upper: vec![10.0, 10.0],
+                        lower: vec![0.0, 0.0],
+                        peak: 10.0,
+                        area: 20.0,
+                        side: DefectSide::Upper,
+                    },
+                ]
+            ),
         ];
         for (step, threshold, initial_points, target) in test_data {
             let mut ctx = MocEval {
@@ -93,11 +160,14 @@ mod width_emissions {
             };
             ctx.ctx = ctx.ctx
                 .clone()
-                .write(EdgeDetectionCtx { result: initial_points })
+                .write(EdgeDetectionCtx { result: initial_points, ..Default::default() })
                 .unwrap();
             let result = WidthEmissions::new(
                 threshold,
+                2,
+                19,
                 *Box::new(Mad::new()),
+                Ransac::default(),
                 ctx,
             ).eval(());
             match result {
@@ -105,7 +175,7 @@ mod width_emissions {
                     let result = ContextRead::<WidthEmissionsCtx>::read(&result)
                         .result.clone();
                     assert!(
-                        result == target, 
+                        result.len() == target.len() && result.iter().zip(&target).all(|(result, target)| similar(result, target)),
                         "step {} \nresult: {:?}\ntarget: {:?}", 
                         step, 
                         result, 
@@ -118,6 +188,17 @@ mod width_emissions {
         test_duration.exit();
     }
     ///
+    /// Returns true if the segments are equal, the profiles and the measures within the rounding error
+    fn similar(result: &EmissionSegment, target: &EmissionSegment) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let profile = |a: &[f64], b: &[f64]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| close(*a, *b));
+        (result.start, result.end, result.first, result.last, result.side) == (target.start, target.end, target.first, target.last, target.side)
+            && profile(&result.upper, &target.upper)
+            && profile(&result.lower, &target.lower)
+            && close(result.peak, target.peak)
+            && close(result.area, target.area)
+    }
+    ///
     ///
     #[derive(Debug, Clone)]
     struct MocEval {